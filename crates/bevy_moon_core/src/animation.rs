@@ -0,0 +1,355 @@
+//! The animatable properties of the nodes, for the clips of [`bevy_animation`].
+//!
+//! The fields of a [`Div`] are not [`Animatable`](bevy_animation::animatable::Animatable), so the
+//! animation curves target the fields of an [`AnimatedDiv`], which are written into the
+//! [`ComputedDiv`], the [`Image`] and the [`TextColor`] of the node after the animations have been
+//! evaluated, before the layout. The [`Div`] is left as the user has set it.
+//!
+//! ```ignore
+//! let mut clip = AnimationClip::default();
+//! clip.add_curve_to_target(
+//!     target_id,
+//!     AnimatableCurve::new(
+//!         DivBackground,
+//!         EasingCurve::new(LinearRgba::WHITE, LinearRgba::RED, EaseFunction::SineInOut)
+//!             .ping_pong()
+//!             .unwrap(),
+//!     ),
+//! );
+//!
+//! commands.spawn((div().size(24.0).rounded_full(), AnimatedDiv::default(), target_id, ...));
+//! ```
+//!
+//! Only the fields which are animated are written, the others are left as they are. When the
+//! clip of a field ends, the [`ComputedDiv`] is resolved from the [`Div`] again.
+
+use core::any::TypeId;
+
+use bevy_animation::{
+    AnimationEntityMut, AnimationEvaluationError,
+    animation_curves::{AnimatableProperty, EvaluatorId},
+};
+use bevy_color::{Color, LinearRgba};
+use bevy_ecs::{
+    change_detection::DetectChangesMut, component::Component, prelude::ReflectComponent,
+    system::Query,
+};
+use bevy_math::{Vec2, Vec4};
+use bevy_reflect::{Reflect, prelude::ReflectDefault};
+use bevy_text::TextColor;
+use smallvec::smallvec;
+use taffy::{Dimension, Size};
+
+use crate::{
+    components::{computed::ComputedDiv, div::Div, image::Image},
+    style::{BoxShadow, Corners},
+    units::LengthProperty,
+};
+
+/// The animated values of a node, see the [module](self) docs.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct AnimatedDiv {
+    pub background: LinearRgba,
+    pub border_color: LinearRgba,
+
+    /// The width and height, in layout units.
+    pub size: Vec2,
+
+    /// The radii of the corners: top-left, top-right, bottom-right and bottom-left.
+    pub corner_radii: Vec4,
+
+    /// The color of the first shadow, which is added when the node has no shadows.
+    pub shadow_color: LinearRgba,
+    pub shadow_offset: Vec2,
+    pub shadow_blur_radius: f32,
+    pub shadow_spread_radius: f32,
+
+    /// The color of the [`Image`] of the node.
+    pub image_color: LinearRgba,
+
+    /// The [`TextColor`] of the node.
+    pub text_color: LinearRgba,
+
+    /// The fields which have been animated since the last update.
+    #[reflect(ignore)]
+    animated: AnimatedFields,
+
+    /// The fields which have been written into the node.
+    #[reflect(ignore)]
+    applied: AnimatedFields,
+}
+
+bitflags::bitflags! {
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    struct AnimatedFields: u16 {
+        const BACKGROUND = 1 << 0;
+        const BORDER_COLOR = 1 << 1;
+        const SIZE = 1 << 2;
+        const CORNER_RADII = 1 << 3;
+        const SHADOW_COLOR = 1 << 4;
+        const SHADOW_OFFSET = 1 << 5;
+        const SHADOW_BLUR_RADIUS = 1 << 6;
+        const SHADOW_SPREAD_RADIUS = 1 << 7;
+        const IMAGE_COLOR = 1 << 8;
+        const TEXT_COLOR = 1 << 9;
+    }
+}
+
+macro_rules! animatable_properties {
+    ($($(#[$meta:meta])* $name:ident => $field:ident: $ty:ty, $flag:ident;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Clone, Copy, Debug, Default, Reflect)]
+            #[reflect(Clone, Debug, Default)]
+            pub struct $name;
+
+            impl AnimatableProperty for $name {
+                type Property = $ty;
+
+                fn get_mut<'a>(
+                    &self,
+                    entity: &'a mut AnimationEntityMut,
+                ) -> Result<&'a mut Self::Property, AnimationEvaluationError> {
+                    let animated_div = entity.get_mut::<AnimatedDiv>().ok_or(
+                        AnimationEvaluationError::ComponentNotPresent(TypeId::of::<AnimatedDiv>()),
+                    )?;
+                    let animated_div = animated_div.into_inner();
+
+                    animated_div.animated.insert(AnimatedFields::$flag);
+                    Ok(&mut animated_div.$field)
+                }
+
+                fn evaluator_id(&self) -> EvaluatorId<'_> {
+                    EvaluatorId::Type(TypeId::of::<Self>())
+                }
+            }
+        )*
+    };
+}
+
+animatable_properties! {
+    /// Animates [`Div::background`].
+    DivBackground => background: LinearRgba, BACKGROUND;
+    /// Animates [`Div::border_color`].
+    DivBorderColor => border_color: LinearRgba, BORDER_COLOR;
+    /// Animates the width and height of the [`Div`].
+    DivSize => size: Vec2, SIZE;
+    /// Animates [`Div::corner_radii`].
+    DivCornerRadii => corner_radii: Vec4, CORNER_RADII;
+    /// Animates the color of the first shadow of the [`Div`].
+    DivShadowColor => shadow_color: LinearRgba, SHADOW_COLOR;
+    /// Animates the offset of the first shadow of the [`Div`].
+    DivShadowOffset => shadow_offset: Vec2, SHADOW_OFFSET;
+    /// Animates the blur radius of the first shadow of the [`Div`].
+    DivShadowBlurRadius => shadow_blur_radius: f32, SHADOW_BLUR_RADIUS;
+    /// Animates the spread radius of the first shadow of the [`Div`].
+    DivShadowSpreadRadius => shadow_spread_radius: f32, SHADOW_SPREAD_RADIUS;
+    /// Animates [`Image::color`].
+    DivImageColor => image_color: LinearRgba, IMAGE_COLOR;
+    /// Animates the [`TextColor`].
+    DivTextColor => text_color: LinearRgba, TEXT_COLOR;
+}
+
+/// Resolves the [`ComputedDiv`] of the nodes again, when the clips of their fields have ended.
+pub fn restore_div_animations(mut div_query: Query<(&AnimatedDiv, &mut Div)>) {
+    for (animated_div, mut div) in &mut div_query {
+        // The image and text colors are not resolved from the node.
+        let ended = animated_div.applied
+            - animated_div.animated
+            - (AnimatedFields::IMAGE_COLOR | AnimatedFields::TEXT_COLOR);
+
+        if !ended.is_empty() {
+            div.set_changed();
+        }
+    }
+}
+
+/// Writes the animated values into the nodes, and clears the animated fields.
+///
+/// The [`ComputedDiv`] is only marked as changed when the animated size is changed, as its other
+/// fields are read every frame.
+pub fn apply_div_animations(
+    mut div_query: Query<(
+        &mut AnimatedDiv,
+        &mut ComputedDiv,
+        Option<&mut Image>,
+        Option<&mut TextColor>,
+    )>,
+) {
+    for (mut animated_div, mut computed_div, image, text_color) in &mut div_query {
+        let animated_div = animated_div.bypass_change_detection();
+        let animated = core::mem::take(&mut animated_div.animated);
+        animated_div.applied = animated;
+
+        if animated.is_empty() {
+            continue;
+        }
+
+        if animated.contains(AnimatedFields::SIZE) {
+            let size = Size {
+                width: Dimension::length(animated_div.size.x),
+                height: Dimension::length(animated_div.size.y),
+            };
+
+            if computed_div.style.size != size
+                || computed_div.is_relative(LengthProperty::Width)
+                || computed_div.is_relative(LengthProperty::Height)
+            {
+                computed_div.0.style.size = size;
+                computed_div.0.clear_relative_lengths(&LengthProperty::SIZE);
+            }
+        }
+
+        let div = &mut computed_div.bypass_change_detection().0;
+
+        if animated.contains(AnimatedFields::BACKGROUND) {
+            div.background = Some(animated_div.background.into());
+        }
+
+        if animated.contains(AnimatedFields::BORDER_COLOR) {
+            div.border_color = Some(animated_div.border_color.into());
+        }
+
+        if animated.contains(AnimatedFields::CORNER_RADII) {
+            let [top_left, top_right, bottom_right, bottom_left] =
+                animated_div.corner_radii.to_array();
+            div.corner_radii = Corners {
+                top_left,
+                top_right,
+                bottom_right,
+                bottom_left,
+            };
+        }
+
+        let shadow_fields = AnimatedFields::SHADOW_COLOR
+            | AnimatedFields::SHADOW_OFFSET
+            | AnimatedFields::SHADOW_BLUR_RADIUS
+            | AnimatedFields::SHADOW_SPREAD_RADIUS;
+
+        if animated.intersects(shadow_fields) {
+            let shadows = div.box_shadow.get_or_insert_with(|| {
+                smallvec![BoxShadow {
+                    color: Color::NONE,
+                    ..Default::default()
+                }]
+            });
+
+            if let Some(shadow) = shadows.first_mut() {
+                if animated.contains(AnimatedFields::SHADOW_COLOR) {
+                    shadow.color = animated_div.shadow_color.into();
+                }
+                if animated.contains(AnimatedFields::SHADOW_OFFSET) {
+                    shadow.offset = animated_div.shadow_offset;
+                }
+                if animated.contains(AnimatedFields::SHADOW_BLUR_RADIUS) {
+                    shadow.blur_radius = animated_div.shadow_blur_radius;
+                }
+                if animated.contains(AnimatedFields::SHADOW_SPREAD_RADIUS) {
+                    shadow.spread_radius = animated_div.shadow_spread_radius;
+                }
+            }
+        }
+
+        if let Some(mut image) = image.filter(|_| animated.contains(AnimatedFields::IMAGE_COLOR)) {
+            image.bypass_change_detection().color = animated_div.image_color.into();
+        }
+
+        if let Some(mut text_color) =
+            text_color.filter(|_| animated.contains(AnimatedFields::TEXT_COLOR))
+        {
+            text_color.bypass_change_detection().0 = animated_div.text_color.into();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{schedule::Schedule, world::World};
+
+    use super::*;
+    use crate::{components::computed::update_computed_divs, prelude::div, theme::MoonTheme};
+
+    #[test]
+    fn test_apply_div_animations() {
+        let mut world = World::new();
+        world.init_resource::<MoonTheme>();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                restore_div_animations,
+                update_computed_divs,
+                apply_div_animations,
+            )
+                .chain(),
+        );
+
+        let node = world
+            .spawn((
+                div().w(10.0).h(20.0).background(Color::WHITE),
+                TextColor(Color::BLACK),
+                AnimatedDiv::default(),
+            ))
+            .id();
+        schedule.run(&mut world);
+
+        // Returns the computed style, and whether its layout has been changed.
+        let mut animate = |world: &mut World, animated: AnimatedFields| {
+            world.clear_trackers();
+            *world.get_mut::<AnimatedDiv>(node).unwrap() = AnimatedDiv {
+                size: Vec2::new(30.0, 40.0),
+                shadow_blur_radius: 8.0,
+                text_color: LinearRgba::RED,
+                animated,
+                ..Default::default()
+            };
+            schedule.run(world);
+
+            let computed_div = world.entity(node).get_ref::<ComputedDiv>().unwrap();
+            (computed_div.0.clone(), computed_div.is_changed())
+        };
+
+        let (current, changed) = animate(
+            &mut world,
+            AnimatedFields::SIZE | AnimatedFields::SHADOW_BLUR_RADIUS | AnimatedFields::TEXT_COLOR,
+        );
+        assert_eq!(current.style.size.width, Dimension::length(30.0));
+        assert_eq!(current.style.size.height, Dimension::length(40.0));
+        assert!(changed);
+        // The fields which are not animated are left as they are.
+        assert_eq!(current.background, Some(Color::WHITE));
+        assert_eq!(current.corner_radii, Corners::DEFAULT);
+
+        let shadows = current.box_shadow.as_ref().unwrap();
+        assert_eq!(shadows.len(), 1);
+        assert_eq!(shadows[0].blur_radius, 8.0);
+        assert_eq!(shadows[0].color, Color::NONE);
+
+        assert_eq!(
+            world.get::<TextColor>(node).unwrap().0,
+            Color::LinearRgba(LinearRgba::RED)
+        );
+
+        // The node is left alone, and the animated fields are cleared.
+        assert_eq!(
+            world.get::<Div>(node),
+            Some(&div().w(10.0).h(20.0).background(Color::WHITE))
+        );
+        assert!(world.get::<AnimatedDiv>(node).unwrap().animated.is_empty());
+
+        // The paint-only fields do not change the layout.
+        let (current, changed) = animate(
+            &mut world,
+            AnimatedFields::SIZE | AnimatedFields::SHADOW_BLUR_RADIUS,
+        );
+        assert_eq!(current.box_shadow.as_ref().unwrap()[0].blur_radius, 8.0);
+        assert!(!changed);
+
+        // The style of the node is restored when the clips have ended.
+        let (current, changed) = animate(&mut world, AnimatedFields::empty());
+        assert_eq!(current.style.size.width, Dimension::length(10.0));
+        assert_eq!(current.box_shadow, None);
+        assert!(changed);
+    }
+}
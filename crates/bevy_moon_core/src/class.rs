@@ -0,0 +1,989 @@
+//! Applies the Tailwind classes to the nodes.
+//!
+//! ```ignore
+//! div().class("flex flex-col w-64 h-32 p-4 gap-2 bg-white rounded-lg shadow-md")
+//! ```
+//!
+//! The common utilities are supported: display, position, flex, grid, alignment, overflow,
+//! spacing, sizing, insets, borders, outlines, rounding, shadows and colors.
+//! A step of the spacing scale is 4 layout units, `0.25rem` of `16px`.
+//! Arbitrary values are supported as well, such as `w-[120px]`, `h-[50%]`, `bg-[#0ea5e9]` and
+//! `grid-cols-[200px_1fr]`, where the underscores of a grid template are spaces.
+
+use bevy_color::{Alpha, Color, Srgba, palettes::tailwind::*};
+use taffy::{
+    AlignContent, AlignItems, Dimension, Display, FlexDirection, FlexWrap, GridAutoFlow,
+    GridPlacement, JustifyContent, LengthPercentage, LengthPercentageAuto, Line, Overflow, Point,
+    Position, Rect,
+    style_helpers::{line, span},
+};
+
+use crate::{
+    components::div::Div,
+    grid::{TrackList, evenly_sized_tracks, full_line, parse_track_list},
+    style::{BorderStyle, BoxShadow, Corners, Edges},
+    units::LengthProperty,
+};
+
+/// The layout units per step of the spacing scale.
+const SPACING: f32 = 4.0;
+
+/// `[top, right, bottom, left]`
+type Sides = [bool; 4];
+
+const ALL_SIDES: Sides = [true; 4];
+
+/// `[top_left, top_right, bottom_right, bottom_left]`
+type CornerMask = [bool; 4];
+
+const ALL_CORNERS: CornerMask = [true; 4];
+
+impl Div {
+    /// Applies a whitespace-separated list of Tailwind classes, the later classes win.
+    ///
+    /// Warns about the classes which are not supported, see [`Div::apply_classes`].
+    pub fn class(mut self, classes: &str) -> Self {
+        self.apply_classes(classes, |class| {
+            tracing::warn!("unknown class `{class}`");
+        });
+        self
+    }
+
+    /// Applies a whitespace-separated list of Tailwind classes,
+    /// and calls `on_unknown` with each class which is not supported.
+    pub fn apply_classes<'a>(&mut self, classes: &'a str, mut on_unknown: impl FnMut(&'a str)) {
+        for class in classes.split_whitespace() {
+            if apply_class(self, class).is_none() {
+                on_unknown(class);
+            }
+        }
+    }
+}
+
+fn apply_class(div: &mut Div, class: &str) -> Option<()> {
+    let style = &mut div.style.0;
+
+    match class {
+        "flex" => style.display = Display::Flex,
+        "block" => style.display = Display::Block,
+        "grid" => style.display = Display::Grid,
+        "hidden" => style.display = Display::None,
+
+        "relative" => style.position = Position::Relative,
+        "absolute" => style.position = Position::Absolute,
+
+        "flex-row" => style.flex_direction = FlexDirection::Row,
+        "flex-col" => style.flex_direction = FlexDirection::Column,
+        "flex-row-reverse" => style.flex_direction = FlexDirection::RowReverse,
+        "flex-col-reverse" => style.flex_direction = FlexDirection::ColumnReverse,
+        "flex-wrap" => style.flex_wrap = FlexWrap::Wrap,
+        "flex-wrap-reverse" => style.flex_wrap = FlexWrap::WrapReverse,
+        "flex-nowrap" => style.flex_wrap = FlexWrap::NoWrap,
+        "flex-1" => set_flex(div, 1.0, 1.0, Dimension::percent(0.0)),
+        "flex-auto" => set_flex(div, 1.0, 1.0, Dimension::auto()),
+        "flex-initial" => set_flex(div, 0.0, 1.0, Dimension::auto()),
+        "flex-none" => set_flex(div, 0.0, 0.0, Dimension::auto()),
+        "grow" => style.flex_grow = 1.0,
+        "grow-0" => style.flex_grow = 0.0,
+        "shrink" => style.flex_shrink = 1.0,
+        "shrink-0" => style.flex_shrink = 0.0,
+
+        "grid-flow-row" => style.grid_auto_flow = GridAutoFlow::Row,
+        "grid-flow-col" => style.grid_auto_flow = GridAutoFlow::Column,
+        "grid-flow-dense" | "grid-flow-row-dense" => style.grid_auto_flow = GridAutoFlow::RowDense,
+        "grid-flow-col-dense" => style.grid_auto_flow = GridAutoFlow::ColumnDense,
+        "grid-cols-none" => style.grid_template_columns = Vec::new(),
+        "grid-rows-none" => style.grid_template_rows = Vec::new(),
+        "col-auto" => style.grid_column = Line::default(),
+        "row-auto" => style.grid_row = Line::default(),
+        "col-span-full" => style.grid_column = full_line(),
+        "row-span-full" => style.grid_row = full_line(),
+
+        "items-start" => style.align_items = Some(AlignItems::FlexStart),
+        "items-end" => style.align_items = Some(AlignItems::FlexEnd),
+        "items-center" => style.align_items = Some(AlignItems::Center),
+        "items-baseline" => style.align_items = Some(AlignItems::Baseline),
+        "items-stretch" => style.align_items = Some(AlignItems::Stretch),
+        "self-auto" => style.align_self = None,
+        "self-start" => style.align_self = Some(AlignItems::FlexStart),
+        "self-end" => style.align_self = Some(AlignItems::FlexEnd),
+        "self-center" => style.align_self = Some(AlignItems::Center),
+        "self-baseline" => style.align_self = Some(AlignItems::Baseline),
+        "self-stretch" => style.align_self = Some(AlignItems::Stretch),
+        "justify-items-start" => style.justify_items = Some(AlignItems::Start),
+        "justify-items-end" => style.justify_items = Some(AlignItems::End),
+        "justify-items-center" => style.justify_items = Some(AlignItems::Center),
+        "justify-items-stretch" => style.justify_items = Some(AlignItems::Stretch),
+
+        "aspect-auto" => style.aspect_ratio = None,
+        "aspect-square" => style.aspect_ratio = Some(1.0),
+        "aspect-video" => style.aspect_ratio = Some(16.0 / 9.0),
+
+        "overflow-visible" => style.overflow = both(Overflow::Visible),
+        "overflow-hidden" => style.overflow = both(Overflow::Hidden),
+        "overflow-clip" => style.overflow = both(Overflow::Clip),
+        "overflow-scroll" | "overflow-auto" => style.overflow = both(Overflow::Scroll),
+        "overflow-x-visible" => style.overflow.x = Overflow::Visible,
+        "overflow-x-hidden" => style.overflow.x = Overflow::Hidden,
+        "overflow-x-clip" => style.overflow.x = Overflow::Clip,
+        "overflow-x-scroll" | "overflow-x-auto" => style.overflow.x = Overflow::Scroll,
+        "overflow-y-visible" => style.overflow.y = Overflow::Visible,
+        "overflow-y-hidden" => style.overflow.y = Overflow::Hidden,
+        "overflow-y-clip" => style.overflow.y = Overflow::Clip,
+        "overflow-y-scroll" | "overflow-y-auto" => style.overflow.y = Overflow::Scroll,
+
+        "border" => {
+            style.border = Rect::length(1.0);
+            div.clear_relative_lengths(&LengthProperty::BORDER);
+        }
+        "border-solid" => div.border_style = BorderStyle::Solid,
+        "border-dashed" => div.border_style = BorderStyle::DASHED,
+        "border-dotted" => div.border_style = BorderStyle::DOTTED,
+        "border-double" => div.border_style = BorderStyle::Double,
+        "rounded" => div.corner_radii = Corners::all(4.0),
+
+        "shadow" => div.set_shadows(false, &BoxShadow::SM),
+        "shadow-none" => div.set_shadows(false, &[]),
+        "shadow-2xs" => div.set_shadows(false, &BoxShadow::XS2),
+        "shadow-xs" => div.set_shadows(false, &BoxShadow::XS),
+        "shadow-sm" => div.set_shadows(false, &BoxShadow::SM),
+        "shadow-md" => div.set_shadows(false, &BoxShadow::MD),
+        "shadow-lg" => div.set_shadows(false, &BoxShadow::LG),
+        "shadow-xl" => div.set_shadows(false, &BoxShadow::XL),
+        "shadow-2xl" => div.set_shadows(false, &BoxShadow::XL2),
+        "inset-shadow-none" => div.set_shadows(true, &[]),
+        "inset-shadow-2xs" => div.set_shadows(true, &BoxShadow::INSET_XS2),
+        "inset-shadow-xs" => div.set_shadows(true, &BoxShadow::INSET_XS),
+        "inset-shadow-sm" => div.set_shadows(true, &BoxShadow::INSET_SM),
+
+        "outline" => div.outline = Some(div.outline.unwrap_or_default()),
+        "outline-none" => div.outline = None,
+
+        _ => return apply_utility(div, class),
+    }
+
+    Some(())
+}
+
+/// Applies the classes which take a value.
+fn apply_utility(div: &mut Div, class: &str) -> Option<()> {
+    // Only the margins and the insets take negative values.
+    if let Some(class) = class.strip_prefix('-') {
+        return apply_offset(div, class, true);
+    }
+    if apply_offset(div, class, false).is_some() {
+        return Some(());
+    }
+
+    let style = &mut div.style.0;
+
+    if let Some(rest) = class.strip_prefix('p') {
+        let (sides, value) = split_sides(rest)?;
+        let padding = Length::parse(value)?.length_percentage()?;
+        set_sides(&mut style.padding, sides, padding);
+        clear_sides(div, LengthProperty::PADDING, sides);
+    } else if let Some(value) = class.strip_prefix("gap-x-") {
+        style.gap.width = Length::parse(value)?.length_percentage()?;
+        div.clear_relative_lengths(&[LengthProperty::GapX]);
+    } else if let Some(value) = class.strip_prefix("gap-y-") {
+        style.gap.height = Length::parse(value)?.length_percentage()?;
+        div.clear_relative_lengths(&[LengthProperty::GapY]);
+    } else if let Some(value) = class.strip_prefix("gap-") {
+        let gap = Length::parse(value)?.length_percentage()?;
+        style.gap.width = gap;
+        style.gap.height = gap;
+        div.clear_relative_lengths(&LengthProperty::GAP);
+    } else if let Some(value) = class.strip_prefix("w-") {
+        style.size.width = Length::parse_width(value)?.dimension();
+        div.clear_relative_lengths(&[LengthProperty::Width]);
+    } else if let Some(value) = class.strip_prefix("h-") {
+        style.size.height = Length::parse(value)?.dimension();
+        div.clear_relative_lengths(&[LengthProperty::Height]);
+    } else if let Some(value) = class.strip_prefix("size-") {
+        let size = Length::parse(value)?.dimension();
+        style.size.width = size;
+        style.size.height = size;
+        div.clear_relative_lengths(&LengthProperty::SIZE);
+    } else if let Some(value) = class.strip_prefix("min-w-") {
+        style.min_size.width = Length::parse_width(value)?.dimension();
+        div.clear_relative_lengths(&[LengthProperty::MinWidth]);
+    } else if let Some(value) = class.strip_prefix("min-h-") {
+        style.min_size.height = Length::parse(value)?.dimension();
+        div.clear_relative_lengths(&[LengthProperty::MinHeight]);
+    } else if let Some(value) = class.strip_prefix("max-w-") {
+        style.max_size.width = match value {
+            "none" => Dimension::auto(),
+            _ => Length::parse_width(value)?.dimension(),
+        };
+        div.clear_relative_lengths(&[LengthProperty::MaxWidth]);
+    } else if let Some(value) = class.strip_prefix("max-h-") {
+        style.max_size.height = match value {
+            "none" => Dimension::auto(),
+            _ => Length::parse(value)?.dimension(),
+        };
+        div.clear_relative_lengths(&[LengthProperty::MaxHeight]);
+    } else if let Some(value) = class.strip_prefix("basis-") {
+        style.flex_basis = Length::parse_width(value)?.dimension();
+        div.clear_relative_lengths(&[LengthProperty::FlexBasis]);
+    } else if let Some(value) = class.strip_prefix("grow-") {
+        style.flex_grow = parse_number(value)?;
+    } else if let Some(value) = class.strip_prefix("shrink-") {
+        style.flex_shrink = parse_number(value)?;
+    } else if let Some(value) = class.strip_prefix("justify-") {
+        style.justify_content = Some(parse_content(value)?);
+    } else if let Some(value) = class.strip_prefix("content-") {
+        style.align_content = Some(parse_content(value)?);
+    } else if let Some(value) = class.strip_prefix("aspect-") {
+        let (width, height) = arbitrary(value)?.split_once('/')?;
+        let (width, height) = (parse_number(width)?, parse_number(height)?);
+        style.aspect_ratio = Some(width / height).filter(|ratio| ratio.is_normal());
+    } else if let Some(value) = class.strip_prefix("grid-cols-") {
+        (
+            style.grid_template_columns,
+            style.grid_template_column_names,
+        ) = parse_template(value)?;
+    } else if let Some(value) = class.strip_prefix("grid-rows-") {
+        (style.grid_template_rows, style.grid_template_row_names) = parse_template(value)?;
+    } else if let Some(value) = class.strip_prefix("col-span-") {
+        style.grid_column = Line {
+            start: span(parse_count(value)?),
+            end: GridPlacement::Auto,
+        };
+    } else if let Some(value) = class.strip_prefix("row-span-") {
+        style.grid_row = Line {
+            start: span(parse_count(value)?),
+            end: GridPlacement::Auto,
+        };
+    } else if let Some(value) = class.strip_prefix("col-start-") {
+        style.grid_column.start = parse_line(value)?;
+    } else if let Some(value) = class.strip_prefix("col-end-") {
+        style.grid_column.end = parse_line(value)?;
+    } else if let Some(value) = class.strip_prefix("row-start-") {
+        style.grid_row.start = parse_line(value)?;
+    } else if let Some(value) = class.strip_prefix("row-end-") {
+        style.grid_row.end = parse_line(value)?;
+    } else if let Some(value) = class.strip_prefix("border-") {
+        apply_border(div, value)?;
+    } else if let Some(value) = class.strip_prefix("outline-") {
+        apply_outline(div, value)?;
+    } else if let Some(value) = class.strip_prefix("rounded-") {
+        apply_rounded(div, value)?;
+    } else if let Some(value) = class.strip_prefix("bg-") {
+        div.background = Some(parse_color(value)?);
+    } else {
+        return None;
+    }
+
+    Some(())
+}
+
+/// Applies the margins and the insets.
+fn apply_offset(div: &mut Div, class: &str, negative: bool) -> Option<()> {
+    let length = |value: &str| {
+        let length = Length::parse(value)?;
+        Some(if negative { length.neg() } else { length }.length_percentage_auto())
+    };
+
+    let (sides, value) = if let Some(rest) = class.strip_prefix("inset-") {
+        match rest.split_once('-') {
+            Some((sides @ ("x" | "y"), value)) => (parse_sides(sides)?, value),
+            _ => (ALL_SIDES, rest),
+        }
+    } else if let Some(value) = class.strip_prefix("top-") {
+        ([true, false, false, false], value)
+    } else if let Some(value) = class.strip_prefix("right-") {
+        ([false, true, false, false], value)
+    } else if let Some(value) = class.strip_prefix("bottom-") {
+        ([false, false, true, false], value)
+    } else if let Some(value) = class.strip_prefix("left-") {
+        ([false, false, false, true], value)
+    } else if let Some(rest) = class.strip_prefix('m') {
+        let (sides, value) = split_sides(rest)?;
+        set_sides(&mut div.style.margin, sides, length(value)?);
+        clear_sides(div, LengthProperty::MARGIN, sides);
+        return Some(());
+    } else {
+        return None;
+    };
+
+    set_sides(&mut div.style.inset, sides, length(value)?);
+    clear_sides(div, LengthProperty::INSET, sides);
+    Some(())
+}
+
+/// Applies `border-2`, `border-t`, `border-x-4`, `border-sky-500` and `border-t-sky-500`.
+fn apply_border(div: &mut Div, value: &str) -> Option<()> {
+    let (sides, width) = if let Some(width) = parse_border_width(value) {
+        (ALL_SIDES, width)
+    } else if let Some(sides) = parse_sides(value) {
+        (sides, 1.0)
+    } else if let Some((sides, width)) = value
+        .split_once('-')
+        .and_then(|(sides, width)| Some((parse_sides(sides)?, parse_border_width(width)?)))
+    {
+        (sides, width)
+    } else if let Some((sides, color)) = value
+        .split_once('-')
+        .and_then(|(sides, color)| Some((parse_sides(sides)?, parse_color(color)?)))
+    {
+        let [top, right, bottom, left] = sides;
+        let border_colors = &mut div.border_colors;
+        if top {
+            border_colors.top = Some(color);
+        }
+        if right {
+            border_colors.right = Some(color);
+        }
+        if bottom {
+            border_colors.bottom = Some(color);
+        }
+        if left {
+            border_colors.left = Some(color);
+        }
+        return Some(());
+    } else {
+        div.border_color = Some(parse_color(value)?);
+        div.border_colors = Edges::all(None);
+        return Some(());
+    };
+
+    set_sides(
+        &mut div.style.border,
+        sides,
+        LengthPercentage::length(width),
+    );
+    clear_sides(div, LengthProperty::BORDER, sides);
+    Some(())
+}
+
+/// Applies `outline-2`, `outline-offset-2`, `outline-dashed` and `outline-sky-500`.
+fn apply_outline(div: &mut Div, value: &str) -> Option<()> {
+    let mut outline = div.outline.unwrap_or_default();
+
+    if let Some(width) = parse_border_width(value) {
+        outline.width = width;
+    } else if let Some(offset) = value.strip_prefix("offset-") {
+        outline.offset = parse_border_width(offset)?;
+    } else {
+        match value {
+            "solid" => outline.style = BorderStyle::Solid,
+            "dashed" => outline.style = BorderStyle::DASHED,
+            "dotted" => outline.style = BorderStyle::DOTTED,
+            "double" => outline.style = BorderStyle::Double,
+            _ => outline.color = parse_color(value)?,
+        }
+    }
+
+    div.outline = Some(outline);
+    Some(())
+}
+
+/// Applies `rounded-lg`, `rounded-t` and `rounded-tl-xl`.
+fn apply_rounded(div: &mut Div, value: &str) -> Option<()> {
+    let (corners, radius) = if let Some(radius) = parse_radius(value) {
+        (ALL_CORNERS, radius)
+    } else if let Some(corners) = parse_corners(value) {
+        (corners, 4.0)
+    } else {
+        let (corners, radius) = value.split_once('-')?;
+        (parse_corners(corners)?, parse_radius(radius)?)
+    };
+
+    let [top_left, top_right, bottom_right, bottom_left] = corners;
+    let corner_radii = &mut div.corner_radii;
+    if top_left {
+        corner_radii.top_left = radius;
+    }
+    if top_right {
+        corner_radii.top_right = radius;
+    }
+    if bottom_right {
+        corner_radii.bottom_right = radius;
+    }
+    if bottom_left {
+        corner_radii.bottom_left = radius;
+    }
+
+    Some(())
+}
+
+fn set_flex(div: &mut Div, grow: f32, shrink: f32, basis: Dimension) {
+    div.style.flex_grow = grow;
+    div.style.flex_shrink = shrink;
+    div.style.flex_basis = basis;
+    div.clear_relative_lengths(&[LengthProperty::FlexBasis]);
+}
+
+fn both<T: Copy>(value: T) -> Point<T> {
+    Point { x: value, y: value }
+}
+
+fn set_sides<T: Copy>(rect: &mut Rect<T>, sides: Sides, value: T) {
+    let [top, right, bottom, left] = sides;
+    if top {
+        rect.top = value;
+    }
+    if right {
+        rect.right = value;
+    }
+    if bottom {
+        rect.bottom = value;
+    }
+    if left {
+        rect.left = value;
+    }
+}
+
+/// Drops the relative lengths of the sides, which are set to absolute lengths by a class.
+fn clear_sides(div: &mut Div, properties: [LengthProperty; 4], sides: Sides) {
+    for (property, side) in properties.into_iter().zip(sides) {
+        if side {
+            div.clear_relative_lengths(&[property]);
+        }
+    }
+}
+
+/// Splits `x-4` into the sides and the value, `-4` is for all sides.
+fn split_sides(rest: &str) -> Option<(Sides, &str)> {
+    match rest.strip_prefix('-') {
+        Some(value) => Some((ALL_SIDES, value)),
+        None => {
+            let (sides, value) = rest.split_once('-')?;
+            Some((parse_sides(sides)?, value))
+        }
+    }
+}
+
+/// The start and end sides are resolved as left-to-right.
+fn parse_sides(sides: &str) -> Option<Sides> {
+    Some(match sides {
+        "x" => [false, true, false, true],
+        "y" => [true, false, true, false],
+        "t" => [true, false, false, false],
+        "r" | "e" => [false, true, false, false],
+        "b" => [false, false, true, false],
+        "l" | "s" => [false, false, false, true],
+        _ => return None,
+    })
+}
+
+/// The start and end corners are resolved as left-to-right.
+fn parse_corners(corners: &str) -> Option<CornerMask> {
+    Some(match corners {
+        "t" => [true, true, false, false],
+        "r" | "e" => [false, true, true, false],
+        "b" => [false, false, true, true],
+        "l" | "s" => [true, false, false, true],
+        "tl" | "ss" => [true, false, false, false],
+        "tr" | "se" => [false, true, false, false],
+        "br" | "ee" => [false, false, true, false],
+        "bl" | "es" => [false, false, false, true],
+        _ => return None,
+    })
+}
+
+fn parse_radius(radius: &str) -> Option<f32> {
+    Some(match radius {
+        "none" => 0.0,
+        "xs" => 2.0,
+        "sm" => 4.0,
+        "md" => 6.0,
+        "lg" => 8.0,
+        "xl" => 12.0,
+        "2xl" => 16.0,
+        "3xl" => 24.0,
+        "4xl" => 32.0,
+        "full" => Corners::FULL.top_left,
+        _ => parse_pixels(arbitrary(radius)?)?,
+    })
+}
+
+/// The border widths are in pixels, such as `border-2` and `border-[3px]`.
+fn parse_border_width(width: &str) -> Option<f32> {
+    match arbitrary(width) {
+        Some(width) => parse_pixels(width),
+        None => parse_number(width),
+    }
+}
+
+fn parse_content(value: &str) -> Option<JustifyContent> {
+    Some(match value {
+        "start" => AlignContent::FlexStart,
+        "end" => AlignContent::FlexEnd,
+        "center" => AlignContent::Center,
+        "between" => AlignContent::SpaceBetween,
+        "around" => AlignContent::SpaceAround,
+        "evenly" => AlignContent::SpaceEvenly,
+        "stretch" => AlignContent::Stretch,
+        _ => return None,
+    })
+}
+
+fn parse_count(value: &str) -> Option<u16> {
+    value.parse().ok().filter(|count| *count > 0)
+}
+
+fn parse_line(value: &str) -> Option<GridPlacement> {
+    match value {
+        "auto" => Some(GridPlacement::Auto),
+        _ => value.parse().ok().filter(|index| *index != 0).map(line),
+    }
+}
+
+/// Parses `3` and `[200px_1fr]`, the underscores of an arbitrary template are spaces.
+fn parse_template(value: &str) -> Option<TrackList> {
+    match arbitrary(value) {
+        Some(template) => parse_track_list(&template.replace('_', " "), true).ok(),
+        None => Some((evenly_sized_tracks(parse_count(value)?), Vec::new())),
+    }
+}
+
+fn parse_number(value: &str) -> Option<f32> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|number| number.is_finite() && *number >= 0.0)
+}
+
+fn parse_pixels(value: &str) -> Option<f32> {
+    parse_number(value.strip_suffix("px").unwrap_or(value))
+}
+
+/// Returns the inner value of `[...]`.
+fn arbitrary(value: &str) -> Option<&str> {
+    value.strip_prefix('[')?.strip_suffix(']')
+}
+
+/// Parses `white`, `sky-500`, `sky-500/50` and `[#0ea5e9]`.
+pub(crate) fn parse_color(value: &str) -> Option<Color> {
+    let (value, opacity) = match value.split_once('/') {
+        Some((value, opacity)) => (
+            value,
+            parse_number(opacity).filter(|o| *o <= 100.0)? / 100.0,
+        ),
+        None => (value, 1.0),
+    };
+
+    let color = match value {
+        "transparent" => Color::NONE,
+        "white" => Color::WHITE,
+        "black" => Color::BLACK,
+        _ => match arbitrary(value) {
+            Some(hex) => Srgba::hex(hex).ok()?.into(),
+            None => {
+                let (name, shade) = value.rsplit_once('-')?;
+                tailwind_color(name, shade)?.into()
+            }
+        },
+    };
+
+    Some(color.with_alpha(color.alpha() * opacity))
+}
+
+/// Returns the color of the Tailwind palette, such as `("sky", "500")`.
+pub(crate) fn tailwind_color(name: &str, shade: &str) -> Option<Srgba> {
+    let index = match shade {
+        "50" => 0,
+        "100" => 1,
+        "200" => 2,
+        "300" => 3,
+        "400" => 4,
+        "500" => 5,
+        "600" => 6,
+        "700" => 7,
+        "800" => 8,
+        "900" => 9,
+        "950" => 10,
+        _ => return None,
+    };
+
+    let shades = match name {
+        "slate" => [
+            SLATE_50, SLATE_100, SLATE_200, SLATE_300, SLATE_400, SLATE_500, SLATE_600, SLATE_700,
+            SLATE_800, SLATE_900, SLATE_950,
+        ],
+        "gray" => [
+            GRAY_50, GRAY_100, GRAY_200, GRAY_300, GRAY_400, GRAY_500, GRAY_600, GRAY_700,
+            GRAY_800, GRAY_900, GRAY_950,
+        ],
+        "zinc" => [
+            ZINC_50, ZINC_100, ZINC_200, ZINC_300, ZINC_400, ZINC_500, ZINC_600, ZINC_700,
+            ZINC_800, ZINC_900, ZINC_950,
+        ],
+        "neutral" => [
+            NEUTRAL_50,
+            NEUTRAL_100,
+            NEUTRAL_200,
+            NEUTRAL_300,
+            NEUTRAL_400,
+            NEUTRAL_500,
+            NEUTRAL_600,
+            NEUTRAL_700,
+            NEUTRAL_800,
+            NEUTRAL_900,
+            NEUTRAL_950,
+        ],
+        "stone" => [
+            STONE_50, STONE_100, STONE_200, STONE_300, STONE_400, STONE_500, STONE_600, STONE_700,
+            STONE_800, STONE_900, STONE_950,
+        ],
+        "red" => [
+            RED_50, RED_100, RED_200, RED_300, RED_400, RED_500, RED_600, RED_700, RED_800,
+            RED_900, RED_950,
+        ],
+        "orange" => [
+            ORANGE_50, ORANGE_100, ORANGE_200, ORANGE_300, ORANGE_400, ORANGE_500, ORANGE_600,
+            ORANGE_700, ORANGE_800, ORANGE_900, ORANGE_950,
+        ],
+        "amber" => [
+            AMBER_50, AMBER_100, AMBER_200, AMBER_300, AMBER_400, AMBER_500, AMBER_600, AMBER_700,
+            AMBER_800, AMBER_900, AMBER_950,
+        ],
+        "yellow" => [
+            YELLOW_50, YELLOW_100, YELLOW_200, YELLOW_300, YELLOW_400, YELLOW_500, YELLOW_600,
+            YELLOW_700, YELLOW_800, YELLOW_900, YELLOW_950,
+        ],
+        "lime" => [
+            LIME_50, LIME_100, LIME_200, LIME_300, LIME_400, LIME_500, LIME_600, LIME_700,
+            LIME_800, LIME_900, LIME_950,
+        ],
+        "green" => [
+            GREEN_50, GREEN_100, GREEN_200, GREEN_300, GREEN_400, GREEN_500, GREEN_600, GREEN_700,
+            GREEN_800, GREEN_900, GREEN_950,
+        ],
+        "emerald" => [
+            EMERALD_50,
+            EMERALD_100,
+            EMERALD_200,
+            EMERALD_300,
+            EMERALD_400,
+            EMERALD_500,
+            EMERALD_600,
+            EMERALD_700,
+            EMERALD_800,
+            EMERALD_900,
+            EMERALD_950,
+        ],
+        "teal" => [
+            TEAL_50, TEAL_100, TEAL_200, TEAL_300, TEAL_400, TEAL_500, TEAL_600, TEAL_700,
+            TEAL_800, TEAL_900, TEAL_950,
+        ],
+        "cyan" => [
+            CYAN_50, CYAN_100, CYAN_200, CYAN_300, CYAN_400, CYAN_500, CYAN_600, CYAN_700,
+            CYAN_800, CYAN_900, CYAN_950,
+        ],
+        "sky" => [
+            SKY_50, SKY_100, SKY_200, SKY_300, SKY_400, SKY_500, SKY_600, SKY_700, SKY_800,
+            SKY_900, SKY_950,
+        ],
+        "blue" => [
+            BLUE_50, BLUE_100, BLUE_200, BLUE_300, BLUE_400, BLUE_500, BLUE_600, BLUE_700,
+            BLUE_800, BLUE_900, BLUE_950,
+        ],
+        "indigo" => [
+            INDIGO_50, INDIGO_100, INDIGO_200, INDIGO_300, INDIGO_400, INDIGO_500, INDIGO_600,
+            INDIGO_700, INDIGO_800, INDIGO_900, INDIGO_950,
+        ],
+        "violet" => [
+            VIOLET_50, VIOLET_100, VIOLET_200, VIOLET_300, VIOLET_400, VIOLET_500, VIOLET_600,
+            VIOLET_700, VIOLET_800, VIOLET_900, VIOLET_950,
+        ],
+        "purple" => [
+            PURPLE_50, PURPLE_100, PURPLE_200, PURPLE_300, PURPLE_400, PURPLE_500, PURPLE_600,
+            PURPLE_700, PURPLE_800, PURPLE_900, PURPLE_950,
+        ],
+        "fuchsia" => [
+            FUCHSIA_50,
+            FUCHSIA_100,
+            FUCHSIA_200,
+            FUCHSIA_300,
+            FUCHSIA_400,
+            FUCHSIA_500,
+            FUCHSIA_600,
+            FUCHSIA_700,
+            FUCHSIA_800,
+            FUCHSIA_900,
+            FUCHSIA_950,
+        ],
+        "pink" => [
+            PINK_50, PINK_100, PINK_200, PINK_300, PINK_400, PINK_500, PINK_600, PINK_700,
+            PINK_800, PINK_900, PINK_950,
+        ],
+        "rose" => [
+            ROSE_50, ROSE_100, ROSE_200, ROSE_300, ROSE_400, ROSE_500, ROSE_600, ROSE_700,
+            ROSE_800, ROSE_900, ROSE_950,
+        ],
+        _ => return None,
+    };
+
+    Some(shades[index])
+}
+
+/// A length of the spacing scale, a fraction or an arbitrary value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Length {
+    Px(f32),
+    Percent(f32),
+    Auto,
+}
+
+impl Length {
+    /// Parses `4`, `0.5`, `px`, `full`, `auto`, `1/2`, `[12px]` and `[50%]`.
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "auto" => return Some(Self::Auto),
+            "full" => return Some(Self::Percent(1.0)),
+            "px" => return Some(Self::Px(1.0)),
+            _ => {}
+        }
+
+        if let Some(value) = arbitrary(value) {
+            return match value.strip_suffix('%') {
+                Some(percent) => {
+                    parse_number(percent).map(|percent| Self::Percent(percent / 100.0))
+                }
+                None => parse_pixels(value).map(Self::Px),
+            };
+        }
+
+        if let Some((numerator, denominator)) = value.split_once('/') {
+            let (numerator, denominator) = (parse_number(numerator)?, parse_number(denominator)?);
+            return (denominator > 0.0).then(|| Self::Percent(numerator / denominator));
+        }
+
+        parse_number(value).map(|steps| Self::Px(steps * SPACING))
+    }
+
+    /// Parses the container sizes as well, such as `max-w-md`.
+    fn parse_width(value: &str) -> Option<Self> {
+        let rem = match value {
+            "3xs" => 16.0,
+            "2xs" => 18.0,
+            "xs" => 20.0,
+            "sm" => 24.0,
+            "md" => 28.0,
+            "lg" => 32.0,
+            "xl" => 36.0,
+            "2xl" => 42.0,
+            "3xl" => 48.0,
+            "4xl" => 56.0,
+            "5xl" => 64.0,
+            "6xl" => 72.0,
+            "7xl" => 80.0,
+            _ => return Self::parse(value),
+        };
+
+        Some(Self::Px(rem * 4.0 * SPACING))
+    }
+
+    fn neg(self) -> Self {
+        match self {
+            Self::Px(value) => Self::Px(-value),
+            Self::Percent(value) => Self::Percent(-value),
+            Self::Auto => Self::Auto,
+        }
+    }
+
+    fn length_percentage(self) -> Option<LengthPercentage> {
+        match self {
+            Self::Px(value) => Some(LengthPercentage::length(value)),
+            Self::Percent(value) => Some(LengthPercentage::percent(value)),
+            Self::Auto => None,
+        }
+    }
+
+    fn length_percentage_auto(self) -> LengthPercentageAuto {
+        match self {
+            Self::Px(value) => LengthPercentageAuto::length(value),
+            Self::Percent(value) => LengthPercentageAuto::percent(value),
+            Self::Auto => LengthPercentageAuto::auto(),
+        }
+    }
+
+    fn dimension(self) -> Dimension {
+        match self {
+            Self::Px(value) => Dimension::length(value),
+            Self::Percent(value) => Dimension::percent(value),
+            Self::Auto => Dimension::auto(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{Outline, div};
+
+    fn apply(classes: &str) -> (Div, Vec<&str>) {
+        let mut node = div();
+        let mut unknown = Vec::new();
+        node.apply_classes(classes, |class| unknown.push(class));
+        (node, unknown)
+    }
+
+    #[test]
+    fn test_class() {
+        let (node, unknown) =
+            apply("flex flex-col w-64 h-32 p-4 gap-2 bg-white rounded-lg shadow-md");
+
+        assert!(unknown.is_empty());
+
+        let style = &node.style;
+        assert_eq!(style.display, Display::Flex);
+        assert_eq!(style.flex_direction, FlexDirection::Column);
+        assert_eq!(style.size.width, Dimension::length(256.0));
+        assert_eq!(style.size.height, Dimension::length(128.0));
+        assert_eq!(style.padding, Rect::length(16.0));
+        assert_eq!(style.gap.width, LengthPercentage::length(8.0));
+        assert_eq!(style.gap.height, LengthPercentage::length(8.0));
+        assert_eq!(node.background, Some(Color::WHITE));
+        assert_eq!(node.corner_radii, Corners::all(8.0));
+        assert_eq!(node.box_shadow.as_deref(), Some(&BoxShadow::MD[..]));
+    }
+
+    #[test]
+    fn test_class_spacing_and_sizing() {
+        let (node, unknown) = apply(
+            "px-2 py-1.5 pt-px -mt-4 mx-auto inset-x-1/2 -top-[3px] \
+             w-1/3 h-full max-w-md min-h-[50%] basis-auto",
+        );
+
+        assert!(unknown.is_empty());
+
+        let style = &node.style;
+        assert_eq!(style.padding.top, LengthPercentage::length(1.0));
+        assert_eq!(style.padding.right, LengthPercentage::length(8.0));
+        assert_eq!(style.padding.bottom, LengthPercentage::length(6.0));
+        assert_eq!(style.padding.left, LengthPercentage::length(8.0));
+        assert_eq!(style.margin.top, LengthPercentageAuto::length(-16.0));
+        assert_eq!(style.margin.right, LengthPercentageAuto::auto());
+        assert_eq!(style.margin.left, LengthPercentageAuto::auto());
+        assert_eq!(style.inset.top, LengthPercentageAuto::length(-3.0));
+        assert_eq!(style.inset.right, LengthPercentageAuto::percent(0.5));
+        assert_eq!(style.inset.left, LengthPercentageAuto::percent(0.5));
+        assert_eq!(style.size.width, Dimension::percent(1.0 / 3.0));
+        assert_eq!(style.size.height, Dimension::percent(1.0));
+        assert_eq!(style.max_size.width, Dimension::length(448.0));
+        assert_eq!(style.min_size.height, Dimension::percent(0.5));
+        assert_eq!(style.flex_basis, Dimension::auto());
+    }
+
+    #[test]
+    fn test_class_border_sides_and_styles() {
+        let (node, unknown) = apply("border-2 border-dashed border-slate-300 border-t-sky-500");
+
+        assert!(unknown.is_empty());
+        assert_eq!(node.border_style, BorderStyle::DASHED);
+        assert_eq!(node.border_color, Some(SLATE_300.into()));
+        assert_eq!(
+            node.border_colors,
+            Edges::all(None).top(Some(SKY_500.into()))
+        );
+    }
+
+    #[test]
+    fn test_class_outlines() {
+        let (node, unknown) = apply("outline-2 outline-offset-4 outline-dotted outline-sky-500");
+
+        assert!(unknown.is_empty());
+        assert_eq!(
+            node.outline,
+            Some(
+                Outline::new(2.0, SKY_500.into())
+                    .with_offset(4.0)
+                    .with_style(BorderStyle::DOTTED)
+            )
+        );
+
+        let (node, _) = apply("outline outline-none");
+        assert_eq!(node.outline, None);
+    }
+
+    #[test]
+    fn test_class_inset_shadows() {
+        let (node, unknown) = apply("inset-shadow-sm shadow-md");
+
+        assert!(unknown.is_empty());
+
+        let shadows = node.box_shadow.as_deref().unwrap();
+        assert_eq!(&shadows[..BoxShadow::MD.len()], &BoxShadow::MD[..]);
+        assert_eq!(&shadows[BoxShadow::MD.len()..], &BoxShadow::INSET_SM[..]);
+
+        // the outer shadows stay before the inset shadows
+        let (other, _) = apply("shadow-md inset-shadow-sm");
+        assert_eq!(other.box_shadow, node.box_shadow);
+
+        let (other, _) = apply("shadow-md inset-shadow-sm shadow-xs");
+        assert_eq!(other.box_shadow.as_deref().unwrap()[0], BoxShadow::XS[0]);
+
+        let (node, _) = apply("shadow-md inset-shadow-xs shadow-none");
+        assert_eq!(node.box_shadow.as_deref(), Some(&BoxShadow::INSET_XS[..]));
+
+        let (node, _) = apply("inset-shadow-xs inset-shadow-none");
+        assert_eq!(node.box_shadow, None);
+    }
+
+    #[test]
+    fn test_class_borders_and_corners() {
+        let (node, unknown) =
+            apply("border border-t-4 border-x-0 border-sky-500/50 rounded-t-xl rounded-br-full");
+
+        assert!(unknown.is_empty());
+
+        let border = node.style.border;
+        assert_eq!(border.top, LengthPercentage::length(4.0));
+        assert_eq!(border.right, LengthPercentage::length(0.0));
+        assert_eq!(border.bottom, LengthPercentage::length(1.0));
+        assert_eq!(border.left, LengthPercentage::length(0.0));
+        assert_eq!(node.border_color, Some(SKY_500.with_alpha(0.5).into()));
+
+        assert_eq!(node.corner_radii.top_left, 12.0);
+        assert_eq!(node.corner_radii.top_right, 12.0);
+        assert_eq!(node.corner_radii.bottom_right, f32::MAX);
+        assert_eq!(node.corner_radii.bottom_left, 0.0);
+        assert_eq!(
+            node.corner_radii.resolve(bevy_math::Vec2::new(40.0, 20.0)),
+            [12.0, 12.0, 10.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_class_grid_and_alignment() {
+        let (node, unknown) =
+            apply("grid grid-cols-3 gap-x-4 col-span-2 row-start-2 items-center justify-between");
+
+        assert!(unknown.is_empty());
+
+        let style = &node.style;
+        assert_eq!(style.display, Display::Grid);
+        assert_eq!(style.grid_template_columns.len(), 1);
+        assert_eq!(style.gap.width, LengthPercentage::length(16.0));
+        assert_eq!(style.grid_column.start, GridPlacement::Span(2));
+        assert_eq!(style.grid_row.start, line(2));
+        assert_eq!(style.align_items, Some(AlignItems::Center));
+        assert_eq!(style.justify_content, Some(JustifyContent::SpaceBetween));
+
+        let (node, unknown) = apply("grid-rows-[auto_1fr] grid-cols-[200px_repeat(2,1fr)]");
+
+        assert!(unknown.is_empty());
+        assert_eq!(node.style.grid_template_rows.len(), 2);
+        assert_eq!(node.style.grid_template_columns.len(), 2);
+    }
+
+    #[test]
+    fn test_class_unknown() {
+        let (node, unknown) =
+            apply("flex text-lg p-auto -p-4 bg-sky-1000 rounded-huge w-64 p-4 px-2");
+
+        assert_eq!(
+            unknown,
+            ["text-lg", "p-auto", "-p-4", "bg-sky-1000", "rounded-huge"]
+        );
+
+        // The known classes are still applied, the later ones win.
+        assert_eq!(node.style.display, Display::Flex);
+        assert_eq!(node.style.size.width, Dimension::length(256.0));
+        assert_eq!(node.style.padding.top, LengthPercentage::length(16.0));
+        assert_eq!(node.style.padding.left, LengthPercentage::length(8.0));
+    }
+}
@@ -1,8 +1,118 @@
-use bevy_ecs::{component::Component, prelude::ReflectComponent};
-use bevy_math::{Affine3A, Vec2, Vec4};
+use std::cmp::Ordering;
+
+use bevy_derive::Deref;
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut, Mut},
+    component::Component,
+    entity::Entity,
+    prelude::ReflectComponent,
+    system::{Commands, Query, Res},
+    world::Ref,
+};
+use bevy_math::{Affine3A, BVec2, Vec2, Vec3, Vec4};
 use bevy_reflect::{Reflect, prelude::ReflectDefault};
+use bevy_text::{TextColor, TextFont};
+use smallvec::SmallVec;
 use taffy::Layout;
 
+use super::{
+    div::Div,
+    interaction::InteractionState,
+    transition::{Transition, TransitionState},
+};
+use crate::{geometry::sd_rounded_box, style_sheet::MatchedRules, theme::MoonTheme};
+
+/// The style of a node as it is laid out and rendered.
+///
+/// It is resolved from the [`Div`] before the layout, with the rules of the stylesheets, the style
+/// variants of the [`InteractionState`] and the tokens of the [`MoonTheme`] applied on top of it, and the values
+/// of a [`Transition`] displayed in place of the resolved ones, see [`update_computed_divs`].
+/// The [`Div`] is left as the user has set it.
+///
+/// It is only marked as changed when its layout changes, as its other fields are read every frame.
+#[derive(Component, Clone, Debug, Default, PartialEq, Deref, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct ComputedDiv(pub(crate) Div);
+
+impl ComputedDiv {
+    /// Sets the resolved style, only marks the node as changed when its layout changes.
+    pub(crate) fn set(this: &mut Mut<Self>, div: Div) {
+        if this.0 == div {
+            return;
+        }
+
+        if has_same_layout(&this.0, &div) {
+            this.bypass_change_detection().0 = div;
+        } else {
+            this.0 = div;
+        }
+    }
+}
+
+/// Returns `true` if the fields of the nodes which are laid out are equal.
+pub(crate) fn has_same_layout(a: &Div, b: &Div) -> bool {
+    a.style == b.style
+        && a.order == b.order
+        && a.relative_mask == b.relative_mask
+        && a.relative_lengths == b.relative_lengths
+}
+
+/// Resolves the [`ComputedDiv`] of the nodes whose [`Div`], matching stylesheet rules or
+/// [`InteractionState`] have been changed, or of all the nodes when the [`MoonTheme`] has been
+/// changed.
+///
+/// Inserts an [`InteractionState`] on the nodes with style variants, and starts the transitions
+/// of the nodes with a [`Transition`].
+pub fn update_computed_divs(
+    mut commands: Commands,
+    theme: Res<MoonTheme>,
+    mut div_query: Query<(
+        Entity,
+        Ref<Div>,
+        Option<Ref<InteractionState>>,
+        Option<Ref<MatchedRules>>,
+        &mut ComputedDiv,
+        Option<&mut TextColor>,
+        Option<&mut TextFont>,
+        Option<(Ref<Transition>, &mut TransitionState)>,
+    )>,
+) {
+    let theme_changed = theme.is_changed();
+
+    for (entity, div, state, rules, mut computed_div, text_color, text_font, transition) in
+        &mut div_query
+    {
+        let changed = theme_changed
+            || div.is_changed()
+            || state.as_ref().is_some_and(Ref::is_changed)
+            || rules.as_ref().is_some_and(Ref::is_changed)
+            || transition
+                .as_ref()
+                .is_some_and(|(transition, _)| transition.is_added());
+        if !changed {
+            continue;
+        }
+
+        let mut resolved = div.clone();
+        if let Some(rules) = rules {
+            rules.apply(&mut resolved);
+        }
+
+        if state.is_none() && resolved.has_variants() {
+            commands.entity(entity).insert(InteractionState::DEFAULT);
+        }
+
+        resolved.apply_variants(state.as_deref().unwrap_or(&InteractionState::DEFAULT));
+        resolved.apply_tokens(&theme, text_color, text_font);
+
+        if let Some((transition, mut transition_state)) = transition {
+            transition_state.retarget(&transition, &mut resolved);
+        }
+
+        ComputedDiv::set(&mut computed_div, resolved);
+    }
+}
+
 /// Provides the computed size and layout properties of the node.
 #[derive(Component, Debug, Copy, Clone, PartialEq, Reflect)]
 #[reflect(Component, Default, Debug, PartialEq, Clone)]
@@ -16,6 +126,20 @@ pub struct ComputedLayout {
     /// The border widths of the node.
     pub border_widths: Vec4,
 
+    /// The padding widths of the node.
+    ///
+    /// `[top, right, bottom, left]`, the same as `border_widths`.
+    pub padding: Vec4,
+
+    /// The size of the node's content, which may overflow its box.
+    pub content_size: Vec2,
+
+    /// The space reserved for the scrollbars.
+    pub scrollbar_size: Vec2,
+
+    /// The clamped scroll offset of the node's content.
+    pub scroll_position: Vec2,
+
     /// The affine of the node is relatived to its parent and used for inversion.
     pub affine: Affine3A,
 }
@@ -26,6 +150,10 @@ impl Default for ComputedLayout {
             location: Vec2::ZERO,
             size: Vec2::ZERO,
             border_widths: Vec4::ZERO,
+            padding: Vec4::ZERO,
+            content_size: Vec2::ZERO,
+            scrollbar_size: Vec2::ZERO,
+            scroll_position: Vec2::ZERO,
 
             affine: Affine3A::IDENTITY,
         }
@@ -39,6 +167,12 @@ impl ComputedLayout {
         self.size.cmpeq(Vec2::ZERO).any()
     }
 
+    /// Returns the maximum scroll offset of the node's content.
+    #[inline]
+    pub fn max_scroll_position(&self) -> Vec2 {
+        (self.content_size - self.size + self.scrollbar_size).max(Vec2::ZERO)
+    }
+
     /// Updates the latest layout result from [`Layout`].
     #[inline]
     pub fn update(&mut self, layout: Layout) {
@@ -48,12 +182,262 @@ impl ComputedLayout {
             location,
             size,
             border,
+            padding,
+            content_size,
+            scrollbar_size,
             ..
         } = layout;
 
         self.location = location.convert();
         self.size = size.convert();
         self.border_widths = border.convert();
+        self.padding = padding.convert();
+        self.content_size = content_size.convert();
+        self.scrollbar_size = scrollbar_size.convert();
+    }
+}
+
+/// The clipping regions of the node, inherited from its clipping ancestors.
+///
+/// A point is visible if it is inside all the regions. Each region is a rounded rectangle in
+/// the local space of a clipping ancestor, so it follows any rotation or scaling of that ancestor.
+///
+/// At most [`ComputedClip::MAX_DEPTH`] regions are kept, nested regions are merged beyond it,
+/// see [`ComputedClip::intersect`].
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Debug, PartialEq, Clone)]
+pub struct ComputedClip {
+    /// The clipping regions, from the outermost to the innermost.
+    pub regions: SmallVec<[ClipRegion; ComputedClip::MAX_DEPTH]>,
+}
+
+impl ComputedClip {
+    /// The maximum number of clipping regions, the same as in the shaders.
+    pub const MAX_DEPTH: usize = 2;
+
+    /// Creates the clipping region from the padding box of the node.
+    ///
+    /// Only the axes which are `true` in `clip` are clipped.
+    pub fn from_padding_box(
+        world_affine: Affine3A,
+        computed_layout: &ComputedLayout,
+        corner_radii: Vec4,
+        clip: BVec2,
+    ) -> Self {
+        ClipRegion::from_padding_box(world_affine, computed_layout, corner_radii, clip).into()
+    }
+
+    /// Returns `true` if the world position is inside all the clipping regions.
+    #[inline]
+    pub fn contains(&self, world_position: Vec3) -> bool {
+        self.regions
+            .iter()
+            .all(|region| region.contains(world_position))
+    }
+
+    /// Intersects the clipping regions of an ancestor with the clipping regions of the node.
+    ///
+    /// The regions are kept apart, up to [`ComputedClip::MAX_DEPTH`]. Beyond it, two adjacent
+    /// regions which are aligned with each other are merged, which is exact for rectangles.
+    /// When no regions are aligned, the two innermost regions are merged, the outer one being
+    /// approximated by its bounding box, see [`ClipRegion::merge`].
+    pub fn intersect(&self, inner: &Self) -> Self {
+        let mut regions = self.regions.clone();
+        regions.extend(inner.regions.iter().copied());
+
+        while regions.len() > Self::MAX_DEPTH {
+            let index = regions
+                .windows(2)
+                .position(|pair| pair[0].is_aligned_with(&pair[1]))
+                .unwrap_or(regions.len() - 2);
+
+            let outer = regions.remove(index);
+            regions[index] = outer.merge(&regions[index]);
+        }
+
+        Self { regions }
+    }
+}
+
+impl From<ClipRegion> for ComputedClip {
+    fn from(region: ClipRegion) -> Self {
+        Self {
+            regions: SmallVec::from_elem(region, 1),
+        }
+    }
+}
+
+/// A clipping region, a rounded rectangle in the local space of a clipping ancestor.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub struct ClipRegion {
+    /// Transforms a world position into the local space of the clipping region.
+    pub clip_from_world: Affine3A,
+
+    /// The center of the clipping region, relative to the center of the clipping ancestor.
+    pub center: Vec2,
+
+    /// The half size of the clipping region.
+    pub half_size: Vec2,
+
+    /// The corner radii of the clipping region.
+    ///
+    /// `[top_left, top_right, bottom_right, bottom_left]`
+    pub corner_radii: Vec4,
+}
+
+impl ClipRegion {
+    /// The half size along an axis which is not clipped.
+    pub const UNBOUNDED: f32 = 1.0e9;
+
+    /// Creates the clipping region from the padding box of the node.
+    ///
+    /// Only the axes which are `true` in `clip` are clipped.
+    pub fn from_padding_box(
+        world_affine: Affine3A,
+        computed_layout: &ComputedLayout,
+        corner_radii: Vec4,
+        clip: BVec2,
+    ) -> Self {
+        let Vec4 {
+            x: top,
+            y: right,
+            z: bottom,
+            w: left,
+        } = computed_layout.border_widths;
+
+        // y-up
+        let center = Vec2::new(left - right, bottom - top) * 0.5;
+        let half_size =
+            ((computed_layout.size - Vec2::new(left + right, top + bottom)) * 0.5).max(Vec2::ZERO);
+
+        // The inner radii of the border, the same as `sd_inset_rounded_box` in the shaders.
+        let corner_radii = if clip.all() {
+            (corner_radii
+                - Vec4::new(
+                    left.max(top),
+                    right.max(top),
+                    right.max(bottom),
+                    left.max(bottom),
+                ))
+            .max(Vec4::ZERO)
+        } else {
+            Vec4::ZERO
+        };
+
+        Self {
+            clip_from_world: world_affine.inverse(),
+            center,
+            half_size: Vec2::select(clip, half_size, Vec2::splat(Self::UNBOUNDED)),
+            corner_radii,
+        }
+    }
+
+    /// Transforms a world position into the local space of the clipping region.
+    #[inline]
+    pub fn to_local(&self, world_position: Vec3) -> Vec2 {
+        self.clip_from_world
+            .transform_point3(world_position)
+            .truncate()
+    }
+
+    /// Returns `true` if the world position is inside the clipping region.
+    #[inline]
+    pub fn contains(&self, world_position: Vec3) -> bool {
+        let point = self.to_local(world_position) - self.center;
+        sd_rounded_box(point, self.half_size, self.corner_radii) <= 0.0
+    }
+
+    /// Returns `true` if the regions are aligned with each other, up to a scaling.
+    pub fn is_aligned_with(&self, inner: &Self) -> bool {
+        let matrix = self.inner_from_outer(inner).matrix3;
+        matrix.x_axis.y.abs() <= f32::EPSILON && matrix.y_axis.x.abs() <= f32::EPSILON
+    }
+
+    /// Maps the local space of this region into the local space of the inner region.
+    #[inline]
+    fn inner_from_outer(&self, inner: &Self) -> Affine3A {
+        inner.clip_from_world * self.clip_from_world.inverse()
+    }
+
+    /// Merges the region of an ancestor with the region of the node into one region.
+    ///
+    /// The result is in the local space of the node. When the node is rotated relative to the
+    /// ancestor, the region of the ancestor is approximated by its bounding box, which is larger
+    /// than the region.
+    pub fn merge(&self, inner: &Self) -> Self {
+        // Maps the ancestor's local space into the node's local space.
+        let inner_from_outer = self.inner_from_outer(inner);
+
+        // [top_left, top_right, bottom_right, bottom_left]
+        let outer_corners = [
+            Vec2::new(-1.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, -1.0),
+            Vec2::new(-1.0, -1.0),
+        ]
+        .map(|sign| {
+            inner_from_outer
+                .transform_point3((self.center + sign * self.half_size).extend(0.0))
+                .truncate()
+        });
+
+        let (outer_min, outer_max) = outer_corners
+            .iter()
+            .fold((Vec2::MAX, Vec2::MIN), |(min, max), &corner| {
+                (min.min(corner), max.max(corner))
+            });
+
+        let inner_min = inner.center - inner.half_size;
+        let inner_max = inner.center + inner.half_size;
+
+        let min = inner_min.max(outer_min);
+        let max = inner_max.min(outer_max).max(min);
+
+        let matrix = inner_from_outer.matrix3;
+        let axis_aligned = self.is_aligned_with(inner);
+        let scale = matrix.x_axis.x.abs().min(matrix.y_axis.y.abs());
+
+        let corners = [
+            Vec2::new(min.x, max.y),
+            Vec2::new(max.x, max.y),
+            Vec2::new(max.x, min.y),
+            Vec2::new(min.x, min.y),
+        ];
+
+        let inner_corners = [
+            Vec2::new(inner_min.x, inner_max.y),
+            Vec2::new(inner_max.x, inner_max.y),
+            Vec2::new(inner_max.x, inner_min.y),
+            Vec2::new(inner_min.x, inner_min.y),
+        ];
+
+        let mut corner_radii = Vec4::ZERO;
+
+        for (index, corner) in corners.into_iter().enumerate() {
+            // Keeps the rounded corner of the region which the corner comes from.
+            corner_radii[index] = if corner.abs_diff_eq(inner_corners[index], 1e-3) {
+                inner.corner_radii[index]
+            } else if let Some(outer_index) = axis_aligned
+                .then(|| {
+                    outer_corners
+                        .iter()
+                        .position(|outer| corner.abs_diff_eq(*outer, 1e-3))
+                })
+                .flatten()
+            {
+                self.corner_radii[outer_index] * scale
+            } else {
+                0.0
+            };
+        }
+
+        Self {
+            clip_from_world: inner.clip_from_world,
+            center: (min + max) * 0.5,
+            half_size: (max - min) * 0.5,
+            corner_radii,
+        }
     }
 }
 
@@ -62,3 +446,124 @@ pub struct ComputedTargetInfo {
     pub scale_factor: f32,
     pub physical_size: Vec2,
 }
+
+impl ComputedTargetInfo {
+    /// Returns the size of the viewport in logical pixels.
+    #[inline]
+    pub fn logical_size(&self) -> Vec2 {
+        self.physical_size / self.scale_factor
+    }
+
+    /// Compares two targets which render the same node.
+    ///
+    /// A node is laid out and measured once for all the cameras which render it,
+    /// so the greatest target is chosen whatever the order of the cameras is:
+    /// the higher scale factor keeps the text crisp on every target, then the larger viewport.
+    pub fn preference(&self, other: &Self) -> Ordering {
+        self.scale_factor
+            .total_cmp(&other.scale_factor)
+            .then(self.physical_size.x.total_cmp(&other.physical_size.x))
+            .then(self.physical_size.y.total_cmp(&other.physical_size.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::FRAC_PI_4;
+
+    use super::*;
+
+    fn region(clip_from_world: Affine3A, half_size: Vec2) -> ComputedClip {
+        ComputedClip::from(ClipRegion {
+            clip_from_world,
+            center: Vec2::ZERO,
+            half_size,
+            corner_radii: Vec4::ZERO,
+        })
+    }
+
+    #[test]
+    fn test_clip_from_padding_box() {
+        let world_affine = Affine3A::from_translation(Vec3::new(10.0, 20.0, 0.0));
+        let computed_layout = ComputedLayout {
+            size: Vec2::new(100.0, 50.0),
+            border_widths: Vec4::new(2.0, 4.0, 6.0, 8.0),
+            ..Default::default()
+        };
+
+        let clip = ComputedClip::from_padding_box(
+            world_affine,
+            &computed_layout,
+            Vec4::splat(10.0),
+            BVec2::TRUE,
+        );
+
+        assert_eq!(clip.regions.len(), 1);
+        let region = clip.regions[0];
+        assert_eq!(region.center, Vec2::new(2.0, 2.0));
+        assert_eq!(region.half_size, Vec2::new(44.0, 21.0));
+        // The outer radii minus the widest adjacent border.
+        assert_eq!(region.corner_radii, Vec4::new(2.0, 6.0, 4.0, 2.0));
+
+        assert!(clip.contains(Vec3::new(10.0 + 2.0 + 40.0, 20.0 + 2.0, 0.0)));
+        assert!(!clip.contains(Vec3::new(10.0 + 2.0 + 45.0, 20.0 + 2.0, 0.0)));
+
+        // Only clips along the x axis.
+        let clip = ComputedClip::from_padding_box(
+            world_affine,
+            &computed_layout,
+            Vec4::splat(10.0),
+            BVec2::new(true, false),
+        );
+
+        let region = clip.regions[0];
+        assert_eq!(region.half_size, Vec2::new(44.0, ClipRegion::UNBOUNDED));
+        assert_eq!(region.corner_radii, Vec4::ZERO);
+        assert!(clip.contains(Vec3::new(10.0, 1000.0, 0.0)));
+        assert!(!clip.contains(Vec3::new(100.0, 20.0, 0.0)));
+    }
+
+    #[test]
+    fn test_clip_contains_rounded_corners() {
+        let clip = ComputedClip::from(ClipRegion {
+            clip_from_world: Affine3A::IDENTITY,
+            center: Vec2::ZERO,
+            half_size: Vec2::new(10.0, 10.0),
+            corner_radii: Vec4::splat(5.0),
+        });
+
+        assert!(clip.contains(Vec3::ZERO));
+        assert!(clip.contains(Vec3::new(9.5, 0.0, 0.0)));
+        assert!(!clip.contains(Vec3::new(9.5, 9.5, 0.0)));
+        assert!(!clip.contains(Vec3::new(11.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_clip_intersect_rotated_parent() {
+        // A square rotated by 45 degrees, which is a diamond in world space.
+        let parent = region(
+            Affine3A::from_rotation_z(FRAC_PI_4).inverse(),
+            Vec2::new(50.0, 50.0),
+        );
+        let child = region(Affine3A::IDENTITY, Vec2::new(100.0, 100.0));
+
+        let clip = parent.intersect(&child);
+
+        assert_eq!(clip.regions.len(), 2);
+        assert!(clip.contains(Vec3::ZERO));
+        assert!(clip.contains(Vec3::new(0.0, 60.0, 0.0)));
+        // Inside the bounding box of the diamond, but outside of its corner.
+        assert!(!clip.contains(Vec3::new(45.0, 45.0, 0.0)));
+
+        // The aligned regions are merged beyond the maximum depth, the rotated one is kept.
+        let grandchild = region(Affine3A::IDENTITY, Vec2::new(30.0, 80.0));
+        let clip = clip.intersect(&grandchild);
+
+        assert_eq!(clip.regions.len(), ComputedClip::MAX_DEPTH);
+        assert_eq!(clip.regions[0], parent.regions[0]);
+        assert_eq!(clip.regions[1].half_size, Vec2::new(30.0, 80.0));
+        assert!(clip.contains(Vec3::new(0.0, 60.0, 0.0)));
+        assert!(!clip.contains(Vec3::new(40.0, 0.0, 0.0)));
+        assert!(!clip.contains(Vec3::new(25.0, 60.0, 0.0)));
+    }
+}
@@ -1,30 +1,75 @@
+use std::borrow::Cow;
+
 use bevy_color::Color;
 use bevy_ecs::{component::Component, prelude::ReflectComponent};
+use bevy_math::BVec2;
 use bevy_reflect::{Reflect, prelude::ReflectDefault};
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 use bevy_transform::components::Transform;
 use smallvec::SmallVec;
 use taffy::*;
 
-use super::computed::ComputedLayout;
-use crate::style::{BoxShadow, Corners};
+use super::{
+    computed::{ComputedDiv, ComputedLayout},
+    interaction::{DivPatch, VariantState},
+    scroll::ScrollPosition,
+};
+use crate::{
+    style::{BorderStyle, BoxShadow, Corners, Edges, Gradient, LayoutStyle, Outline},
+    theme::ThemeProperty,
+    units::{LengthProperty, RelativeLength},
+};
 
-#[derive(Component, Clone, Debug, Reflect)]
-#[require(Transform, ComputedLayout)]
-#[reflect(Component, Clone, Debug, Default)]
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[require(Transform, ComputedLayout, ComputedDiv, ScrollPosition)]
+#[reflect(Component, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub struct Div {
+    /// The index of the node in the stack of the camera which renders it.
+    ///
+    /// When the node is rendered by several cameras, it holds the index of the last one,
+    /// uses [`UiStack::iter`](crate::prelude::UiStack::iter) for the index in each camera.
+    #[cfg_attr(feature = "serialize", serde(skip))]
     pub stack_index: usize,
 
-    #[reflect(ignore, clone)]
-    pub(crate) style: Style,
+    pub(crate) style: LayoutStyle,
+
+    /// The order of the node among its siblings in the layout, see [`Div::order`].
+    pub(crate) order: i32,
+
+    /// The lengths which are resolved before the layout, see [`Div::relative_length`].
+    pub(crate) relative_lengths: SmallVec<[(LengthProperty, RelativeLength); 2]>,
+
+    /// The bits of the properties which are set to their relative lengths,
+    /// the other entries of [`Div::relative_lengths`] have been set to absolute lengths since.
+    pub(crate) relative_mask: u32,
+
+    /// The tokens of the theme which are referenced by the node, see [`Div::token`].
+    pub(crate) tokens: SmallVec<[(ThemeProperty, Cow<'static, str>); 2]>,
 
     pub background: Option<Color>,
+    /// Takes precedence over [`Div::background`], see [`Div::background_gradient`].
+    pub background_gradient: Option<Gradient>,
     pub corner_radii: Corners<f32>,
     pub border_color: Option<Color>,
+    /// Takes precedence over [`Div::border_color`], see [`Div::border_gradient`].
+    pub border_gradient: Option<Gradient>,
+    /// The colors of the sides, which take precedence over [`Div::border_color`].
+    ///
+    /// They are ignored when [`Div::border_gradient`] is set, the gradient is drawn on all sides.
+    pub border_colors: Edges<Option<Color>>,
+    pub border_style: BorderStyle,
     pub box_shadow: Option<SmallVec<[BoxShadow; 2]>>,
-}
+    pub outline: Option<Outline>,
 
-unsafe impl Send for Div {}
-unsafe impl Sync for Div {}
+    /// The style variants, in the order of their interaction states, see [`Div::hover`].
+    pub(crate) variants: Vec<(VariantState, DivPatch)>,
+}
 
 impl Default for Div {
     fn default() -> Self {
@@ -34,12 +79,22 @@ impl Default for Div {
 
 impl Div {
     pub const DEFAULT: Self = Self {
-        style: Style::DEFAULT,
+        style: LayoutStyle::DEFAULT,
+        order: 0,
+        relative_lengths: SmallVec::new_const(),
+        relative_mask: 0,
+        tokens: SmallVec::new_const(),
         stack_index: 0,
         background: None,
+        background_gradient: None,
         corner_radii: Corners::DEFAULT,
         border_color: None,
+        border_gradient: None,
+        border_colors: Edges::all(None),
+        border_style: BorderStyle::Solid,
         box_shadow: None,
+        outline: None,
+        variants: Vec::new(),
     };
 
     pub fn flex(mut self) -> Self {
@@ -72,6 +127,66 @@ impl Div {
         self
     }
 
+    pub fn overflow_visible(mut self) -> Self {
+        self.style.overflow = Point {
+            x: Overflow::Visible,
+            y: Overflow::Visible,
+        };
+        self
+    }
+
+    pub fn overflow_hidden(mut self) -> Self {
+        self.style.overflow = Point {
+            x: Overflow::Hidden,
+            y: Overflow::Hidden,
+        };
+        self
+    }
+
+    pub fn overflow_clip(mut self) -> Self {
+        self.style.overflow = Point {
+            x: Overflow::Clip,
+            y: Overflow::Clip,
+        };
+        self
+    }
+
+    pub fn overflow_scroll(mut self) -> Self {
+        self.style.overflow = Point {
+            x: Overflow::Scroll,
+            y: Overflow::Scroll,
+        };
+        self
+    }
+
+    pub fn overflow_x_scroll(mut self) -> Self {
+        self.style.overflow.x = Overflow::Scroll;
+        self
+    }
+
+    pub fn overflow_y_scroll(mut self) -> Self {
+        self.style.overflow.y = Overflow::Scroll;
+        self
+    }
+
+    /// Returns `true` if the content of the node is scrollable along the x and y axes.
+    #[inline]
+    pub fn scrollable(&self) -> BVec2 {
+        BVec2::new(
+            self.style.overflow.x == Overflow::Scroll,
+            self.style.overflow.y == Overflow::Scroll,
+        )
+    }
+
+    /// Returns `true` if the content of the node is clipped along the x and y axes.
+    #[inline]
+    pub fn clipped(&self) -> BVec2 {
+        BVec2::new(
+            self.style.overflow.x != Overflow::Visible,
+            self.style.overflow.y != Overflow::Visible,
+        )
+    }
+
     pub fn flex_row(mut self) -> Self {
         self.style.display = Display::Flex;
         self.style.flex_direction = FlexDirection::Row;
@@ -120,6 +235,7 @@ impl Div {
         self.style.flex_grow = 1.0;
         self.style.flex_shrink = 1.0;
         self.style.flex_basis = Dimension::auto();
+        self.clear_relative_lengths(&[LengthProperty::FlexBasis]);
         self
     }
 
@@ -127,6 +243,7 @@ impl Div {
         self.style.flex_grow = 1.0;
         self.style.flex_shrink = 1.0;
         self.style.flex_basis = Dimension::length(0.0);
+        self.clear_relative_lengths(&[LengthProperty::FlexBasis]);
         self
     }
 
@@ -147,144 +264,1369 @@ impl Div {
 
     pub fn w(mut self, val: f32) -> Self {
         self.style.size.width = Dimension::length(val);
+        self.clear_relative_lengths(&[LengthProperty::Width]);
         self
     }
 
     pub fn w_p(mut self, val: f32) -> Self {
         self.style.size.width = Dimension::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::Width]);
         self
     }
 
     pub fn w_full(mut self) -> Self {
         self.style.size.width = Dimension::percent(1.0);
+        self.clear_relative_lengths(&[LengthProperty::Width]);
         self
     }
 
     pub fn w_auto(mut self) -> Self {
         self.style.size.width = Dimension::auto();
+        self.clear_relative_lengths(&[LengthProperty::Width]);
         self
     }
 
     pub fn h(mut self, val: f32) -> Self {
         self.style.size.height = Dimension::length(val);
+        self.clear_relative_lengths(&[LengthProperty::Height]);
         self
     }
 
     pub fn h_p(mut self, val: f32) -> Self {
         self.style.size.height = Dimension::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::Height]);
         self
     }
 
     pub fn h_full(mut self) -> Self {
         self.style.size.height = Dimension::percent(1.0);
+        self.clear_relative_lengths(&[LengthProperty::Height]);
         self
     }
 
     pub fn h_auto(mut self) -> Self {
         self.style.size.height = Dimension::auto();
+        self.clear_relative_lengths(&[LengthProperty::Height]);
         self
     }
 
-    pub fn background(mut self, color: impl Into<Color>) -> Self {
-        self.background = Some(color.into());
+    pub fn box_border(mut self) -> Self {
+        self.style.box_sizing = BoxSizing::BorderBox;
         self
     }
 
-    #[inline]
-    pub const fn corner_radii(mut self, radii: Corners<f32>) -> Self {
-        self.corner_radii = radii;
+    pub fn box_content(mut self) -> Self {
+        self.style.box_sizing = BoxSizing::ContentBox;
         self
     }
 
-    pub fn border_color(mut self, color: impl Into<Color>) -> Self {
-        self.border_color = Some(color.into());
+    pub fn overflow_x_visible(mut self) -> Self {
+        self.style.overflow.x = Overflow::Visible;
         self
     }
 
-    #[inline]
-    pub const fn border(mut self, border: Rect<LengthPercentage>) -> Self {
-        self.style.border = border;
+    pub fn overflow_x_hidden(mut self) -> Self {
+        self.style.overflow.x = Overflow::Hidden;
         self
     }
 
-    pub fn shadow(mut self, shadows: Vec<BoxShadow>) -> Self {
-        self.box_shadow = Some(SmallVec::from_vec(shadows));
+    pub fn overflow_x_clip(mut self) -> Self {
+        self.style.overflow.x = Overflow::Clip;
         self
     }
 
-    pub fn shadow_none(mut self) -> Self {
-        self.box_shadow = None;
+    pub fn overflow_y_visible(mut self) -> Self {
+        self.style.overflow.y = Overflow::Visible;
         self
     }
 
-    pub fn shadow_2xs(mut self) -> Self {
-        self.box_shadow = Some(SmallVec::from_slice(&BoxShadow::XS2));
+    pub fn overflow_y_hidden(mut self) -> Self {
+        self.style.overflow.y = Overflow::Hidden;
         self
     }
 
-    pub fn shadow_xs(mut self) -> Self {
-        self.box_shadow = Some(SmallVec::from_slice(&BoxShadow::XS));
+    pub fn overflow_y_clip(mut self) -> Self {
+        self.style.overflow.y = Overflow::Clip;
         self
     }
 
-    pub fn shadow_sm(mut self) -> Self {
-        self.box_shadow = Some(BoxShadow::SM.into());
+    pub fn scrollbar_width(mut self, val: f32) -> Self {
+        self.style.scrollbar_width = val;
         self
     }
 
-    pub fn shadow_md(mut self) -> Self {
-        self.box_shadow = Some(BoxShadow::MD.into());
+    pub fn inset(mut self, val: f32) -> Self {
+        self.style.inset = Rect::length(val);
+        self.clear_relative_lengths(&LengthProperty::INSET);
         self
     }
 
-    pub fn shadow_lg(mut self) -> Self {
-        self.box_shadow = Some(BoxShadow::LG.into());
+    pub fn inset_p(mut self, val: f32) -> Self {
+        self.style.inset = Rect::percent(val);
+        self.clear_relative_lengths(&LengthProperty::INSET);
         self
     }
 
-    pub fn shadow_xl(mut self) -> Self {
-        self.box_shadow = Some(BoxShadow::XL.into());
+    pub fn inset_auto(mut self) -> Self {
+        self.style.inset = Rect::auto();
+        self.clear_relative_lengths(&LengthProperty::INSET);
         self
     }
 
-    pub fn shadow_2xl(mut self) -> Self {
-        self.box_shadow = Some(SmallVec::from_slice(&BoxShadow::XL2));
+    pub fn inset_x(mut self, val: f32) -> Self {
+        self.style.inset.left = LengthPercentageAuto::length(val);
+        self.style.inset.right = LengthPercentageAuto::length(val);
+        self.clear_relative_lengths(&[LengthProperty::Left, LengthProperty::Right]);
         self
     }
 
-    pub fn p_px(mut self) -> Self {
-        self.style.padding = Rect::length(1.0);
+    pub fn inset_x_p(mut self, val: f32) -> Self {
+        self.style.inset.left = LengthPercentageAuto::percent(val);
+        self.style.inset.right = LengthPercentageAuto::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::Left, LengthProperty::Right]);
         self
     }
-}
 
-pub fn div() -> Div {
-    Div::DEFAULT
-}
+    pub fn inset_x_auto(mut self) -> Self {
+        self.style.inset.left = LengthPercentageAuto::auto();
+        self.style.inset.right = LengthPercentageAuto::auto();
+        self.clear_relative_lengths(&[LengthProperty::Left, LengthProperty::Right]);
+        self
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub fn inset_y(mut self, val: f32) -> Self {
+        self.style.inset.top = LengthPercentageAuto::length(val);
+        self.style.inset.bottom = LengthPercentageAuto::length(val);
+        self.clear_relative_lengths(&[LengthProperty::Top, LengthProperty::Bottom]);
+        self
+    }
 
-    #[test]
-    fn test_div_builder() {
-        let d = div().flex();
+    pub fn inset_y_p(mut self, val: f32) -> Self {
+        self.style.inset.top = LengthPercentageAuto::percent(val);
+        self.style.inset.bottom = LengthPercentageAuto::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::Top, LengthProperty::Bottom]);
+        self
+    }
 
-        assert_eq!(d.style.display, Display::Flex);
+    pub fn inset_y_auto(mut self) -> Self {
+        self.style.inset.top = LengthPercentageAuto::auto();
+        self.style.inset.bottom = LengthPercentageAuto::auto();
+        self.clear_relative_lengths(&[LengthProperty::Top, LengthProperty::Bottom]);
+        self
+    }
 
-        let d = d.block();
+    pub fn top(mut self, val: f32) -> Self {
+        self.style.inset.top = LengthPercentageAuto::length(val);
+        self.clear_relative_lengths(&[LengthProperty::Top]);
+        self
+    }
 
-        assert_eq!(d.style.display, Display::Block);
+    pub fn top_p(mut self, val: f32) -> Self {
+        self.style.inset.top = LengthPercentageAuto::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::Top]);
+        self
     }
 
-    #[test]
-    fn test_div_sizing() {
-        let d = div().w(100.0);
+    pub fn top_auto(mut self) -> Self {
+        self.style.inset.top = LengthPercentageAuto::auto();
+        self.clear_relative_lengths(&[LengthProperty::Top]);
+        self
+    }
 
-        assert_eq!(d.style.size.width, Dimension::length(100.0));
+    pub fn right(mut self, val: f32) -> Self {
+        self.style.inset.right = LengthPercentageAuto::length(val);
+        self.clear_relative_lengths(&[LengthProperty::Right]);
+        self
+    }
 
-        let d = d.h_full();
+    pub fn right_p(mut self, val: f32) -> Self {
+        self.style.inset.right = LengthPercentageAuto::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::Right]);
+        self
+    }
 
-        assert_eq!(d.style.size.height, Dimension::percent(1.0));
+    pub fn right_auto(mut self) -> Self {
+        self.style.inset.right = LengthPercentageAuto::auto();
+        self.clear_relative_lengths(&[LengthProperty::Right]);
+        self
+    }
+
+    pub fn bottom(mut self, val: f32) -> Self {
+        self.style.inset.bottom = LengthPercentageAuto::length(val);
+        self.clear_relative_lengths(&[LengthProperty::Bottom]);
+        self
+    }
+
+    pub fn bottom_p(mut self, val: f32) -> Self {
+        self.style.inset.bottom = LengthPercentageAuto::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::Bottom]);
+        self
+    }
+
+    pub fn bottom_auto(mut self) -> Self {
+        self.style.inset.bottom = LengthPercentageAuto::auto();
+        self.clear_relative_lengths(&[LengthProperty::Bottom]);
+        self
+    }
+
+    pub fn left(mut self, val: f32) -> Self {
+        self.style.inset.left = LengthPercentageAuto::length(val);
+        self.clear_relative_lengths(&[LengthProperty::Left]);
+        self
+    }
+
+    pub fn left_p(mut self, val: f32) -> Self {
+        self.style.inset.left = LengthPercentageAuto::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::Left]);
+        self
+    }
+
+    pub fn left_auto(mut self) -> Self {
+        self.style.inset.left = LengthPercentageAuto::auto();
+        self.clear_relative_lengths(&[LengthProperty::Left]);
+        self
+    }
+
+    pub fn m(mut self, val: f32) -> Self {
+        self.style.margin = Rect::length(val);
+        self.clear_relative_lengths(&LengthProperty::MARGIN);
+        self
+    }
+
+    pub fn m_p(mut self, val: f32) -> Self {
+        self.style.margin = Rect::percent(val);
+        self.clear_relative_lengths(&LengthProperty::MARGIN);
+        self
+    }
+
+    pub fn m_auto(mut self) -> Self {
+        self.style.margin = Rect::auto();
+        self.clear_relative_lengths(&LengthProperty::MARGIN);
+        self
+    }
+
+    pub fn mx(mut self, val: f32) -> Self {
+        self.style.margin.left = LengthPercentageAuto::length(val);
+        self.style.margin.right = LengthPercentageAuto::length(val);
+        self.clear_relative_lengths(&[LengthProperty::MarginLeft, LengthProperty::MarginRight]);
+        self
+    }
+
+    pub fn mx_p(mut self, val: f32) -> Self {
+        self.style.margin.left = LengthPercentageAuto::percent(val);
+        self.style.margin.right = LengthPercentageAuto::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::MarginLeft, LengthProperty::MarginRight]);
+        self
+    }
+
+    pub fn mx_auto(mut self) -> Self {
+        self.style.margin.left = LengthPercentageAuto::auto();
+        self.style.margin.right = LengthPercentageAuto::auto();
+        self.clear_relative_lengths(&[LengthProperty::MarginLeft, LengthProperty::MarginRight]);
+        self
+    }
+
+    pub fn my(mut self, val: f32) -> Self {
+        self.style.margin.top = LengthPercentageAuto::length(val);
+        self.style.margin.bottom = LengthPercentageAuto::length(val);
+        self.clear_relative_lengths(&[LengthProperty::MarginTop, LengthProperty::MarginBottom]);
+        self
+    }
+
+    pub fn my_p(mut self, val: f32) -> Self {
+        self.style.margin.top = LengthPercentageAuto::percent(val);
+        self.style.margin.bottom = LengthPercentageAuto::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::MarginTop, LengthProperty::MarginBottom]);
+        self
+    }
+
+    pub fn my_auto(mut self) -> Self {
+        self.style.margin.top = LengthPercentageAuto::auto();
+        self.style.margin.bottom = LengthPercentageAuto::auto();
+        self.clear_relative_lengths(&[LengthProperty::MarginTop, LengthProperty::MarginBottom]);
+        self
+    }
+
+    pub fn mt(mut self, val: f32) -> Self {
+        self.style.margin.top = LengthPercentageAuto::length(val);
+        self.clear_relative_lengths(&[LengthProperty::MarginTop]);
+        self
+    }
+
+    pub fn mt_p(mut self, val: f32) -> Self {
+        self.style.margin.top = LengthPercentageAuto::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::MarginTop]);
+        self
+    }
+
+    pub fn mt_auto(mut self) -> Self {
+        self.style.margin.top = LengthPercentageAuto::auto();
+        self.clear_relative_lengths(&[LengthProperty::MarginTop]);
+        self
+    }
+
+    pub fn mr(mut self, val: f32) -> Self {
+        self.style.margin.right = LengthPercentageAuto::length(val);
+        self.clear_relative_lengths(&[LengthProperty::MarginRight]);
+        self
+    }
+
+    pub fn mr_p(mut self, val: f32) -> Self {
+        self.style.margin.right = LengthPercentageAuto::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::MarginRight]);
+        self
+    }
+
+    pub fn mr_auto(mut self) -> Self {
+        self.style.margin.right = LengthPercentageAuto::auto();
+        self.clear_relative_lengths(&[LengthProperty::MarginRight]);
+        self
+    }
+
+    pub fn mb(mut self, val: f32) -> Self {
+        self.style.margin.bottom = LengthPercentageAuto::length(val);
+        self.clear_relative_lengths(&[LengthProperty::MarginBottom]);
+        self
+    }
+
+    pub fn mb_p(mut self, val: f32) -> Self {
+        self.style.margin.bottom = LengthPercentageAuto::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::MarginBottom]);
+        self
+    }
+
+    pub fn mb_auto(mut self) -> Self {
+        self.style.margin.bottom = LengthPercentageAuto::auto();
+        self.clear_relative_lengths(&[LengthProperty::MarginBottom]);
+        self
+    }
+
+    pub fn ml(mut self, val: f32) -> Self {
+        self.style.margin.left = LengthPercentageAuto::length(val);
+        self.clear_relative_lengths(&[LengthProperty::MarginLeft]);
+        self
+    }
+
+    pub fn ml_p(mut self, val: f32) -> Self {
+        self.style.margin.left = LengthPercentageAuto::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::MarginLeft]);
+        self
+    }
+
+    pub fn ml_auto(mut self) -> Self {
+        self.style.margin.left = LengthPercentageAuto::auto();
+        self.clear_relative_lengths(&[LengthProperty::MarginLeft]);
+        self
+    }
+
+    pub fn p(mut self, val: f32) -> Self {
+        self.style.padding = Rect::length(val);
+        self.clear_relative_lengths(&LengthProperty::PADDING);
+        self
+    }
+
+    pub fn p_p(mut self, val: f32) -> Self {
+        self.style.padding = Rect::percent(val);
+        self.clear_relative_lengths(&LengthProperty::PADDING);
+        self
+    }
+
+    pub fn px(mut self, val: f32) -> Self {
+        self.style.padding.left = LengthPercentage::length(val);
+        self.style.padding.right = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::PaddingLeft, LengthProperty::PaddingRight]);
+        self
+    }
+
+    pub fn px_p(mut self, val: f32) -> Self {
+        self.style.padding.left = LengthPercentage::percent(val);
+        self.style.padding.right = LengthPercentage::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::PaddingLeft, LengthProperty::PaddingRight]);
+        self
+    }
+
+    pub fn py(mut self, val: f32) -> Self {
+        self.style.padding.top = LengthPercentage::length(val);
+        self.style.padding.bottom = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::PaddingTop, LengthProperty::PaddingBottom]);
+        self
+    }
+
+    pub fn py_p(mut self, val: f32) -> Self {
+        self.style.padding.top = LengthPercentage::percent(val);
+        self.style.padding.bottom = LengthPercentage::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::PaddingTop, LengthProperty::PaddingBottom]);
+        self
+    }
+
+    pub fn pt(mut self, val: f32) -> Self {
+        self.style.padding.top = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::PaddingTop]);
+        self
+    }
+
+    pub fn pt_p(mut self, val: f32) -> Self {
+        self.style.padding.top = LengthPercentage::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::PaddingTop]);
+        self
+    }
+
+    pub fn pr(mut self, val: f32) -> Self {
+        self.style.padding.right = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::PaddingRight]);
+        self
+    }
+
+    pub fn pr_p(mut self, val: f32) -> Self {
+        self.style.padding.right = LengthPercentage::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::PaddingRight]);
+        self
+    }
+
+    pub fn pb(mut self, val: f32) -> Self {
+        self.style.padding.bottom = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::PaddingBottom]);
+        self
+    }
+
+    pub fn pb_p(mut self, val: f32) -> Self {
+        self.style.padding.bottom = LengthPercentage::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::PaddingBottom]);
+        self
+    }
+
+    pub fn pl(mut self, val: f32) -> Self {
+        self.style.padding.left = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::PaddingLeft]);
+        self
+    }
+
+    pub fn pl_p(mut self, val: f32) -> Self {
+        self.style.padding.left = LengthPercentage::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::PaddingLeft]);
+        self
+    }
+
+    pub fn border_width(mut self, val: f32) -> Self {
+        self.style.border = Rect::length(val);
+        self.clear_relative_lengths(&LengthProperty::BORDER);
+        self
+    }
+
+    pub fn border_x(mut self, val: f32) -> Self {
+        self.style.border.left = LengthPercentage::length(val);
+        self.style.border.right = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::BorderLeft, LengthProperty::BorderRight]);
+        self
+    }
+
+    pub fn border_y(mut self, val: f32) -> Self {
+        self.style.border.top = LengthPercentage::length(val);
+        self.style.border.bottom = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::BorderTop, LengthProperty::BorderBottom]);
+        self
+    }
+
+    pub fn border_t(mut self, val: f32) -> Self {
+        self.style.border.top = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::BorderTop]);
+        self
+    }
+
+    pub fn border_r(mut self, val: f32) -> Self {
+        self.style.border.right = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::BorderRight]);
+        self
+    }
+
+    pub fn border_b(mut self, val: f32) -> Self {
+        self.style.border.bottom = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::BorderBottom]);
+        self
+    }
+
+    pub fn border_l(mut self, val: f32) -> Self {
+        self.style.border.left = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::BorderLeft]);
+        self
+    }
+
+    pub fn gap(mut self, val: f32) -> Self {
+        self.style.gap.width = LengthPercentage::length(val);
+        self.style.gap.height = LengthPercentage::length(val);
+        self.clear_relative_lengths(&LengthProperty::GAP);
+        self
+    }
+
+    pub fn gap_p(mut self, val: f32) -> Self {
+        self.style.gap.width = LengthPercentage::percent(val);
+        self.style.gap.height = LengthPercentage::percent(val);
+        self.clear_relative_lengths(&LengthProperty::GAP);
+        self
+    }
+
+    pub fn gap_x(mut self, val: f32) -> Self {
+        self.style.gap.width = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::GapX]);
+        self
+    }
+
+    pub fn gap_x_p(mut self, val: f32) -> Self {
+        self.style.gap.width = LengthPercentage::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::GapX]);
+        self
+    }
+
+    pub fn gap_y(mut self, val: f32) -> Self {
+        self.style.gap.height = LengthPercentage::length(val);
+        self.clear_relative_lengths(&[LengthProperty::GapY]);
+        self
+    }
+
+    pub fn gap_y_p(mut self, val: f32) -> Self {
+        self.style.gap.height = LengthPercentage::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::GapY]);
+        self
+    }
+
+    pub fn size(mut self, val: f32) -> Self {
+        self.style.size.width = Dimension::length(val);
+        self.style.size.height = Dimension::length(val);
+        self.clear_relative_lengths(&LengthProperty::SIZE);
+        self
+    }
+
+    pub fn size_p(mut self, val: f32) -> Self {
+        self.style.size.width = Dimension::percent(val);
+        self.style.size.height = Dimension::percent(val);
+        self.clear_relative_lengths(&LengthProperty::SIZE);
+        self
+    }
+
+    pub fn size_full(mut self) -> Self {
+        self.style.size.width = Dimension::percent(1.0);
+        self.style.size.height = Dimension::percent(1.0);
+        self.clear_relative_lengths(&LengthProperty::SIZE);
+        self
+    }
+
+    pub fn size_auto(mut self) -> Self {
+        self.style.size.width = Dimension::auto();
+        self.style.size.height = Dimension::auto();
+        self.clear_relative_lengths(&LengthProperty::SIZE);
+        self
+    }
+
+    pub fn min_w(mut self, val: f32) -> Self {
+        self.style.min_size.width = Dimension::length(val);
+        self.clear_relative_lengths(&[LengthProperty::MinWidth]);
+        self
+    }
+
+    pub fn min_w_p(mut self, val: f32) -> Self {
+        self.style.min_size.width = Dimension::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::MinWidth]);
+        self
+    }
+
+    pub fn min_w_full(mut self) -> Self {
+        self.style.min_size.width = Dimension::percent(1.0);
+        self.clear_relative_lengths(&[LengthProperty::MinWidth]);
+        self
+    }
+
+    pub fn min_w_auto(mut self) -> Self {
+        self.style.min_size.width = Dimension::auto();
+        self.clear_relative_lengths(&[LengthProperty::MinWidth]);
+        self
+    }
+
+    pub fn min_h(mut self, val: f32) -> Self {
+        self.style.min_size.height = Dimension::length(val);
+        self.clear_relative_lengths(&[LengthProperty::MinHeight]);
+        self
+    }
+
+    pub fn min_h_p(mut self, val: f32) -> Self {
+        self.style.min_size.height = Dimension::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::MinHeight]);
+        self
+    }
+
+    pub fn min_h_full(mut self) -> Self {
+        self.style.min_size.height = Dimension::percent(1.0);
+        self.clear_relative_lengths(&[LengthProperty::MinHeight]);
+        self
+    }
+
+    pub fn min_h_auto(mut self) -> Self {
+        self.style.min_size.height = Dimension::auto();
+        self.clear_relative_lengths(&[LengthProperty::MinHeight]);
+        self
+    }
+
+    pub fn max_w(mut self, val: f32) -> Self {
+        self.style.max_size.width = Dimension::length(val);
+        self.clear_relative_lengths(&[LengthProperty::MaxWidth]);
+        self
+    }
+
+    pub fn max_w_p(mut self, val: f32) -> Self {
+        self.style.max_size.width = Dimension::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::MaxWidth]);
+        self
+    }
+
+    pub fn max_w_full(mut self) -> Self {
+        self.style.max_size.width = Dimension::percent(1.0);
+        self.clear_relative_lengths(&[LengthProperty::MaxWidth]);
+        self
+    }
+
+    pub fn max_w_none(mut self) -> Self {
+        self.style.max_size.width = Dimension::auto();
+        self.clear_relative_lengths(&[LengthProperty::MaxWidth]);
+        self
+    }
+
+    pub fn max_h(mut self, val: f32) -> Self {
+        self.style.max_size.height = Dimension::length(val);
+        self.clear_relative_lengths(&[LengthProperty::MaxHeight]);
+        self
+    }
+
+    pub fn max_h_p(mut self, val: f32) -> Self {
+        self.style.max_size.height = Dimension::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::MaxHeight]);
+        self
+    }
+
+    pub fn max_h_full(mut self) -> Self {
+        self.style.max_size.height = Dimension::percent(1.0);
+        self.clear_relative_lengths(&[LengthProperty::MaxHeight]);
+        self
+    }
+
+    pub fn max_h_none(mut self) -> Self {
+        self.style.max_size.height = Dimension::auto();
+        self.clear_relative_lengths(&[LengthProperty::MaxHeight]);
+        self
+    }
+
+    pub fn aspect_ratio(mut self, ratio: f32) -> Self {
+        self.style.aspect_ratio = Some(ratio);
+        self
+    }
+
+    pub fn aspect_square(mut self) -> Self {
+        self.style.aspect_ratio = Some(1.0);
+        self
+    }
+
+    pub fn aspect_video(mut self) -> Self {
+        self.style.aspect_ratio = Some(16.0 / 9.0);
+        self
+    }
+
+    pub fn aspect_auto(mut self) -> Self {
+        self.style.aspect_ratio = None;
+        self
+    }
+
+    pub fn flex_wrap_reverse(mut self) -> Self {
+        self.style.flex_wrap = FlexWrap::WrapReverse;
+        self
+    }
+
+    pub fn flex_nowrap(mut self) -> Self {
+        self.style.flex_wrap = FlexWrap::NoWrap;
+        self
+    }
+
+    pub fn flex_initial(mut self) -> Self {
+        self.style.flex_grow = 0.0;
+        self.style.flex_shrink = 1.0;
+        self.style.flex_basis = Dimension::auto();
+        self.clear_relative_lengths(&[LengthProperty::FlexBasis]);
+        self
+    }
+
+    pub fn flex_none(mut self) -> Self {
+        self.style.flex_grow = 0.0;
+        self.style.flex_shrink = 0.0;
+        self.style.flex_basis = Dimension::auto();
+        self.clear_relative_lengths(&[LengthProperty::FlexBasis]);
+        self
+    }
+
+    pub fn flex_shrink_value(mut self, value: f32) -> Self {
+        self.style.flex_shrink = value;
+        self
+    }
+
+    pub fn flex_basis(mut self, val: f32) -> Self {
+        self.style.flex_basis = Dimension::length(val);
+        self.clear_relative_lengths(&[LengthProperty::FlexBasis]);
+        self
+    }
+
+    pub fn flex_basis_p(mut self, val: f32) -> Self {
+        self.style.flex_basis = Dimension::percent(val);
+        self.clear_relative_lengths(&[LengthProperty::FlexBasis]);
+        self
+    }
+
+    pub fn flex_basis_auto(mut self) -> Self {
+        self.style.flex_basis = Dimension::auto();
+        self.clear_relative_lengths(&[LengthProperty::FlexBasis]);
+        self
+    }
+
+    pub fn items_start(mut self) -> Self {
+        self.style.align_items = Some(AlignItems::FlexStart);
+        self
+    }
+
+    pub fn items_end(mut self) -> Self {
+        self.style.align_items = Some(AlignItems::FlexEnd);
+        self
+    }
+
+    pub fn items_baseline(mut self) -> Self {
+        self.style.align_items = Some(AlignItems::Baseline);
+        self
+    }
+
+    pub fn items_stretch(mut self) -> Self {
+        self.style.align_items = Some(AlignItems::Stretch);
+        self
+    }
+
+    pub fn self_auto(mut self) -> Self {
+        self.style.align_self = None;
+        self
+    }
+
+    pub fn self_start(mut self) -> Self {
+        self.style.align_self = Some(AlignSelf::FlexStart);
+        self
+    }
+
+    pub fn self_end(mut self) -> Self {
+        self.style.align_self = Some(AlignSelf::FlexEnd);
+        self
+    }
+
+    pub fn self_center(mut self) -> Self {
+        self.style.align_self = Some(AlignSelf::Center);
+        self
+    }
+
+    pub fn self_baseline(mut self) -> Self {
+        self.style.align_self = Some(AlignSelf::Baseline);
+        self
+    }
+
+    pub fn self_stretch(mut self) -> Self {
+        self.style.align_self = Some(AlignSelf::Stretch);
+        self
+    }
+
+    pub fn justify_items_start(mut self) -> Self {
+        self.style.justify_items = Some(JustifyItems::Start);
+        self
+    }
+
+    pub fn justify_items_end(mut self) -> Self {
+        self.style.justify_items = Some(JustifyItems::End);
+        self
+    }
+
+    pub fn justify_items_center(mut self) -> Self {
+        self.style.justify_items = Some(JustifyItems::Center);
+        self
+    }
+
+    pub fn justify_items_stretch(mut self) -> Self {
+        self.style.justify_items = Some(JustifyItems::Stretch);
+        self
+    }
+
+    pub fn justify_self_auto(mut self) -> Self {
+        self.style.justify_self = None;
+        self
+    }
+
+    pub fn justify_self_start(mut self) -> Self {
+        self.style.justify_self = Some(JustifySelf::Start);
+        self
+    }
+
+    pub fn justify_self_end(mut self) -> Self {
+        self.style.justify_self = Some(JustifySelf::End);
+        self
+    }
+
+    pub fn justify_self_center(mut self) -> Self {
+        self.style.justify_self = Some(JustifySelf::Center);
+        self
+    }
+
+    pub fn justify_self_stretch(mut self) -> Self {
+        self.style.justify_self = Some(JustifySelf::Stretch);
+        self
+    }
+
+    pub fn content_start(mut self) -> Self {
+        self.style.align_content = Some(AlignContent::FlexStart);
+        self
+    }
+
+    pub fn content_end(mut self) -> Self {
+        self.style.align_content = Some(AlignContent::FlexEnd);
+        self
+    }
+
+    pub fn content_center(mut self) -> Self {
+        self.style.align_content = Some(AlignContent::Center);
+        self
+    }
+
+    pub fn content_between(mut self) -> Self {
+        self.style.align_content = Some(AlignContent::SpaceBetween);
+        self
+    }
+
+    pub fn content_around(mut self) -> Self {
+        self.style.align_content = Some(AlignContent::SpaceAround);
+        self
+    }
+
+    pub fn content_evenly(mut self) -> Self {
+        self.style.align_content = Some(AlignContent::SpaceEvenly);
+        self
+    }
+
+    pub fn content_stretch(mut self) -> Self {
+        self.style.align_content = Some(AlignContent::Stretch);
+        self
+    }
+
+    pub fn justify_start(mut self) -> Self {
+        self.style.justify_content = Some(JustifyContent::FlexStart);
+        self
+    }
+
+    pub fn justify_end(mut self) -> Self {
+        self.style.justify_content = Some(JustifyContent::FlexEnd);
+        self
+    }
+
+    pub fn justify_between(mut self) -> Self {
+        self.style.justify_content = Some(JustifyContent::SpaceBetween);
+        self
+    }
+
+    pub fn justify_around(mut self) -> Self {
+        self.style.justify_content = Some(JustifyContent::SpaceAround);
+        self
+    }
+
+    pub fn justify_evenly(mut self) -> Self {
+        self.style.justify_content = Some(JustifyContent::SpaceEvenly);
+        self
+    }
+
+    pub fn justify_stretch(mut self) -> Self {
+        self.style.justify_content = Some(JustifyContent::Stretch);
+        self
+    }
+
+    /// Sets the order of the node among its siblings in the layout, the lower comes first.
+    ///
+    /// The siblings with the same order keep their document order, the painting order is not affected.
+    pub fn order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn order_first(mut self) -> Self {
+        self.order = -9999;
+        self
+    }
+
+    pub fn order_last(mut self) -> Self {
+        self.order = 9999;
+        self
+    }
+
+    pub fn order_none(mut self) -> Self {
+        self.order = 0;
+        self
+    }
+
+    /// Returns the layout style of the node.
+    #[inline]
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Returns the layout style of the node for the properties without builders.
+    #[inline]
+    pub fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    /// Sets the background color, it replaces the background gradient.
+    pub fn background(mut self, color: impl Into<Color>) -> Self {
+        self.background = Some(color.into());
+        self.background_gradient = None;
+        self
+    }
+
+    /// Sets the background gradient, it is drawn instead of the background color.
+    pub fn background_gradient(mut self, gradient: Gradient) -> Self {
+        self.background_gradient = Some(gradient);
+        self
+    }
+
+    #[inline]
+    pub const fn corner_radii(mut self, radii: Corners<f32>) -> Self {
+        self.corner_radii = radii;
+        self
+    }
+
+    /// Sets the border color of all sides, it replaces the border gradient and the side colors.
+    pub fn border_color(mut self, color: impl Into<Color>) -> Self {
+        self.border_color = Some(color.into());
+        self.border_gradient = None;
+        self.border_colors = Edges::all(None);
+        self
+    }
+
+    pub fn border_x_color(mut self, color: impl Into<Color>) -> Self {
+        let color = Some(color.into());
+        self.border_colors.left = color;
+        self.border_colors.right = color;
+        self
+    }
+
+    pub fn border_y_color(mut self, color: impl Into<Color>) -> Self {
+        let color = Some(color.into());
+        self.border_colors.top = color;
+        self.border_colors.bottom = color;
+        self
+    }
+
+    pub fn border_t_color(mut self, color: impl Into<Color>) -> Self {
+        self.border_colors.top = Some(color.into());
+        self
+    }
+
+    pub fn border_r_color(mut self, color: impl Into<Color>) -> Self {
+        self.border_colors.right = Some(color.into());
+        self
+    }
+
+    pub fn border_b_color(mut self, color: impl Into<Color>) -> Self {
+        self.border_colors.bottom = Some(color.into());
+        self
+    }
+
+    pub fn border_l_color(mut self, color: impl Into<Color>) -> Self {
+        self.border_colors.left = Some(color.into());
+        self
+    }
+
+    #[inline]
+    pub const fn border_style(mut self, style: BorderStyle) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    #[inline]
+    pub const fn border_solid(self) -> Self {
+        self.border_style(BorderStyle::Solid)
+    }
+
+    #[inline]
+    pub const fn border_dashed(self) -> Self {
+        self.border_style(BorderStyle::DASHED)
+    }
+
+    /// Sets the dashed border style with the length of the dashes and the gap between them.
+    #[inline]
+    pub const fn border_dash(self, length: f32, gap: f32) -> Self {
+        self.border_style(BorderStyle::Dashed { length, gap })
+    }
+
+    #[inline]
+    pub const fn border_dotted(self) -> Self {
+        self.border_style(BorderStyle::DOTTED)
+    }
+
+    #[inline]
+    pub const fn border_double(self) -> Self {
+        self.border_style(BorderStyle::Double)
+    }
+
+    /// Sets the border gradient, it is drawn instead of the border color.
+    pub fn border_gradient(mut self, gradient: Gradient) -> Self {
+        self.border_gradient = Some(gradient);
+        self
+    }
+
+    #[inline]
+    pub const fn border(mut self, border: Rect<LengthPercentage>) -> Self {
+        self.style.border = border;
+        self.clear_relative_lengths(&LengthProperty::BORDER);
+        self
+    }
+
+    /// Sets all the shadows, the outer and the inset ones.
+    pub fn shadow(mut self, shadows: Vec<BoxShadow>) -> Self {
+        self.box_shadow = Some(SmallVec::from_vec(shadows));
+        self
+    }
+
+    /// Removes the outer shadows, the inset shadows are kept.
+    pub fn shadow_none(mut self) -> Self {
+        self.set_shadows(false, &[]);
+        self
+    }
+
+    pub fn shadow_2xs(mut self) -> Self {
+        self.set_shadows(false, &BoxShadow::XS2);
+        self
+    }
+
+    pub fn shadow_xs(mut self) -> Self {
+        self.set_shadows(false, &BoxShadow::XS);
+        self
+    }
+
+    pub fn shadow_sm(mut self) -> Self {
+        self.set_shadows(false, &BoxShadow::SM);
+        self
+    }
+
+    pub fn shadow_md(mut self) -> Self {
+        self.set_shadows(false, &BoxShadow::MD);
+        self
+    }
+
+    pub fn shadow_lg(mut self) -> Self {
+        self.set_shadows(false, &BoxShadow::LG);
+        self
+    }
+
+    pub fn shadow_xl(mut self) -> Self {
+        self.set_shadows(false, &BoxShadow::XL);
+        self
+    }
+
+    pub fn shadow_2xl(mut self) -> Self {
+        self.set_shadows(false, &BoxShadow::XL2);
+        self
+    }
+
+    /// Removes the inset shadows, the outer shadows are kept.
+    pub fn inset_shadow_none(mut self) -> Self {
+        self.set_shadows(true, &[]);
+        self
+    }
+
+    pub fn inset_shadow_2xs(mut self) -> Self {
+        self.set_shadows(true, &BoxShadow::INSET_XS2);
+        self
+    }
+
+    pub fn inset_shadow_xs(mut self) -> Self {
+        self.set_shadows(true, &BoxShadow::INSET_XS);
+        self
+    }
+
+    pub fn inset_shadow_sm(mut self) -> Self {
+        self.set_shadows(true, &BoxShadow::INSET_SM);
+        self
+    }
+
+    /// Replaces the outer or the inset shadows, like the separate `box-shadow` and
+    /// `inset-shadow` utilities of Tailwind CSS.
+    ///
+    /// The outer shadows go before the inset shadows, so the list does not depend on the order in
+    /// which they are set, and the transitions mix the same shadows.
+    pub(crate) fn set_shadows(&mut self, inset: bool, shadows: &[BoxShadow]) {
+        let mut box_shadow = self.box_shadow.take().unwrap_or_default();
+        box_shadow.retain(|shadow| shadow.inset != inset);
+
+        let index = match inset {
+            true => box_shadow.len(),
+            false => 0,
+        };
+        box_shadow.insert_many(index, shadows.iter().copied());
+
+        self.box_shadow = Some(box_shadow).filter(|shadows| !shadows.is_empty());
+    }
+
+    /// Sets the outline, which is drawn around the border box without affecting the layout.
+    #[inline]
+    pub const fn outline(mut self, outline: Outline) -> Self {
+        self.outline = Some(outline);
+        self
+    }
+
+    #[inline]
+    pub const fn outline_none(mut self) -> Self {
+        self.outline = None;
+        self
+    }
+
+    pub fn p_px(mut self) -> Self {
+        self.style.padding = Rect::length(1.0);
+        self.clear_relative_lengths(&LengthProperty::PADDING);
+        self
+    }
+}
+
+pub fn div() -> Div {
+    Div::DEFAULT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_div_builder() {
+        let d = div().flex();
+
+        assert_eq!(d.style.display, Display::Flex);
+
+        let d = d.block();
+
+        assert_eq!(d.style.display, Display::Block);
+    }
+
+    #[test]
+    fn test_div_sizing() {
+        let d = div().w(100.0);
+
+        assert_eq!(d.style.size.width, Dimension::length(100.0));
+
+        let d = d.h_full();
+
+        assert_eq!(d.style.size.height, Dimension::percent(1.0));
+    }
+
+    #[test]
+    fn test_div_min_max_sizing() {
+        let d = div()
+            .size(20.0)
+            .min_w_p(0.5)
+            .max_w(200.0)
+            .min_h_auto()
+            .max_h_full();
+
+        assert_eq!(d.style.size, Size::from_lengths(20.0, 20.0));
+        assert_eq!(d.style.min_size.width, Dimension::percent(0.5));
+        assert_eq!(d.style.min_size.height, Dimension::auto());
+        assert_eq!(d.style.max_size.width, Dimension::length(200.0));
+        assert_eq!(d.style.max_size.height, Dimension::percent(1.0));
+
+        let d = d.max_w_none().aspect_video();
+
+        assert_eq!(d.style.max_size.width, Dimension::auto());
+        assert_eq!(d.style.aspect_ratio, Some(16.0 / 9.0));
+    }
+
+    #[test]
+    fn test_div_spacing() {
+        let d = div().p(8.0).px(4.0).pt_p(0.1).m(2.0).mx_auto().mb(-4.0);
+
+        assert_eq!(d.style.padding.top, LengthPercentage::percent(0.1));
+        assert_eq!(d.style.padding.right, LengthPercentage::length(4.0));
+        assert_eq!(d.style.padding.bottom, LengthPercentage::length(8.0));
+        assert_eq!(d.style.padding.left, LengthPercentage::length(4.0));
+        assert_eq!(d.style.margin.top, LengthPercentageAuto::length(2.0));
+        assert_eq!(d.style.margin.right, LengthPercentageAuto::auto());
+        assert_eq!(d.style.margin.bottom, LengthPercentageAuto::length(-4.0));
+        assert_eq!(d.style.margin.left, LengthPercentageAuto::auto());
+
+        let d = d.gap(4.0).gap_y_p(0.5).border_width(1.0).border_t(2.0);
+
+        assert_eq!(d.style.gap.width, LengthPercentage::length(4.0));
+        assert_eq!(d.style.gap.height, LengthPercentage::percent(0.5));
+        assert_eq!(d.style.border.top, LengthPercentage::length(2.0));
+        assert_eq!(d.style.border.bottom, LengthPercentage::length(1.0));
+    }
+
+    #[test]
+    fn test_div_positioning() {
+        let d = div().absolute().inset(0.0).top_p(0.5).left_auto();
+
+        assert_eq!(d.style.position, Position::Absolute);
+        assert_eq!(d.style.inset.top, LengthPercentageAuto::percent(0.5));
+        assert_eq!(d.style.inset.right, LengthPercentageAuto::length(0.0));
+        assert_eq!(d.style.inset.bottom, LengthPercentageAuto::length(0.0));
+        assert_eq!(d.style.inset.left, LengthPercentageAuto::auto());
+
+        let d = d.inset_y(10.0);
+
+        assert_eq!(d.style.inset.top, LengthPercentageAuto::length(10.0));
+        assert_eq!(d.style.inset.bottom, LengthPercentageAuto::length(10.0));
+    }
+
+    #[test]
+    fn test_div_flex_and_alignment() {
+        let d = div()
+            .flex_none()
+            .flex_basis_p(0.25)
+            .flex_wrap_reverse()
+            .order(2)
+            .self_end()
+            .justify_self_center()
+            .justify_items_stretch()
+            .content_between()
+            .justify_evenly();
+
+        assert_eq!(d.style.flex_grow, 0.0);
+        assert_eq!(d.style.flex_shrink, 0.0);
+        assert_eq!(d.style.flex_basis, Dimension::percent(0.25));
+        assert_eq!(d.style.flex_wrap, FlexWrap::WrapReverse);
+        assert_eq!(d.order, 2);
+        assert_eq!(d.style.align_self, Some(AlignSelf::FlexEnd));
+        assert_eq!(d.style.justify_self, Some(JustifySelf::Center));
+        assert_eq!(d.style.justify_items, Some(JustifyItems::Stretch));
+        assert_eq!(d.style.align_content, Some(AlignContent::SpaceBetween));
+        assert_eq!(d.style.justify_content, Some(JustifyContent::SpaceEvenly));
+    }
+
+    #[test]
+    fn test_div_overflow() {
+        let d = div().overflow_y_scroll();
+
+        assert_eq!(d.style.overflow.x, Overflow::Visible);
+        assert_eq!(d.scrollable(), BVec2::new(false, true));
+
+        let d = d.overflow_scroll();
+
+        assert_eq!(d.scrollable(), BVec2::TRUE);
+
+        let d = d.overflow_hidden();
+
+        assert_eq!(d.scrollable(), BVec2::FALSE);
+        assert_eq!(d.clipped(), BVec2::TRUE);
+    }
+
+    /// Scenes serialize each component with the reflection serializer of its type.
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_scene_round_trip() {
+        use core::fmt::Debug;
+
+        use bevy_reflect::{
+            FromReflect, TypePath, TypeRegistry,
+            serde::{TypedReflectDeserializer, TypedReflectSerializer},
+        };
+        use serde::de::DeserializeSeed;
+
+        use crate::prelude::{Image, ObjectPosition, Text, text};
+
+        fn round_trip<T>(value: &T, registry: &TypeRegistry)
+        where
+            T: FromReflect + TypePath + PartialEq + Debug,
+        {
+            let serialized = ron::to_string(&TypedReflectSerializer::new(value, registry)).unwrap();
+
+            let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+            let deserialized = TypedReflectDeserializer::of::<T>(registry)
+                .deserialize(&mut deserializer)
+                .unwrap();
+
+            assert_eq!(
+                T::from_reflect(deserialized.as_ref()).as_ref(),
+                Some(value),
+                "{serialized}"
+            );
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Div>();
+        registry.register::<Image>();
+        registry.register::<Text>();
+
+        round_trip(
+            &div()
+                .flex_col()
+                .w(120.0)
+                .h_full()
+                .overflow_y_scroll()
+                .p_px()
+                .border(Rect::length(2.0))
+                .background(Color::WHITE)
+                .border_color(Color::BLACK)
+                .corner_radii(Corners::all(8.0).top_left(0.0))
+                .shadow_md(),
+            &registry,
+        );
+        round_trip(
+            &Image::DEFAULT
+                .object_fit_contain()
+                .object_position(ObjectPosition::TOP_LEFT)
+                .flip_x(),
+            &registry,
+        );
+        round_trip(&text("Moon"), &registry);
+    }
+
+    /// The layout style round-trips through the serializer of a whole scene.
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_dynamic_scene_round_trip() {
+        use bevy_ecs::{entity::EntityHashMap, reflect::AppTypeRegistry, world::World};
+        use bevy_scene::{DynamicScene, DynamicSceneBuilder, serde::SceneDeserializer};
+        use serde::de::DeserializeSeed;
+
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Div>();
+
+        let node = div()
+            .grid()
+            .grid_cols("1fr 2fr")
+            .w(160.0)
+            .min_h(40.0)
+            .px(4.0)
+            .absolute()
+            .top(8.0)
+            .background(Color::WHITE)
+            .shadow_md();
+
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        let entity = world.spawn(node.clone()).id();
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .deny_all()
+            .allow_component::<Div>()
+            .extract_entity(entity)
+            .build();
+        let serialized = scene.serialize(&registry.read()).unwrap();
+
+        let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+        let scene: DynamicScene = SceneDeserializer {
+            type_registry: &registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+        let mut world = World::new();
+        world.insert_resource(registry);
+        scene
+            .write_to_world(&mut world, &mut EntityHashMap::default())
+            .unwrap();
+
+        let mut query = world.query::<&Div>();
+        assert_eq!(query.single(&world).unwrap(), &node, "{serialized}");
     }
 }
@@ -0,0 +1,15 @@
+use bevy_camera::visibility::Visibility;
+use bevy_ecs::{component::Component, prelude::ReflectComponent};
+use bevy_reflect::{Reflect, prelude::ReflectDefault};
+use bevy_transform::components::Transform;
+
+/// A node which takes part in the hierarchy without producing a layout node, like `display: contents`.
+///
+/// Its children are laid out as the direct children of its nearest [`Div`](super::div::Div) ancestor,
+/// so the wrapper entities, for example for conditional spawning, don't break the layouts.
+///
+/// It should keep the default [`Transform`], since it is not positioned by the layout.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[require(Transform, Visibility)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct GhostNode;
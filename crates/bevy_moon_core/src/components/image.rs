@@ -8,9 +8,12 @@ use crate::style::{ObjectFit, ObjectPosition};
 
 use super::div::Div;
 
-#[derive(Component, Clone, Debug, Reflect)]
+/// Draws an image in the node.
+///
+/// The handle is serialized by reflection, so only the handles with UUIDs round-trip through scenes.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
 #[require(Div)]
-#[reflect(Component, Clone, Debug, Default)]
+#[reflect(Component, Clone, Debug, Default, PartialEq)]
 pub struct Image {
     pub color: Color,
     pub handle: Handle<bevy_image::Image>,
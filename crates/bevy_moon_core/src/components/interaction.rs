@@ -0,0 +1,577 @@
+//! The interaction states of the nodes, and the style variants which they select.
+//!
+//! ```ignore
+//! div()
+//!     .background(WHITE)
+//!     .hover(|d| d.background(GRAY_100).shadow_lg())
+//!     .active(|d| d.background(GRAY_200).shadow_none())
+//!     .disabled(|d| d.background(GRAY_50))
+//! ```
+//!
+//! A variant records the fields which its closure changes on the node as it is when the variant
+//! is declared, so the variants are declared after the base style. The nodes with variants get an
+//! [`InteractionState`], which is updated from the pointers and the [`InputFocus`], and the fields
+//! of the active variants are applied on top of the base style into the
+//! [`ComputedDiv`](super::computed::ComputedDiv) before the layout, the [`Div`] is left as it is.
+
+use std::borrow::Cow;
+
+use bevy_color::Color;
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    component::Component,
+    entity::{Entity, EntityHashSet},
+    prelude::ReflectComponent,
+    system::{Local, Query, Res},
+};
+use bevy_input_focus::InputFocus;
+use bevy_reflect::{Reflect, prelude::ReflectDefault};
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use smallvec::SmallVec;
+use taffy::Style;
+
+use super::div::Div;
+use crate::{
+    style::{BorderStyle, BoxShadow, Corners, Edges, Gradient, LayoutStyle, Outline},
+    theme::ThemeProperty,
+    units::{LengthProperty, RelativeLength},
+};
+
+/// The interaction state of a node.
+///
+/// It is inserted on the nodes with style variants, and can be inserted on any node which needs it.
+/// The pointer and focus states are updated every frame, the `disabled` state is set by the users.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct InteractionState {
+    /// A pointer is over the node or one of its descendants, requires the `picking` feature.
+    pub hovered: bool,
+
+    /// A pointer over the node or one of its descendants is pressed, requires the `picking` feature.
+    pub pressed: bool,
+
+    /// The node has the [`InputFocus`].
+    pub focused: bool,
+
+    /// The node is disabled.
+    pub disabled: bool,
+}
+
+impl InteractionState {
+    pub const DEFAULT: Self = Self {
+        hovered: false,
+        pressed: false,
+        focused: false,
+        disabled: false,
+    };
+
+    /// Returns a disabled state.
+    pub const fn disabled() -> Self {
+        Self {
+            disabled: true,
+            ..Self::DEFAULT
+        }
+    }
+}
+
+macro_rules! style_fields {
+    ($($field:ident: $($path:ident).+),* $(,)?) => {
+        /// The fields of the layout style, which are patched one by one by the variants.
+        #[allow(non_camel_case_types)]
+        #[derive(Clone, Copy)]
+        enum StyleField {
+            $($field,)*
+        }
+
+        impl StyleField {
+            /// The whole style, for the fields which are only set with [`Div::style_mut`].
+            const ALL: u64 = u64::MAX;
+
+            const fn bit(self) -> u64 {
+                1 << self as u64
+            }
+
+            /// Returns the mask of the fields which differ between the styles.
+            fn diff(base: &Style, variant: &Style) -> u64 {
+                let mut fields = 0;
+                $(
+                    if base.$($path).+ != variant.$($path).+ {
+                        fields |= Self::$field.bit();
+                    }
+                )*
+
+                let mut patched = base.clone();
+                Self::apply(&mut patched, variant, fields);
+                if patched == *variant { fields } else { Self::ALL }
+            }
+
+            /// Copies the fields of the mask from the variant.
+            fn apply(style: &mut Style, variant: &Style, fields: u64) {
+                if fields == Self::ALL {
+                    style.clone_from(variant);
+                    return;
+                }
+                $(
+                    if fields & Self::$field.bit() != 0 {
+                        style.$($path).+.clone_from(&variant.$($path).+);
+                    }
+                )*
+            }
+        }
+    };
+}
+
+style_fields! {
+    display: display,
+    box_sizing: box_sizing,
+    overflow: overflow,
+    scrollbar_width: scrollbar_width,
+    position: position,
+    inset_left: inset.left,
+    inset_right: inset.right,
+    inset_top: inset.top,
+    inset_bottom: inset.bottom,
+    size_width: size.width,
+    size_height: size.height,
+    min_size_width: min_size.width,
+    min_size_height: min_size.height,
+    max_size_width: max_size.width,
+    max_size_height: max_size.height,
+    aspect_ratio: aspect_ratio,
+    margin_left: margin.left,
+    margin_right: margin.right,
+    margin_top: margin.top,
+    margin_bottom: margin.bottom,
+    padding_left: padding.left,
+    padding_right: padding.right,
+    padding_top: padding.top,
+    padding_bottom: padding.bottom,
+    border_left: border.left,
+    border_right: border.right,
+    border_top: border.top,
+    border_bottom: border.bottom,
+    align_items: align_items,
+    align_self: align_self,
+    justify_items: justify_items,
+    justify_self: justify_self,
+    align_content: align_content,
+    justify_content: justify_content,
+    gap_width: gap.width,
+    gap_height: gap.height,
+    flex_direction: flex_direction,
+    flex_wrap: flex_wrap,
+    flex_basis: flex_basis,
+    flex_grow: flex_grow,
+    flex_shrink: flex_shrink,
+    grid_template_rows: grid_template_rows,
+    grid_template_columns: grid_template_columns,
+    grid_auto_rows: grid_auto_rows,
+    grid_auto_columns: grid_auto_columns,
+    grid_auto_flow: grid_auto_flow,
+    grid_template_areas: grid_template_areas,
+    grid_template_column_names: grid_template_column_names,
+    grid_template_row_names: grid_template_row_names,
+    grid_row: grid_row,
+    grid_column: grid_column,
+}
+
+macro_rules! div_patch {
+    ($($field:ident: $ty:ty),* $(,)?) => {
+        /// The fields of a node which are overridden by a variant.
+        ///
+        /// Only the fields which the variant has changed are recorded, down to the fields of the
+        /// layout style, the relative lengths and the tokens, so the fields which are set on the
+        /// base style after the variant is declared are kept while the variant is applied.
+        #[derive(Clone, Debug, Default, PartialEq, Reflect)]
+        #[reflect(Clone, Debug, Default, PartialEq)]
+        #[cfg_attr(
+            feature = "serialize",
+            derive(serde::Serialize, serde::Deserialize),
+            reflect(Serialize, Deserialize)
+        )]
+        pub(crate) struct DivPatch {
+            /// The layout style of the variant, of which the fields in `style_fields` are applied.
+            style: Option<LayoutStyle>,
+
+            /// The fields of the layout style which are set by the variant, see `StyleField`.
+            style_fields: u64,
+
+            /// The relative lengths which are set by the variant.
+            relative_lengths: SmallVec<[(LengthProperty, RelativeLength); 2]>,
+
+            /// The properties which are set to absolute lengths by the variant.
+            absolute_lengths: u32,
+
+            /// The tokens which are set by the variant, `None` for the removed ones.
+            tokens: SmallVec<[(ThemeProperty, Option<Cow<'static, str>>); 2]>,
+
+            $($field: Option<$ty>,)*
+        }
+
+        impl DivPatch {
+            /// Returns the fields of the variant which differ from the base.
+            fn diff(base: &Div, variant: &Div) -> Self {
+                let style_fields = StyleField::diff(&base.style, &variant.style);
+
+                let relative_lengths = variant
+                    .relative_lengths
+                    .iter()
+                    .filter(|&&(property, length)| {
+                        variant.is_relative(property)
+                            && !(base.is_relative(property)
+                                && base.relative_lengths.contains(&(property, length)))
+                    })
+                    .copied()
+                    .collect();
+
+                let tokens = variant
+                    .tokens
+                    .iter()
+                    .filter(|token| !base.tokens.contains(token))
+                    .map(|(property, name)| (*property, Some(name.clone())))
+                    .chain(
+                        base.tokens
+                            .iter()
+                            .filter(|(property, _)| {
+                                !variant.tokens.iter().any(|(other, _)| other == property)
+                            })
+                            .map(|(property, _)| (*property, None)),
+                    )
+                    .collect();
+
+                Self {
+                    style: (style_fields != 0).then(|| variant.style.clone()),
+                    style_fields,
+                    relative_lengths,
+                    absolute_lengths: base.relative_mask & !variant.relative_mask,
+                    tokens,
+                    $($field: (base.$field != variant.$field).then(|| variant.$field.clone()),)*
+                }
+            }
+
+            /// Applies the fields on the node.
+            fn apply(&self, div: &mut Div) {
+                if let Some(style) = &self.style {
+                    StyleField::apply(&mut div.style, style, self.style_fields);
+                }
+
+                div.relative_mask &= !self.absolute_lengths;
+                for &(property, length) in &self.relative_lengths {
+                    div.set_relative_length(property, length);
+                }
+
+                for (property, name) in &self.tokens {
+                    div.tokens.retain(|(other, _)| other != property);
+                    if let Some(name) = name {
+                        div.tokens.push((*property, name.clone()));
+                    }
+                }
+
+                $(
+                    if let Some(value) = &self.$field {
+                        div.$field.clone_from(value);
+                    }
+                )*
+            }
+        }
+    };
+}
+
+div_patch! {
+    order: i32,
+    background: Option<Color>,
+    background_gradient: Option<Gradient>,
+    corner_radii: Corners<f32>,
+    border_color: Option<Color>,
+    border_gradient: Option<Gradient>,
+    border_colors: Edges<Option<Color>>,
+    border_style: BorderStyle,
+    box_shadow: Option<SmallVec<[BoxShadow; 2]>>,
+    outline: Option<Outline>,
+}
+
+/// The interaction states which select a style variant, see [`Div::hover`].
+///
+/// The variants are applied in this order, so the later ones win.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[reflect(Clone, Debug, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub(crate) enum VariantState {
+    Hover,
+    Focus,
+    Active,
+    Disabled,
+}
+
+impl VariantState {
+    fn is_active(self, state: &InteractionState) -> bool {
+        match self {
+            Self::Hover => state.hovered,
+            Self::Focus => state.focused,
+            Self::Active => state.pressed,
+            Self::Disabled => state.disabled,
+        }
+    }
+}
+
+impl Div {
+    /// Overrides the style while the node is hovered.
+    pub fn hover(self, f: impl FnOnce(Self) -> Self) -> Self {
+        self.variant(VariantState::Hover, f)
+    }
+
+    /// Overrides the style while the node has the focus.
+    pub fn focus(self, f: impl FnOnce(Self) -> Self) -> Self {
+        self.variant(VariantState::Focus, f)
+    }
+
+    /// Overrides the style while the node is pressed.
+    pub fn active(self, f: impl FnOnce(Self) -> Self) -> Self {
+        self.variant(VariantState::Active, f)
+    }
+
+    /// Overrides the style while the node is disabled.
+    pub fn disabled(self, f: impl FnOnce(Self) -> Self) -> Self {
+        self.variant(VariantState::Disabled, f)
+    }
+
+    /// Returns `true` if the node has some style variants.
+    pub fn has_variants(&self) -> bool {
+        !self.variants.is_empty()
+    }
+
+    fn variant(mut self, state: VariantState, f: impl FnOnce(Self) -> Self) -> Self {
+        let variants = core::mem::take(&mut self.variants);
+        let mut variant = f(self.clone());
+        variant.drop_overridden_tokens(&self);
+        let patch = DivPatch::diff(&self, &variant);
+
+        self.variants = variants;
+        match self
+            .variants
+            .binary_search_by_key(&state, |(other, _)| *other)
+        {
+            Ok(index) => self.variants[index].1 = patch,
+            Err(index) => self.variants.insert(index, (state, patch)),
+        }
+        self
+    }
+
+    /// Applies the variants of the state on top of the style, and drops all the variants.
+    pub(crate) fn apply_variants(&mut self, state: &InteractionState) {
+        for (variant, patch) in core::mem::take(&mut self.variants) {
+            if variant.is_active(state) {
+                patch.apply(self);
+            }
+        }
+    }
+}
+
+/// Updates the interaction states from the pointers and the input focus.
+pub fn update_interaction_states(
+    input_focus: Option<Res<InputFocus>>,
+    #[cfg(feature = "picking")] hover_map: Option<Res<bevy_picking::hover::HoverMap>>,
+    #[cfg(feature = "picking")] pointer_query: Query<(
+        &bevy_picking::pointer::PointerId,
+        &bevy_picking::pointer::PointerPress,
+    )>,
+    #[cfg(feature = "picking")] parent_query: Query<&bevy_ecs::hierarchy::ChildOf>,
+    mut state_query: Query<(Entity, &mut InteractionState)>,
+    mut hovered: Local<EntityHashSet>,
+    mut pressed: Local<EntityHashSet>,
+) {
+    if state_query.is_empty() {
+        return;
+    }
+
+    hovered.clear();
+    pressed.clear();
+
+    // A node is hovered when a pointer is over it or one of its descendants.
+    #[cfg(feature = "picking")]
+    for (pointer_id, hits) in hover_map.iter().flat_map(|hover_map| hover_map.iter()) {
+        let is_pressed = pointer_query
+            .iter()
+            .any(|(id, press)| id == pointer_id && press.is_primary_pressed());
+
+        for &entity in hits.keys() {
+            for entity in core::iter::once(entity).chain(parent_query.iter_ancestors(entity)) {
+                hovered.insert(entity);
+                if is_pressed {
+                    pressed.insert(entity);
+                }
+            }
+        }
+    }
+
+    let focused = input_focus.and_then(|input_focus| input_focus.0);
+
+    for (entity, mut state) in &mut state_query {
+        let next_state = InteractionState {
+            hovered: hovered.contains(&entity),
+            pressed: pressed.contains(&entity),
+            focused: focused == Some(entity),
+            disabled: state.disabled,
+        };
+
+        state.set_if_neq(next_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_color::palettes::basic::{GRAY, SILVER, WHITE};
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use taffy::{Dimension, LengthPercentage};
+
+    use super::*;
+    use crate::{
+        components::computed::{ComputedDiv, update_computed_divs},
+        prelude::div,
+        theme::MoonTheme,
+    };
+
+    const HOVERED: InteractionState = InteractionState {
+        hovered: true,
+        ..InteractionState::DEFAULT
+    };
+
+    fn button() -> Div {
+        div()
+            .w(100.0)
+            .background(WHITE)
+            .hover(|d| d.background(SILVER).shadow_lg())
+            .active(|d| d.background(GRAY).w(96.0))
+            .disabled(|d| d.background(GRAY).shadow_none())
+    }
+
+    fn resolve(node: &Div, state: &InteractionState) -> Div {
+        let mut node = node.clone();
+        node.apply_variants(state);
+        node
+    }
+
+    #[test]
+    fn test_variant_patch() {
+        let node = button();
+        let (state, hover) = &node.variants[0];
+
+        assert_eq!(*state, VariantState::Hover);
+        assert_eq!(hover.background, Some(Some(SILVER.into())));
+        assert!(hover.box_shadow.is_some());
+        assert!(hover.style.is_none());
+        assert!(hover.corner_radii.is_none());
+
+        // The later variants win.
+        let current = resolve(
+            &node,
+            &InteractionState {
+                pressed: true,
+                ..HOVERED
+            },
+        );
+        assert_eq!(current.background, Some(GRAY.into()));
+        assert_eq!(current.box_shadow, hover.box_shadow);
+        assert_eq!(current.style().size.width, Dimension::length(96.0));
+        assert!(!current.has_variants());
+
+        let current = resolve(&node, &InteractionState::DEFAULT);
+        assert_eq!(current.background, Some(WHITE.into()));
+        assert_eq!(current.style().size.width, Dimension::length(100.0));
+    }
+
+    #[test]
+    fn test_variant_keeps_later_style() {
+        // Only the fields which are set by the variant are overridden.
+        let node = div().w(100.0).hover(|d| d.h(20.0)).p(4.0).w(50.0);
+        let current = resolve(&node, &HOVERED);
+
+        assert_eq!(current.style().size.width, Dimension::length(50.0));
+        assert_eq!(current.style().size.height, Dimension::length(20.0));
+        assert_eq!(current.style().padding.top, LengthPercentage::length(4.0));
+
+        // The relative lengths are overridden by the absolute lengths of the variant.
+        let node = div().w_rem(2.0).hover(|d| d.w(96.0)).h_rem(1.0);
+
+        let current = resolve(&node, &HOVERED);
+        assert!(!current.is_relative(LengthProperty::Width));
+        assert!(current.is_relative(LengthProperty::Height));
+        assert_eq!(current.style().size.width, Dimension::length(96.0));
+
+        let current = resolve(&node, &InteractionState::DEFAULT);
+        assert!(current.is_relative(LengthProperty::Width));
+    }
+
+    #[test]
+    fn test_update_computed_divs() {
+        let mut world = World::new();
+        world.init_resource::<MoonTheme>();
+
+        let node = world.spawn(button()).id();
+        world.run_system_once(update_computed_divs).unwrap();
+
+        assert_eq!(
+            world.get::<InteractionState>(node),
+            Some(&InteractionState::DEFAULT)
+        );
+
+        // Returns the computed style, and whether its layout has been changed.
+        let set_state = |world: &mut World, state: InteractionState| {
+            world.clear_trackers();
+            *world.get_mut::<InteractionState>(node).unwrap() = state;
+            world.run_system_once(update_computed_divs).unwrap();
+
+            let computed_div = world.entity(node).get_ref::<ComputedDiv>().unwrap();
+            (computed_div.0.clone(), computed_div.is_changed())
+        };
+
+        let (current, changed) = set_state(&mut world, HOVERED);
+        assert_eq!(current.background, Some(SILVER.into()));
+        assert!(current.box_shadow.is_some());
+        assert_eq!(current.style().size.width, Dimension::length(100.0));
+        assert!(!changed);
+
+        let (current, changed) = set_state(
+            &mut world,
+            InteractionState {
+                pressed: true,
+                ..HOVERED
+            },
+        );
+        assert_eq!(current.background, Some(GRAY.into()));
+        assert_eq!(current.style().size.width, Dimension::length(96.0));
+        assert!(changed);
+
+        let (current, _) = set_state(&mut world, InteractionState::disabled());
+        assert_eq!(current.background, Some(GRAY.into()));
+        assert_eq!(current.box_shadow, None);
+        assert_eq!(current.style().size.width, Dimension::length(100.0));
+
+        // The base style is restored, and the node is left as it has been set.
+        let (current, _) = set_state(&mut world, InteractionState::DEFAULT);
+        assert_eq!(current, resolve(&button(), &InteractionState::DEFAULT));
+        assert_eq!(world.get::<Div>(node), Some(&button()));
+    }
+
+    #[test]
+    fn test_update_focused_state() {
+        let mut world = World::new();
+
+        let focused = world.spawn((div(), InteractionState::DEFAULT)).id();
+        let other = world.spawn((div(), InteractionState::disabled())).id();
+        world.insert_resource(InputFocus(Some(focused)));
+
+        world.run_system_once(update_interaction_states).unwrap();
+
+        assert!(world.get::<InteractionState>(focused).unwrap().focused);
+        assert_eq!(
+            world.get::<InteractionState>(other),
+            Some(&InteractionState::disabled())
+        );
+    }
+}
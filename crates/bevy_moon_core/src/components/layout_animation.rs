@@ -0,0 +1,559 @@
+//! The layout animations of the nodes, which animate a node from its previous layout box to the
+//! new one when its siblings are inserted, removed or reordered.
+//!
+//! ```ignore
+//! commands.spawn((
+//!     div().flex_col().gap(4.0),
+//!     children![
+//!         (div().h(24.0), LayoutAnimation::spring(170.0, 26.0)),
+//!         (div().h(24.0), LayoutAnimation::new(Duration::from_millis(200))),
+//!     ],
+//! ));
+//!
+//! // Fades the node out, it is despawned when its exit animation has ended.
+//! app.add_observer(|exit: On<LayoutExit>, mut query: Query<&mut Div>| {
+//!     if let Ok(mut div) = query.get_mut(exit.entity) {
+//!         div.background = Some(Color::NONE);
+//!     }
+//! });
+//! commands.entity(item).despawn_after_exit();
+//! ```
+//!
+//! The animations follow the FLIP technique: the node is laid out at once, then its [`Transform`]
+//! is offset and scaled back to its previous box, and the offset and the scale are played back to
+//! the identity. The scale is applied to the descendants as well, and the transform is restored
+//! before the layout, so the transforms set by the users are kept.
+
+use core::time::Duration;
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::EntityEvent,
+    prelude::ReflectComponent,
+    system::{Commands, EntityCommands, Query, Res},
+    world::EntityWorldMut,
+};
+use bevy_math::{
+    Vec2,
+    curve::{Curve, EaseFunction},
+};
+use bevy_reflect::{Reflect, prelude::ReflectDefault};
+use bevy_time::Time;
+use bevy_transform::components::Transform;
+
+use super::computed::ComputedLayout;
+use crate::geometry::FLIP_Y;
+
+/// Animates a node from its previous layout box to the new one.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[require(LayoutAnimationState)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct LayoutAnimation {
+    /// How the node moves to its new layout box.
+    pub motion: LayoutMotion,
+
+    /// The size is animated as well as the position.
+    pub size: bool,
+
+    /// The duration of the exit animation, see [`LayoutExit`].
+    pub exit_duration: Duration,
+}
+
+impl Default for LayoutAnimation {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl LayoutAnimation {
+    pub const DEFAULT: Self = Self {
+        motion: LayoutMotion::Ease {
+            duration: Duration::from_millis(150),
+            easing: EaseFunction::CubicInOut,
+        },
+        size: true,
+        exit_duration: Duration::from_millis(150),
+    };
+
+    /// Eases the node to its new layout box for the duration.
+    pub const fn new(duration: Duration) -> Self {
+        Self {
+            motion: LayoutMotion::Ease {
+                duration,
+                easing: EaseFunction::CubicInOut,
+            },
+            exit_duration: duration,
+            ..Self::DEFAULT
+        }
+    }
+
+    /// Moves the node to its new layout box with a spring.
+    pub const fn spring(stiffness: f32, damping: f32) -> Self {
+        Self {
+            motion: LayoutMotion::Spring { stiffness, damping },
+            ..Self::DEFAULT
+        }
+    }
+
+    pub const fn with_motion(mut self, motion: LayoutMotion) -> Self {
+        self.motion = motion;
+        self
+    }
+
+    pub const fn with_size(mut self, size: bool) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub const fn with_exit_duration(mut self, exit_duration: Duration) -> Self {
+        self.exit_duration = exit_duration;
+        self
+    }
+}
+
+/// How a node moves to its new layout box.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum LayoutMotion {
+    /// Follows the easing curve for the duration.
+    Ease {
+        duration: Duration,
+        easing: EaseFunction,
+    },
+
+    /// Follows a damped spring of unit mass, until it comes to rest.
+    Spring { stiffness: f32, damping: f32 },
+}
+
+impl LayoutMotion {
+    /// The remaining distance below which a spring comes to rest.
+    const REST_THRESHOLD: f32 = 1.0e-3;
+
+    /// Advances the remaining fraction of the motion, from `1.0` to `0.0`.
+    ///
+    /// Returns `true` when the motion has ended.
+    fn advance(&self, state: &mut LayoutAnimationState, delta: Duration) -> bool {
+        match *self {
+            Self::Ease { duration, easing } => {
+                state.elapsed += delta;
+
+                let progress = if duration.is_zero() {
+                    1.0
+                } else {
+                    state.elapsed.as_secs_f32() / duration.as_secs_f32()
+                };
+
+                state.remaining = 1.0 - easing.sample_clamped(progress);
+                progress >= 1.0
+            }
+            Self::Spring { stiffness, damping } => {
+                // Semi-implicit Euler integration, in small steps to keep stiff springs stable.
+                let steps = (delta.as_secs_f32() * 240.0).ceil().max(1.0);
+                let dt = delta.as_secs_f32() / steps;
+
+                for _ in 0..steps as u32 {
+                    let acceleration = -stiffness * state.remaining - damping * state.velocity;
+                    state.velocity += acceleration * dt;
+                    state.remaining += state.velocity * dt;
+                }
+
+                state.remaining.abs() < Self::REST_THRESHOLD
+                    && state.velocity.abs() < Self::REST_THRESHOLD
+            }
+        }
+    }
+}
+
+/// The progress of the layout animation of a node.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct LayoutAnimationState {
+    /// The center and the size of the last layout box, `None` before the first layout.
+    last: Option<(Vec2, Vec2)>,
+
+    /// The offset and the scale from the new layout box to the previous one.
+    from_offset: Vec2,
+    from_scale: Vec2,
+
+    /// The remaining fraction of the animation, and its velocity for the springs.
+    remaining: f32,
+    velocity: f32,
+    elapsed: Duration,
+    running: bool,
+
+    /// The transform before the offset and the scale have been applied, `None` when nothing has
+    /// been applied.
+    applied: Option<Transform>,
+}
+
+impl Default for LayoutAnimationState {
+    fn default() -> Self {
+        Self {
+            last: None,
+            from_offset: Vec2::ZERO,
+            from_scale: Vec2::ONE,
+            remaining: 0.0,
+            velocity: 0.0,
+            elapsed: Duration::ZERO,
+            running: false,
+            applied: None,
+        }
+    }
+}
+
+impl LayoutAnimationState {
+    /// The smallest displayed scale, which keeps the transform invertible.
+    const MIN_SCALE: f32 = 1.0e-3;
+
+    /// Returns `true` while an animation is running.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// The displayed offset from the layout box, in layout units.
+    pub fn offset(&self) -> Vec2 {
+        self.from_offset * self.remaining
+    }
+
+    /// The displayed scale of the layout box.
+    pub fn scale(&self) -> Vec2 {
+        (Vec2::ONE + (self.from_scale - Vec2::ONE) * self.remaining)
+            .max(Vec2::splat(Self::MIN_SCALE))
+    }
+}
+
+/// Triggered on a node with a [`LayoutAnimation`] when it is laid out for the first time.
+///
+/// The nodes which have not been laid out yet, such as the nodes which are not rendered by a
+/// camera, are skipped until they are.
+#[derive(EntityEvent, Clone, Copy, Debug)]
+pub struct LayoutEnter {
+    pub entity: Entity,
+}
+
+/// Triggered on a node when its exit animation starts, see [`ExitCommandsExt::despawn_after_exit`].
+#[derive(EntityEvent, Clone, Copy, Debug)]
+pub struct LayoutExit {
+    pub entity: Entity,
+}
+
+/// A node which is despawned when its exit animation has ended.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct Exiting {
+    /// The elapsed time of the exit animation.
+    pub elapsed: Duration,
+}
+
+impl Exiting {
+    /// Returns the progress of the exit animation, from `0.0` to `1.0`.
+    pub fn progress(&self, layout_animation: Option<&LayoutAnimation>) -> f32 {
+        layout_animation
+            .map(|layout_animation| layout_animation.exit_duration)
+            .filter(|duration| !duration.is_zero())
+            .map_or(1.0, |duration| {
+                (self.elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.0)
+            })
+    }
+}
+
+/// Defers the despawn of the nodes until their exit animations have ended.
+pub trait ExitCommandsExt {
+    /// Triggers a [`LayoutExit`] on the node, and despawns it with its descendants after the
+    /// [`LayoutAnimation::exit_duration`], or at once without a [`LayoutAnimation`].
+    ///
+    /// The node keeps its place in the layout until it is despawned.
+    /// Calling it again while the node is exiting has no effect.
+    fn despawn_after_exit(&mut self) -> &mut Self;
+}
+
+impl ExitCommandsExt for EntityCommands<'_> {
+    fn despawn_after_exit(&mut self) -> &mut Self {
+        self.queue(|mut entity: EntityWorldMut| {
+            if entity.contains::<Exiting>() {
+                return;
+            }
+
+            let entity_id = entity.id();
+            entity.insert(Exiting::default());
+            entity.world_scope(|world| world.trigger(LayoutExit { entity: entity_id }));
+        })
+    }
+}
+
+/// Despawns the exiting nodes whose exit animations have ended.
+pub fn update_exits(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut exiting_query: Query<(Entity, &mut Exiting, Option<&LayoutAnimation>)>,
+) {
+    for (entity, mut exiting, layout_animation) in &mut exiting_query {
+        if exiting.progress(layout_animation) >= 1.0 {
+            commands.entity(entity).despawn();
+        } else {
+            exiting.elapsed += time.delta();
+        }
+    }
+}
+
+/// Restores the transforms from before the offsets and the scales were applied, before the layout.
+pub fn restore_layout_animations(
+    mut animation_query: Query<(&mut Transform, &mut LayoutAnimationState)>,
+) {
+    for (mut transform, mut state) in &mut animation_query {
+        if let Some(applied) = state.applied.take() {
+            *transform = applied;
+        }
+    }
+}
+
+/// Starts an animation when the layout box of a node has been changed, and applies the displayed
+/// offset and scale on its transform.
+pub fn update_layout_animations(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut animation_query: Query<(
+        Entity,
+        &mut Transform,
+        &ComputedLayout,
+        &LayoutAnimation,
+        &mut LayoutAnimationState,
+    )>,
+) {
+    for (entity, mut transform, computed_layout, layout_animation, mut state) in
+        &mut animation_query
+    {
+        let state = &mut *state;
+
+        // Not laid out yet.
+        if state.last.is_none() && *computed_layout == ComputedLayout::default() {
+            continue;
+        }
+
+        let center = computed_layout.location + 0.5 * computed_layout.size;
+        let size = computed_layout.size;
+
+        let Some((last_center, last_size)) = state.last.replace((center, size)) else {
+            commands.trigger(LayoutEnter { entity });
+            continue;
+        };
+
+        if last_center != center || last_size != size {
+            // Starts from the displayed box.
+            let displayed_center = last_center + state.offset();
+            let displayed_size = last_size * state.scale();
+
+            state.from_offset = displayed_center - center;
+            state.from_scale = if layout_animation.size {
+                Vec2::select(size.cmpgt(Vec2::ZERO), displayed_size / size, Vec2::ONE)
+            } else {
+                Vec2::ONE
+            };
+            state.remaining = 1.0;
+            state.velocity = 0.0;
+            state.elapsed = Duration::ZERO;
+            state.running = true;
+        }
+
+        if !state.running {
+            continue;
+        }
+
+        if layout_animation.motion.advance(state, time.delta()) {
+            state.remaining = 0.0;
+            state.running = false;
+            continue;
+        }
+
+        // The offset is in the layout space of the parent, as the layout translation.
+        let offset = (state.offset() * FLIP_Y).extend(0.0);
+        let translation = transform.rotation * (transform.scale * offset);
+        let scale = state.scale().extend(1.0);
+
+        state.applied = Some(*transform);
+        transform.translation += translation;
+        transform.scale *= scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{schedule::Schedule, world::World};
+    use bevy_math::Vec3;
+
+    use super::*;
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                update_exits,
+                restore_layout_animations,
+                update_layout_animations,
+            )
+                .chain(),
+        );
+
+        (world, schedule)
+    }
+
+    fn advance(world: &mut World, schedule: &mut Schedule, millis: u64) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(millis));
+        schedule.run(world);
+    }
+
+    #[test]
+    fn test_layout_animation() {
+        let (mut world, mut schedule) = setup();
+
+        let node = world
+            .spawn((
+                Transform::default(),
+                ComputedLayout {
+                    location: Vec2::new(0.0, 0.0),
+                    size: Vec2::new(100.0, 20.0),
+                    ..Default::default()
+                },
+                LayoutAnimation::new(Duration::from_millis(100)).with_motion(LayoutMotion::Ease {
+                    duration: Duration::from_millis(100),
+                    easing: EaseFunction::Linear,
+                }),
+            ))
+            .id();
+
+        advance(&mut world, &mut schedule, 0);
+        assert!(
+            !world
+                .get::<LayoutAnimationState>(node)
+                .unwrap()
+                .is_running()
+        );
+
+        // Moves down and grows, the transform starts from the previous box.
+        world.get_mut::<ComputedLayout>(node).unwrap().location = Vec2::new(0.0, 40.0);
+        world.get_mut::<ComputedLayout>(node).unwrap().size = Vec2::new(100.0, 40.0);
+
+        advance(&mut world, &mut schedule, 50);
+        let state = world.get::<LayoutAnimationState>(node).unwrap();
+        assert!(state.is_running());
+        assert_eq!(state.offset(), Vec2::new(0.0, -25.0));
+        assert_eq!(state.scale(), Vec2::new(1.0, 0.75));
+
+        let transform = world.get::<Transform>(node).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.0, 25.0, 0.0));
+        assert_eq!(transform.scale, Vec3::new(1.0, 0.75, 1.0));
+
+        advance(&mut world, &mut schedule, 50);
+        assert!(
+            !world
+                .get::<LayoutAnimationState>(node)
+                .unwrap()
+                .is_running()
+        );
+        assert_eq!(world.get::<Transform>(node).unwrap(), &Transform::default());
+    }
+
+    #[test]
+    fn test_layout_spring() {
+        let (mut world, mut schedule) = setup();
+
+        let node = world
+            .spawn((
+                Transform::default(),
+                ComputedLayout {
+                    size: Vec2::new(10.0, 10.0),
+                    ..Default::default()
+                },
+                LayoutAnimation::spring(170.0, 26.0).with_size(false),
+            ))
+            .id();
+
+        advance(&mut world, &mut schedule, 0);
+        world.get_mut::<ComputedLayout>(node).unwrap().location = Vec2::new(100.0, 0.0);
+
+        advance(&mut world, &mut schedule, 16);
+        let offset = world.get::<LayoutAnimationState>(node).unwrap().offset();
+        assert!(offset.x < 0.0 && offset.x > -100.0);
+
+        for _ in 0..120 {
+            advance(&mut world, &mut schedule, 16);
+        }
+        assert!(
+            !world
+                .get::<LayoutAnimationState>(node)
+                .unwrap()
+                .is_running()
+        );
+        assert_eq!(world.get::<Transform>(node).unwrap(), &Transform::default());
+    }
+
+    #[test]
+    fn test_layout_animation_restore() {
+        let (mut world, mut schedule) = setup();
+
+        let transform = Transform::from_xyz(1.0, 2.0, 3.0).with_scale(Vec3::new(0.3, 0.7, 1.0));
+        let node = world
+            .spawn((
+                transform,
+                ComputedLayout::default(),
+                LayoutAnimation::new(Duration::from_millis(100)),
+            ))
+            .id();
+
+        // Not laid out yet.
+        advance(&mut world, &mut schedule, 0);
+        assert_eq!(world.get::<LayoutAnimationState>(node).unwrap().last, None);
+
+        world.get_mut::<ComputedLayout>(node).unwrap().size = Vec2::new(100.0, 20.0);
+        advance(&mut world, &mut schedule, 0);
+
+        // Grows from an empty box, the scale is kept invertible.
+        world.get_mut::<LayoutAnimationState>(node).unwrap().last =
+            Some((Vec2::new(50.0, 10.0), Vec2::ZERO));
+
+        for _ in 0..4 {
+            advance(&mut world, &mut schedule, 10);
+            let scale = world.get::<Transform>(node).unwrap().scale;
+            assert!(scale.cmpgt(Vec3::ZERO).all());
+        }
+
+        // The transform is restored exactly.
+        advance(&mut world, &mut schedule, 100);
+        assert!(
+            !world
+                .get::<LayoutAnimationState>(node)
+                .unwrap()
+                .is_running()
+        );
+        assert_eq!(world.get::<Transform>(node), Some(&transform));
+    }
+
+    #[test]
+    fn test_despawn_after_exit() {
+        let (mut world, mut schedule) = setup();
+
+        let node = world
+            .spawn((
+                Transform::default(),
+                ComputedLayout::default(),
+                LayoutAnimation::new(Duration::from_millis(100)),
+            ))
+            .id();
+
+        world.commands().entity(node).despawn_after_exit();
+        world.flush();
+
+        advance(&mut world, &mut schedule, 60);
+        advance(&mut world, &mut schedule, 60);
+        assert!(world.get_entity(node).is_ok());
+
+        advance(&mut world, &mut schedule, 0);
+        assert!(world.get_entity(node).is_err());
+    }
+}
@@ -1,5 +1,12 @@
 pub mod computed;
 pub mod content_size;
 pub mod div;
+#[cfg(feature = "ghost_nodes")]
+pub mod ghost;
 pub mod image;
+pub mod interaction;
+pub mod layout_animation;
+pub mod root;
+pub mod scroll;
 pub mod text;
+pub mod transition;
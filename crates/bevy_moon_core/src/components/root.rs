@@ -0,0 +1,103 @@
+use bevy_ecs::{component::Component, prelude::ReflectComponent};
+use bevy_math::{Affine3A, Vec2, Vec3};
+use bevy_reflect::{Reflect, prelude::ReflectDefault};
+use bevy_sprite::Anchor;
+
+use super::div::Div;
+
+/// Declares the root of a layout tree, which is laid out independently of the cameras.
+///
+/// Without it, a root [`Div`] is laid out within the viewport of the camera which renders it.
+/// It only takes effect on entities without a parent.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[require(Div)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct UiRoot {
+    /// The available size of the layout, in world units.
+    ///
+    /// An infinite axis is sized to fit its content.
+    pub available_size: Vec2,
+
+    /// How the layout box sits on the [`Transform`](bevy_transform::components::Transform) of the root.
+    pub anchor: Anchor,
+
+    /// The world units per layout unit (pixel).
+    pub units_per_pixel: f32,
+}
+
+impl Default for UiRoot {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl UiRoot {
+    pub const DEFAULT: Self = Self {
+        available_size: Vec2::INFINITY,
+        anchor: Anchor::CENTER,
+        units_per_pixel: 1.0,
+    };
+
+    /// Creates a root with the available size in world units.
+    pub const fn new(available_size: Vec2) -> Self {
+        Self {
+            available_size,
+            ..Self::DEFAULT
+        }
+    }
+
+    pub const fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    pub const fn with_units_per_pixel(mut self, units_per_pixel: f32) -> Self {
+        self.units_per_pixel = units_per_pixel;
+        self
+    }
+
+    /// Returns the available size of the layout, in layout units.
+    #[inline]
+    pub fn available_layout_size(&self) -> Vec2 {
+        self.available_size / self.units_per_pixel
+    }
+
+    /// Returns the affine which places the layout box of the given size on the root's transform.
+    #[inline]
+    pub fn affine(&self, size: Vec2) -> Affine3A {
+        // y-up, moves the anchor point of the box to the origin.
+        let offset = -self.anchor.as_vec() * size;
+
+        Affine3A::from_scale(Vec3::splat(self.units_per_pixel))
+            * Affine3A::from_translation(offset.extend(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_root_affine() {
+        let root = UiRoot::new(Vec2::new(2.0, 1.0))
+            .with_anchor(Anchor::TOP_LEFT)
+            .with_units_per_pixel(0.01);
+
+        assert_eq!(root.available_layout_size(), Vec2::new(200.0, 100.0));
+
+        let affine = root.affine(Vec2::new(200.0, 100.0));
+
+        // The top-left corner of the box sits on the origin.
+        assert!(
+            affine
+                .transform_point3(Vec3::new(-100.0, 50.0, 0.0))
+                .abs_diff_eq(Vec3::ZERO, 1e-6)
+        );
+        // The bottom-right corner of the box.
+        assert!(
+            affine
+                .transform_point3(Vec3::new(100.0, -50.0, 0.0))
+                .abs_diff_eq(Vec3::new(2.0, -1.0, 0.0), 1e-6)
+        );
+    }
+}
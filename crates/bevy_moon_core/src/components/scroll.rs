@@ -0,0 +1,473 @@
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    component::Component, entity::Entity, event::EntityEvent, hierarchy::ChildOf, observer::On,
+    reflect::ReflectComponent, system::Query,
+};
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{Reflect, prelude::ReflectDefault};
+
+use super::computed::{ComputedDiv, ComputedLayout};
+
+/// The height of a line, used to convert line-based mouse wheel deltas into layout units.
+pub const SCROLL_LINE_HEIGHT: f32 = 21.0;
+
+/// The scroll offset of a node's content, in layout units.
+///
+/// It is only applied along the axes whose overflow is set to [`taffy::Overflow::Scroll`],
+/// and is clamped by the content size of the node when the layout is propagated.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Deref, DerefMut, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct ScrollPosition(pub Vec2);
+
+impl ScrollPosition {
+    pub const DEFAULT: Self = Self(Vec2::ZERO);
+}
+
+impl From<Vec2> for ScrollPosition {
+    fn from(value: Vec2) -> Self {
+        Self(value)
+    }
+}
+
+/// Scrolls all the scrollable ancestors of the entity, so that the entity becomes visible.
+///
+/// ```ignore
+/// commands.trigger(ScrollIntoView { entity });
+/// ```
+#[derive(EntityEvent, Clone, Copy, Debug)]
+pub struct ScrollIntoView {
+    pub entity: Entity,
+}
+
+pub fn scroll_into_view(
+    scroll_into_view: On<ScrollIntoView>,
+    parent_query: Query<&ChildOf>,
+    mut div_query: Query<(&ComputedDiv, &ComputedLayout, &mut ScrollPosition)>,
+) {
+    let entity = scroll_into_view.entity;
+
+    let Ok((_, computed_layout, _)) = div_query.get(entity) else {
+        return;
+    };
+
+    // The rect of the target, relative to the content box of its parent.
+    let mut rect = Rect::from_corners(
+        computed_layout.location,
+        computed_layout.location + computed_layout.size,
+    );
+
+    for ancestor in parent_query.iter_ancestors(entity) {
+        let Ok((div, computed_layout, mut scroll_position)) = div_query.get_mut(ancestor) else {
+            break;
+        };
+
+        let scrollable = div.scrollable();
+        let max_scroll_position = computed_layout.max_scroll_position();
+        let visible_size = computed_layout.size - computed_layout.scrollbar_size;
+        let mut position = computed_layout.scroll_position;
+
+        for (axis, enabled) in [scrollable.x, scrollable.y].into_iter().enumerate() {
+            if !enabled {
+                continue;
+            }
+
+            let (min, max) = (rect.min[axis], rect.max[axis]);
+
+            if min < position[axis] {
+                position[axis] = min;
+            } else if max > position[axis] + visible_size[axis] {
+                position[axis] = (max - visible_size[axis]).min(min);
+            }
+
+            position[axis] = position[axis].clamp(0.0, max_scroll_position[axis]);
+        }
+
+        if position != computed_layout.scroll_position {
+            scroll_position.0 = position;
+        }
+
+        // Moves the rect into the space of the ancestor's parent.
+        let offset = computed_layout.location - position;
+        rect = Rect::from_corners(rect.min + offset, rect.max + offset);
+    }
+}
+
+#[cfg(feature = "picking")]
+pub use self::picking::{scroll_on_drag, scroll_on_wheel};
+
+#[cfg(feature = "picking")]
+mod picking {
+    use bevy_ecs::{
+        observer::On,
+        system::{Local, Query, Res},
+    };
+    use bevy_input::mouse::MouseScrollUnit;
+    use bevy_math::{Vec2, primitives::InfinitePlane3d};
+    use bevy_picking::{
+        backend::ray::RayMap,
+        events::{Drag, Pointer, Scroll},
+        pointer::PointerId,
+    };
+    use bevy_platform::collections::HashMap;
+    use bevy_transform::components::GlobalTransform;
+
+    use crate::{
+        components::{
+            computed::{ComputedDiv, ComputedLayout},
+            div::Div,
+        },
+        geometry::FLIP_Y,
+        stack::UiStackMap,
+    };
+
+    use super::{SCROLL_LINE_HEIGHT, ScrollPosition};
+
+    /// Applies a scroll delta to the node, returns `true` if the node has been scrolled.
+    fn apply_scroll_delta(
+        div: &Div,
+        computed_layout: &ComputedLayout,
+        scroll_position: &mut ScrollPosition,
+        mut delta: Vec2,
+    ) -> bool {
+        let scrollable = div.scrollable();
+
+        // Only horizontal scrolling is allowed, so uses the vertical wheel delta instead.
+        if scrollable.x && !scrollable.y && delta.x == 0.0 {
+            delta.x = delta.y;
+        }
+
+        let delta = Vec2::select(scrollable, delta, Vec2::ZERO);
+        let max_scroll_position = computed_layout.max_scroll_position();
+        let position =
+            (computed_layout.scroll_position + delta).clamp(Vec2::ZERO, max_scroll_position);
+
+        if position == computed_layout.scroll_position {
+            return false;
+        }
+
+        scroll_position.0 = position;
+
+        true
+    }
+
+    /// Scrolls the nearest scrollable node with the mouse wheel.
+    pub fn scroll_on_wheel(
+        mut scroll: On<Pointer<Scroll>>,
+        mut div_query: Query<(&ComputedDiv, &ComputedLayout, &mut ScrollPosition)>,
+    ) {
+        let Ok((div, computed_layout, mut scroll_position)) = div_query.get_mut(scroll.entity)
+        else {
+            return;
+        };
+
+        let Scroll { unit, x, y, .. } = scroll.event;
+
+        let delta = match unit {
+            MouseScrollUnit::Line => Vec2::new(x, y) * SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => Vec2::new(x, y),
+        };
+
+        // Scrolling up moves the content down.
+        if apply_scroll_delta(div, computed_layout, &mut scroll_position, -delta) {
+            scroll.propagate(false);
+        }
+    }
+
+    /// Scrolls the nearest scrollable node with touch drags.
+    ///
+    /// The pointer is projected onto the plane of the node, so that the content follows the
+    /// finger whatever the transform of the node is.
+    pub fn scroll_on_drag(
+        mut drag: On<Pointer<Drag>>,
+        ray_map: Res<RayMap>,
+        ui_stack_map: Res<UiStackMap>,
+        mut div_query: Query<(
+            &ComputedDiv,
+            &GlobalTransform,
+            &ComputedLayout,
+            &mut ScrollPosition,
+        )>,
+        mut last_points: Local<HashMap<PointerId, Vec2>>,
+    ) {
+        let pointer_id = drag.pointer_id;
+
+        if !pointer_id.is_touch() {
+            return;
+        }
+
+        let entity = drag.entity;
+
+        let Ok((div, transform, computed_layout, mut scroll_position)) = div_query.get_mut(entity)
+        else {
+            return;
+        };
+
+        if !div.scrollable().any() {
+            return;
+        }
+
+        let Some(point) = ray_map
+            .iter()
+            .filter(|(ray_id, _)| ray_id.pointer == pointer_id)
+            .filter(|(ray_id, _)| {
+                ui_stack_map
+                    .get(&ray_id.camera)
+                    .is_some_and(|ui_stack| ui_stack.bitset.contains(entity.index_u32() as usize))
+            })
+            .find_map(|(_, ray)| {
+                let plane = InfinitePlane3d::new(transform.back());
+                let distance = ray.intersect_plane(transform.translation(), plane)?;
+                let local = transform
+                    .affine()
+                    .inverse()
+                    .transform_point3(ray.get_point(distance));
+                Some(local.truncate() * FLIP_Y)
+            })
+        else {
+            return;
+        };
+
+        // The first drag event of a gesture.
+        if drag.event.distance == drag.event.delta {
+            last_points.insert(pointer_id, point);
+            drag.propagate(false);
+            return;
+        }
+
+        let Some(last_point) = last_points.insert(pointer_id, point) else {
+            return;
+        };
+
+        // Dragging down moves the content down.
+        apply_scroll_delta(
+            div,
+            computed_layout,
+            &mut scroll_position,
+            last_point - point,
+        );
+
+        drag.propagate(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        children,
+        hierarchy::Children,
+        schedule::{IntoScheduleConfigs, Schedule},
+        world::World,
+    };
+    use bevy_transform::components::Transform;
+
+    use super::*;
+    use crate::{
+        components::computed::update_computed_divs,
+        prelude::div,
+        systems::{
+            mark_dirty_roots_system,
+            tests::{render_with, setup_world, spawn_camera},
+            ui_layout_system,
+        },
+    };
+
+    /// Lays out a container of 100x100, which scrolls vertically through three items of 100x100.
+    fn setup() -> (World, Schedule, Entity, [Entity; 3]) {
+        let mut world = setup_world();
+
+        let camera = spawn_camera(&mut world, 1.0, Vec2::new(800.0, 600.0));
+        let item = || div().w(100.0).h(100.0).flex_shrink_0();
+        let container = world
+            .spawn((
+                div().w(100.0).h(100.0).flex_col().overflow_y_scroll(),
+                children![item(), item(), item()],
+            ))
+            .id();
+        let children = world.get::<Children>(container).unwrap();
+        let items = [children[0], children[1], children[2]];
+
+        render_with(&mut world, &[container], &[camera]);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                update_computed_divs,
+                mark_dirty_roots_system,
+                ui_layout_system,
+            )
+                .chain(),
+        );
+        schedule.run(&mut world);
+
+        (world, schedule, container, items)
+    }
+
+    fn scroll_position(world: &World, container: Entity) -> Vec2 {
+        world
+            .get::<ComputedLayout>(container)
+            .unwrap()
+            .scroll_position
+    }
+
+    /// The offset of the center of the item from the center of the container, y up.
+    fn item_offset(world: &World, item: Entity) -> f32 {
+        world.get::<Transform>(item).unwrap().translation.y
+    }
+
+    #[test]
+    fn test_scroll_position_clamped() {
+        let (mut world, mut schedule, container, items) = setup();
+
+        let computed_layout = world.get::<ComputedLayout>(container).unwrap();
+        assert_eq!(computed_layout.max_scroll_position(), Vec2::new(0.0, 200.0));
+
+        // Scrolls past the range, along an axis which does not scroll too.
+        world.get_mut::<ScrollPosition>(container).unwrap().0 = Vec2::new(50.0, 1000.0);
+        schedule.run(&mut world);
+
+        assert_eq!(scroll_position(&world, container), Vec2::new(0.0, 200.0));
+
+        // The children are moved by the scroll position.
+        assert_eq!(item_offset(&world, items[0]), 200.0);
+        assert_eq!(item_offset(&world, items[2]), 0.0);
+
+        world.get_mut::<ScrollPosition>(container).unwrap().0 = Vec2::new(0.0, -10.0);
+        schedule.run(&mut world);
+
+        assert_eq!(scroll_position(&world, container), Vec2::ZERO);
+        assert_eq!(item_offset(&world, items[0]), 0.0);
+        assert_eq!(item_offset(&world, items[2]), -200.0);
+    }
+
+    #[test]
+    fn test_scroll_into_view() {
+        let (mut world, mut schedule, container, items) = setup();
+        world.add_observer(scroll_into_view);
+
+        // Scrolls down until the bottom of the last item is in view.
+        world.trigger(ScrollIntoView { entity: items[2] });
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<ScrollPosition>(container).unwrap(),
+            ScrollPosition(Vec2::new(0.0, 200.0))
+        );
+        assert_eq!(scroll_position(&world, container), Vec2::new(0.0, 200.0));
+        assert_eq!(item_offset(&world, items[2]), 0.0);
+
+        // An item which is already in view does not scroll.
+        world.trigger(ScrollIntoView { entity: items[2] });
+        schedule.run(&mut world);
+        assert_eq!(scroll_position(&world, container), Vec2::new(0.0, 200.0));
+
+        // Scrolls up until the top of the first item is in view.
+        world.trigger(ScrollIntoView { entity: items[0] });
+        schedule.run(&mut world);
+
+        assert_eq!(scroll_position(&world, container), Vec2::ZERO);
+        assert_eq!(item_offset(&world, items[0]), 0.0);
+    }
+
+    #[cfg(feature = "picking")]
+    fn location() -> bevy_picking::pointer::Location {
+        bevy_picking::pointer::Location {
+            target: bevy_camera::NormalizedRenderTarget::None {
+                width: 800,
+                height: 600,
+            },
+            position: Vec2::ZERO,
+        }
+    }
+
+    #[cfg(feature = "picking")]
+    #[test]
+    fn test_scroll_on_wheel() {
+        use bevy_input::mouse::MouseScrollUnit;
+        use bevy_picking::{
+            backend::HitData,
+            events::{Pointer, Scroll},
+            pointer::PointerId,
+        };
+
+        let (mut world, mut schedule, container, _) = setup();
+        world.add_observer(scroll_on_wheel);
+
+        let mut scroll = |world: &mut World, lines: f32| {
+            world.trigger(Pointer::new(
+                PointerId::Mouse,
+                location(),
+                Scroll {
+                    unit: MouseScrollUnit::Line,
+                    x: 0.0,
+                    y: lines,
+                    hit: HitData::new(Entity::PLACEHOLDER, 0.0, None, None),
+                },
+                container,
+            ));
+            schedule.run(world);
+            scroll_position(world, container)
+        };
+
+        // Scrolling down moves the content up.
+        assert_eq!(scroll(&mut world, -1.0), Vec2::new(0.0, SCROLL_LINE_HEIGHT));
+
+        // Clamped at the end of the content.
+        assert_eq!(scroll(&mut world, -100.0), Vec2::new(0.0, 200.0));
+        assert_eq!(scroll(&mut world, 100.0), Vec2::ZERO);
+    }
+
+    #[cfg(feature = "picking")]
+    #[test]
+    fn test_scroll_on_drag() {
+        use bevy_math::{Dir3, Ray3d, Vec3};
+        use bevy_picking::{
+            backend::ray::{RayId, RayMap},
+            events::{Drag, Pointer},
+            pointer::{PointerButton, PointerId},
+        };
+
+        use crate::stack::UiStackMap;
+
+        let (mut world, mut schedule, container, _) = setup();
+        world.add_observer(scroll_on_drag);
+        world.init_resource::<RayMap>();
+
+        let camera = *world.resource::<UiStackMap>().keys().next().unwrap();
+        let pointer_id = PointerId::Touch(0);
+
+        // Drags the finger to the height in world units, y up.
+        let mut drag = |world: &mut World, height: f32, distance: Vec2| {
+            world.resource_mut::<RayMap>().map.insert(
+                RayId::new(camera, pointer_id),
+                Ray3d::new(Vec3::new(0.0, height, 10.0), Dir3::NEG_Z),
+            );
+            world.trigger(Pointer::new(
+                pointer_id,
+                location(),
+                Drag {
+                    button: PointerButton::Primary,
+                    distance,
+                    delta: Vec2::new(0.0, -1.0),
+                },
+                container,
+            ));
+            schedule.run(world);
+            scroll_position(world, container)
+        };
+
+        // The first drag event of a gesture does not scroll.
+        assert_eq!(drag(&mut world, 0.0, Vec2::new(0.0, -1.0)), Vec2::ZERO);
+
+        // Dragging up moves the content up.
+        assert_eq!(
+            drag(&mut world, 50.0, Vec2::new(0.0, -50.0)),
+            Vec2::new(0.0, 50.0)
+        );
+
+        // Clamped at the end of the content.
+        assert_eq!(
+            drag(&mut world, 500.0, Vec2::new(0.0, -500.0)),
+            Vec2::new(0.0, 200.0)
+        );
+    }
+}
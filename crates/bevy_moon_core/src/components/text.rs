@@ -8,11 +8,13 @@ use bevy_ecs::{
     query::With,
     reflect::ReflectComponent,
     schedule::SystemSet,
-    system::{Query, Res, ResMut},
+    system::{Commands, Query, Res, ResMut},
     world::Ref,
 };
 use bevy_math::Vec2;
 use bevy_reflect::{Reflect, prelude::ReflectDefault};
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 use bevy_text::{
     ComputedTextBlock, Font, FontAtlasSet, FontCx, FontHinting, LayoutCx, LetterSpacing, LineBreak,
     LineHeight, RemSize, ScaleCx, TextBounds, TextColor, TextError, TextFont, TextLayout,
@@ -20,7 +22,11 @@ use bevy_text::{
 };
 
 use crate::{
-    components::{computed::ComputedTargetInfo, content_size::ContentSize},
+    components::{
+        computed::{ComputedDiv, ComputedTargetInfo},
+        content_size::ContentSize,
+    },
+    error::{MoonError, report_error},
     measure::{FixedMeasure, Measure, MeasureArgs},
     prelude::ComputedLayout,
     stack::UiStackMap,
@@ -30,6 +36,11 @@ use super::div::Div;
 
 #[derive(Component, Debug, Default, Clone, Deref, DerefMut, Reflect, PartialEq)]
 #[reflect(Component, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 #[require(
     Div,
     ContentSize,
@@ -189,6 +200,7 @@ pub(crate) struct AmbiguousWithText;
 pub(crate) struct AmbiguousWithUpdateText2dLayout;
 
 pub fn measure_text_system(
+    mut commands: Commands,
     camera_query: Query<Ref<ComputedTargetInfo>, With<Camera>>,
     ui_stack_map: Res<UiStackMap>,
     fonts: Res<Assets<Font>>,
@@ -221,16 +233,9 @@ pub fn measure_text_system(
     ) in &mut text_query
     {
         let Some(target_info) = ui_stack_map
-            .iter()
-            .find_map(|stack| {
-                stack
-                    .1
-                    .bitset
-                    .contains(entity.index_u32() as usize)
-                    .then_some(*stack.0)
-            })
-            .iter()
-            .find_map(|&camera_entity| camera_query.get(camera_entity).ok())
+            .cameras_of(entity)
+            .filter_map(|camera_entity| camera_query.get(camera_entity).ok())
+            .max_by(|a, b| a.preference(b))
         else {
             continue;
         };
@@ -285,19 +290,25 @@ pub fn measure_text_system(
                 text_flags.needs_measure_fn = true;
             }
             Err(
-                e @ (TextError::FailedToAddGlyph(_)
+                error @ (TextError::FailedToAddGlyph(_)
                 | TextError::FailedToGetGlyphImage(_)
                 | TextError::MissingAtlasLayout
                 | TextError::MissingAtlasTexture
                 | TextError::InconsistentAtlasState),
             ) => {
-                panic!("Fatal error when processing text: {e}.");
+                // Skips the text node until it is changed again.
+                content_size.set(FixedMeasure { size: Vec2::ZERO });
+                text_flags.needs_measure_fn = false;
+                text_flags.needs_recompute = false;
+
+                report_error(&mut commands, MoonError::Text { entity, error });
             }
         };
     }
 }
 
 pub fn text_system(
+    mut commands: Commands,
     camera_query: Query<Ref<ComputedTargetInfo>, With<Camera>>,
     ui_stack_map: Res<UiStackMap>,
     mut textures: ResMut<Assets<bevy_image::Image>>,
@@ -305,7 +316,7 @@ pub fn text_system(
     mut text_pipeline: ResMut<TextPipeline>,
     mut text_query: Query<(
         Entity,
-        Ref<Div>,
+        Ref<ComputedDiv>,
         Ref<ComputedLayout>,
         Ref<FontHinting>,
         Ref<TextLayout>,
@@ -327,16 +338,9 @@ pub fn text_system(
     ) in &mut text_query
     {
         let Some(target_info) = ui_stack_map
-            .iter()
-            .find_map(|stack| {
-                stack
-                    .1
-                    .bitset
-                    .contains(entity.index_u32() as usize)
-                    .then_some(*stack.0)
-            })
-            .iter()
-            .find_map(|&camera_entity| camera_query.get(camera_entity).ok())
+            .cameras_of(entity)
+            .filter_map(|camera_entity| camera_query.get(camera_entity).ok())
+            .max_by(|a, b| a.preference(b))
         else {
             continue;
         };
@@ -390,12 +394,16 @@ pub fn text_system(
                 text_layout_info.clear();
             }
             Err(
-                e @ (TextError::FailedToAddGlyph(_)
+                error @ (TextError::FailedToAddGlyph(_)
                 | TextError::MissingAtlasLayout
                 | TextError::MissingAtlasTexture
                 | TextError::InconsistentAtlasState),
             ) => {
-                panic!("Fatal error when processing text: {e}.");
+                // Skips the text node until it is changed again.
+                text_flags.needs_recompute = false;
+                text_layout_info.clear();
+
+                report_error(&mut commands, MoonError::Text { entity, error });
             }
             Ok(()) => {
                 text_layout_info.scale_factor = scale_factor;
@@ -0,0 +1,574 @@
+//! The transitions of the visual and size fields of the nodes.
+//!
+//! ```ignore
+//! commands.spawn((
+//!     div()
+//!         .background(WHITE)
+//!         .hover(|d| d.background(GRAY_100).shadow_lg()),
+//!     Transition::new(Duration::from_millis(200)).with_easing(EaseFunction::QuadraticOut),
+//! ));
+//! ```
+//!
+//! Every time a transitioned field of the [`ComputedDiv`] is resolved to a new value, by the
+//! [`Div`] or by a style variant, the field is interpolated from its displayed value to the new one.
+//! The new values are kept in [`TransitionState`], and the interpolated ones are written into the
+//! [`ComputedDiv`], so the [`Div`] is left as the user has set it.
+
+use core::time::Duration;
+
+use bevy_color::{Alpha, Color};
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    component::Component,
+    prelude::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_math::{
+    FloatExt,
+    curve::{Curve, EaseFunction},
+};
+use bevy_reflect::{Reflect, prelude::ReflectDefault};
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use bevy_time::Time;
+use smallvec::SmallVec;
+use taffy::{CompactLength, Dimension, Size};
+
+use super::{computed::ComputedDiv, div::Div};
+use crate::{
+    style::{BoxShadow, ColorSpace, Corners},
+    units::LengthProperty,
+};
+
+/// Animates the changes of the fields of a node.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[require(TransitionState)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct Transition {
+    /// The fields which are animated.
+    pub properties: TransitionProperties,
+
+    /// The duration of a transition.
+    pub duration: Duration,
+
+    /// The easing curve of a transition.
+    pub easing: EaseFunction,
+
+    /// The color space in which the colors are interpolated.
+    pub color_space: ColorSpace,
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Transition {
+    pub const DEFAULT: Self = Self {
+        properties: TransitionProperties::ALL,
+        duration: Duration::from_millis(150),
+        easing: EaseFunction::CubicInOut,
+        color_space: ColorSpace::Oklaba,
+    };
+
+    /// Animates all the fields for the duration.
+    pub const fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            ..Self::DEFAULT
+        }
+    }
+
+    pub const fn with_properties(mut self, properties: TransitionProperties) -> Self {
+        self.properties = properties;
+        self
+    }
+
+    pub const fn with_easing(mut self, easing: EaseFunction) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub const fn with_color_space(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+}
+
+/// The fields of a node which are animated by a [`Transition`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Clone, Debug, PartialEq, Hash)]
+pub struct TransitionProperties {
+    pub background: bool,
+    pub border_color: bool,
+    pub corner_radii: bool,
+
+    /// The shadows are interpolated when their lists have the same length and inset flags,
+    /// or from transparent shadows when there are no shadows.
+    pub box_shadow: bool,
+
+    /// The width and height are interpolated when both values are lengths or percentages,
+    /// the relative lengths are not interpolated.
+    pub size: bool,
+}
+
+impl TransitionProperties {
+    pub const ALL: Self = Self {
+        background: true,
+        border_color: true,
+        corner_radii: true,
+        box_shadow: true,
+        size: true,
+    };
+
+    pub const NONE: Self = Self {
+        background: false,
+        border_color: false,
+        corner_radii: false,
+        box_shadow: false,
+        size: false,
+    };
+
+    pub const COLORS: Self = Self {
+        background: true,
+        border_color: true,
+        ..Self::NONE
+    };
+}
+
+/// The values of the animated fields.
+#[derive(Clone, Debug, PartialEq)]
+struct TransitionValues {
+    background: Option<Color>,
+    border_color: Option<Color>,
+    corner_radii: Corners<f32>,
+    box_shadow: Option<SmallVec<[BoxShadow; 2]>>,
+    size: Size<Dimension>,
+
+    /// Whether the width and height are relative lengths, which are only resolved in the layout.
+    relative_size: Size<bool>,
+}
+
+impl Default for TransitionValues {
+    fn default() -> Self {
+        Self {
+            background: None,
+            border_color: None,
+            corner_radii: Corners::DEFAULT,
+            box_shadow: None,
+            size: Size {
+                width: Dimension::auto(),
+                height: Dimension::auto(),
+            },
+            relative_size: Size {
+                width: false,
+                height: false,
+            },
+        }
+    }
+}
+
+impl TransitionValues {
+    fn read(div: &Div) -> Self {
+        Self {
+            background: div.background,
+            border_color: div.border_color,
+            corner_radii: div.corner_radii,
+            box_shadow: div.box_shadow.clone(),
+            size: div.style.size,
+            relative_size: Size {
+                width: div.is_relative(LengthProperty::Width),
+                height: div.is_relative(LengthProperty::Height),
+            },
+        }
+    }
+
+    /// Writes the values on the node.
+    fn write(&self, div: &mut Div) {
+        div.background = self.background;
+        div.border_color = self.border_color;
+        div.corner_radii = self.corner_radii;
+        div.box_shadow.clone_from(&self.box_shadow);
+        div.style.size = self.size;
+    }
+
+    /// Takes the fields which are not animated from the other values.
+    fn snap(&mut self, other: &Self, properties: &TransitionProperties) {
+        if !properties.background {
+            self.background = other.background;
+        }
+        if !properties.border_color {
+            self.border_color = other.border_color;
+        }
+        if !properties.corner_radii {
+            self.corner_radii = other.corner_radii;
+        }
+        if !properties.box_shadow {
+            self.box_shadow.clone_from(&other.box_shadow);
+        }
+        if !properties.size {
+            self.size = other.size;
+        }
+
+        // The relative lengths are only resolved in the layout.
+        if self.relative_size.width || other.relative_size.width {
+            self.size.width = other.size.width;
+        }
+        if self.relative_size.height || other.relative_size.height {
+            self.size.height = other.size.height;
+        }
+        self.relative_size = other.relative_size;
+    }
+
+    fn interpolate(from: &Self, to: &Self, t: f32, color_space: ColorSpace) -> Self {
+        Self {
+            background: mix_colors(from.background, to.background, t, color_space),
+            border_color: mix_colors(from.border_color, to.border_color, t, color_space),
+            corner_radii: Corners {
+                top_left: from.corner_radii.top_left.lerp(to.corner_radii.top_left, t),
+                top_right: from
+                    .corner_radii
+                    .top_right
+                    .lerp(to.corner_radii.top_right, t),
+                bottom_right: from
+                    .corner_radii
+                    .bottom_right
+                    .lerp(to.corner_radii.bottom_right, t),
+                bottom_left: from
+                    .corner_radii
+                    .bottom_left
+                    .lerp(to.corner_radii.bottom_left, t),
+            },
+            box_shadow: mix_shadows(&from.box_shadow, &to.box_shadow, t, color_space),
+            size: Size {
+                width: mix_dimensions(from.size.width, to.size.width, t),
+                height: mix_dimensions(from.size.height, to.size.height, t),
+            },
+            relative_size: to.relative_size,
+        }
+    }
+}
+
+/// A missing color is a transparent version of the other one.
+fn mix_colors(
+    from: Option<Color>,
+    to: Option<Color>,
+    t: f32,
+    color_space: ColorSpace,
+) -> Option<Color> {
+    match (from, to) {
+        (Some(from), Some(to)) => Some(color_space.mix(from, to, t)),
+        (Some(from), None) => Some(color_space.mix(from, from.with_alpha(0.0), t)),
+        (None, Some(to)) => Some(color_space.mix(to.with_alpha(0.0), to, t)),
+        (None, None) => None,
+    }
+}
+
+/// The shadows of lists of the same length are mixed pairwise, missing shadows are transparent
+/// versions of the other ones, other lists and the lists whose inset flags differ snap at the end.
+fn mix_shadows(
+    from: &Option<SmallVec<[BoxShadow; 2]>>,
+    to: &Option<SmallVec<[BoxShadow; 2]>>,
+    t: f32,
+    color_space: ColorSpace,
+) -> Option<SmallVec<[BoxShadow; 2]>> {
+    let transparent = |shadows: &SmallVec<[BoxShadow; 2]>| {
+        shadows
+            .iter()
+            .map(|shadow| BoxShadow {
+                color: shadow.color.with_alpha(0.0),
+                ..*shadow
+            })
+            .collect::<SmallVec<[BoxShadow; 2]>>()
+    };
+
+    let (from_shadows, to_shadows) = match (from, to) {
+        (Some(from), Some(to)) if from.len() == to.len() => (from.clone(), to.clone()),
+        (Some(from), None) => (from.clone(), transparent(from)),
+        (None, Some(to)) => (transparent(to), to.clone()),
+        _ => return if t < 1.0 { from.clone() } else { to.clone() },
+    };
+
+    // an outer shadow does not turn into an inset one
+    if from_shadows
+        .iter()
+        .zip(&to_shadows)
+        .any(|(from, to)| from.inset != to.inset)
+    {
+        return if t < 1.0 { from.clone() } else { to.clone() };
+    }
+
+    Some(
+        from_shadows
+            .iter()
+            .zip(&to_shadows)
+            .map(|(from, to)| BoxShadow {
+                color: color_space.mix(from.color, to.color, t),
+                offset: from.offset.lerp(to.offset, t),
+                blur_radius: from.blur_radius.lerp(to.blur_radius, t),
+                spread_radius: from.spread_radius.lerp(to.spread_radius, t),
+                inset: from.inset,
+            })
+            .collect(),
+    )
+}
+
+/// Lengths and percentages are mixed with their own kind, other dimensions snap at the end.
+fn mix_dimensions(from: Dimension, to: Dimension, t: f32) -> Dimension {
+    let (from, to) = (from.into_raw(), to.into_raw());
+
+    match (from.tag(), to.tag()) {
+        (CompactLength::LENGTH_TAG, CompactLength::LENGTH_TAG) => {
+            Dimension::length(from.value().lerp(to.value(), t))
+        }
+        (CompactLength::PERCENT_TAG, CompactLength::PERCENT_TAG) => {
+            Dimension::percent(from.value().lerp(to.value(), t))
+        }
+        _ => Dimension::from_raw(if t < 1.0 { from } else { to }),
+    }
+}
+
+/// The progress of the transition of a node.
+#[derive(Component, Clone, Debug, Default)]
+pub struct TransitionState {
+    /// The resolved values of the node, `None` before the node is first resolved.
+    target: Option<TransitionValues>,
+
+    /// The values at the start of the transition.
+    start: TransitionValues,
+
+    /// The displayed values.
+    current: TransitionValues,
+
+    /// The elapsed time of the transition, `None` when it has finished.
+    elapsed: Option<Duration>,
+}
+
+impl TransitionState {
+    /// Returns `true` while a transition is running.
+    pub fn is_running(&self) -> bool {
+        self.elapsed.is_some()
+    }
+
+    /// Starts a transition when the resolved values of the node differ from the target, and
+    /// writes the displayed values on the resolved node in their place.
+    pub(crate) fn retarget(&mut self, transition: &Transition, div: &mut Div) {
+        let values = TransitionValues::read(div);
+
+        let Some(target) = self.target.as_mut() else {
+            // Nothing to animate from.
+            self.current = values.clone();
+            self.target = Some(values);
+            return;
+        };
+
+        if *target != values {
+            // Starts from the displayed values.
+            self.start = self.current.clone();
+            self.start.snap(&values, &transition.properties);
+            self.current = self.start.clone();
+            *target = values;
+            self.elapsed = Some(Duration::ZERO);
+        }
+
+        self.current.write(div);
+    }
+}
+
+/// Displays the interpolated values of the nodes in transition, until the transitions have
+/// finished.
+///
+/// The [`ComputedDiv`] is only marked as changed when the interpolated size is changed.
+pub fn update_transitions(
+    time: Res<Time>,
+    mut div_query: Query<(&mut ComputedDiv, &Transition, &mut TransitionState)>,
+) {
+    for (mut computed_div, transition, mut state) in &mut div_query {
+        let state = &mut *state;
+
+        let (Some(target), Some(elapsed)) = (state.target.as_ref(), state.elapsed.as_mut()) else {
+            continue;
+        };
+
+        *elapsed += time.delta();
+
+        let progress = if transition.duration.is_zero() {
+            1.0
+        } else {
+            elapsed.as_secs_f32() / transition.duration.as_secs_f32()
+        };
+
+        if progress >= 1.0 {
+            state.current = target.clone();
+            state.elapsed = None;
+        } else {
+            let t = transition.easing.sample_clamped(progress);
+            state.current =
+                TransitionValues::interpolate(&state.start, target, t, transition.color_space);
+        }
+
+        let div = &mut computed_div.bypass_change_detection().0;
+        let size = div.style.size;
+        state.current.write(div);
+
+        if div.style.size != size {
+            computed_div.set_changed();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_color::{
+        LinearRgba,
+        palettes::basic::{BLACK, WHITE},
+    };
+    use bevy_ecs::{schedule::Schedule, world::World};
+
+    use super::*;
+    use crate::{components::computed::update_computed_divs, prelude::div, theme::MoonTheme};
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<MoonTheme>();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((update_computed_divs, update_transitions).chain());
+
+        (world, schedule)
+    }
+
+    fn advance(world: &mut World, schedule: &mut Schedule, millis: u64) -> Div {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(millis));
+        schedule.run(world);
+        world
+            .query::<&ComputedDiv>()
+            .single(world)
+            .unwrap()
+            .0
+            .clone()
+    }
+
+    #[test]
+    fn test_transition() {
+        let (mut world, mut schedule) = setup();
+
+        let node = world
+            .spawn((
+                div().w(100.0).background(BLACK),
+                Transition::new(Duration::from_millis(100))
+                    .with_easing(EaseFunction::Linear)
+                    .with_color_space(ColorSpace::LinearRgba),
+            ))
+            .id();
+
+        advance(&mut world, &mut schedule, 0);
+
+        {
+            let mut div = world.get_mut::<Div>(node).unwrap();
+            div.background = Some(WHITE.into());
+            div.style.size.width = Dimension::length(200.0);
+            div.box_shadow = Some(SmallVec::from_slice(&BoxShadow::LG));
+        }
+
+        // Starts from the displayed values.
+        let current = advance(&mut world, &mut schedule, 0);
+        assert_eq!(current.background, Some(BLACK.into()));
+        assert_eq!(current.style.size.width, Dimension::length(100.0));
+
+        let current = advance(&mut world, &mut schedule, 50);
+        assert_eq!(
+            current.background,
+            Some(LinearRgba::rgb(0.5, 0.5, 0.5).into())
+        );
+        assert_eq!(current.style.size.width, Dimension::length(150.0));
+        let shadows = current.box_shadow.unwrap();
+        assert_eq!(shadows.len(), BoxShadow::LG.len());
+        assert_eq!(
+            shadows[0].color.alpha(),
+            0.5 * BoxShadow::LG[0].color.alpha()
+        );
+
+        // Retargets from the displayed values.
+        world.get_mut::<Div>(node).unwrap().style.size.width = Dimension::length(100.0);
+
+        let current = advance(&mut world, &mut schedule, 50);
+        assert_eq!(current.style.size.width, Dimension::length(125.0));
+        assert!(world.get::<TransitionState>(node).unwrap().is_running());
+
+        let current = advance(&mut world, &mut schedule, 100);
+        assert_eq!(current.background, Some(WHITE.into()));
+        assert_eq!(current.style.size.width, Dimension::length(100.0));
+        assert!(!world.get::<TransitionState>(node).unwrap().is_running());
+    }
+
+    #[test]
+    fn test_transition_properties() {
+        let (mut world, mut schedule) = setup();
+
+        let node = world
+            .spawn((
+                div().w(100.0).background(BLACK),
+                Transition::new(Duration::from_millis(100))
+                    .with_properties(TransitionProperties::COLORS)
+                    .with_easing(EaseFunction::Linear),
+            ))
+            .id();
+
+        advance(&mut world, &mut schedule, 0);
+
+        {
+            let mut div = world.get_mut::<Div>(node).unwrap();
+            div.background = Some(WHITE.into());
+            div.style.size.width = Dimension::auto();
+        }
+
+        advance(&mut world, &mut schedule, 0);
+        let current = advance(&mut world, &mut schedule, 50);
+
+        // The size is not animated.
+        assert_eq!(current.style.size.width, Dimension::auto());
+        assert_ne!(current.background, Some(WHITE.into()));
+    }
+
+    #[test]
+    fn test_transition_relative_size() {
+        let (mut world, mut schedule) = setup();
+
+        let node = world
+            .spawn((
+                div().w(100.0).w_rem(10.0),
+                Transition::new(Duration::from_millis(100)).with_easing(EaseFunction::Linear),
+            ))
+            .id();
+
+        advance(&mut world, &mut schedule, 0);
+
+        *world.get_mut::<Div>(node).unwrap() = div().w(200.0);
+
+        // The relative width is not interpolated.
+        let current = advance(&mut world, &mut schedule, 50);
+        assert_eq!(current.style.size.width, Dimension::length(200.0));
+        assert!(!current.is_relative(LengthProperty::Width));
+
+        // The user's node is left alone.
+        assert_eq!(world.get::<Div>(node), Some(&div().w(200.0)));
+    }
+
+    #[test]
+    fn test_transition_inset_shadows() {
+        let outer = Some(SmallVec::from_slice(&BoxShadow::XS));
+        let inset = Some(SmallVec::from_slice(&BoxShadow::INSET_SM));
+
+        // The inset flags differ, the shadows snap at the end.
+        let mixed = mix_shadows(&outer, &inset, 0.5, ColorSpace::default());
+        assert_eq!(mixed, outer);
+
+        let mixed = mix_shadows(&outer, &inset, 1.0, ColorSpace::default());
+        assert_eq!(mixed, inset);
+    }
+}
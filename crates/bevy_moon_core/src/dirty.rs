@@ -0,0 +1,57 @@
+use bevy_ecs::{
+    entity::{Entity, EntityHashSet},
+    resource::Resource,
+};
+
+/// The roots whose subtrees have been changed since they were last updated.
+///
+/// It is filled by `mark_dirty_roots_system` from the changed [`Div`](crate::prelude::Div)s,
+/// content sizes, children, scroll positions and transforms, the cameras mark their roots when
+/// their targets have been changed.
+#[derive(Resource, Default, Debug)]
+pub struct UiDirtyRoots {
+    /// The roots which need to be re-stacked, cleared after stacking.
+    pub(crate) stack: EntityHashSet,
+
+    /// The roots which need to be laid out and whose geometry needs to be propagated.
+    ///
+    /// A root is kept until it is rendered by a camera and laid out.
+    pub(crate) layout: EntityHashSet,
+
+    /// The set of roots may have been changed, e.g. a node has been removed or re-parented,
+    /// every camera collects its roots again.
+    pub(crate) restack_all: bool,
+}
+
+impl UiDirtyRoots {
+    /// Marks the root to be re-stacked and laid out.
+    #[inline]
+    pub fn mark(&mut self, root: Entity) {
+        self.stack.insert(root);
+        self.layout.insert(root);
+    }
+
+    /// Marks the root to be re-stacked only, e.g. its depth has been changed.
+    #[inline]
+    pub fn mark_stack(&mut self, root: Entity) {
+        self.stack.insert(root);
+    }
+
+    /// Returns `true` if the root needs to be laid out.
+    #[inline]
+    pub fn needs_layout(&self, root: Entity) -> bool {
+        self.layout.contains(&root)
+    }
+}
+
+/// Counts the roots which have been recomputed in the last frame.
+#[derive(Resource, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct UiRecomputeCounter {
+    /// The roots whose subtrees have been walked again to build the stacks of the cameras.
+    ///
+    /// A root rendered by several cameras is counted once per camera.
+    pub restacked_roots: usize,
+
+    /// The roots which have been laid out and whose geometry has been propagated.
+    pub relayout_roots: usize,
+}
@@ -0,0 +1,87 @@
+use bevy_ecs::{entity::Entity, event::EntityEvent, system::Commands};
+use bevy_text::TextError;
+use taffy::TaffyError;
+
+/// An error which happened while laying out a node or processing its text.
+///
+/// The node is skipped until it is changed again, the other nodes are not affected.
+#[derive(thiserror::Error, Debug)]
+pub enum MoonError {
+    #[error("failed to lay out node {entity}: {error}")]
+    Layout {
+        entity: Entity,
+        #[source]
+        error: TaffyError,
+    },
+
+    #[error("failed to process the text of node {entity}: {error}")]
+    Text {
+        entity: Entity,
+        #[source]
+        error: TextError,
+    },
+}
+
+impl MoonError {
+    /// Returns the offending node.
+    pub fn entity(&self) -> Entity {
+        match self {
+            Self::Layout { entity, .. } | Self::Text { entity, .. } => *entity,
+        }
+    }
+}
+
+/// Triggered on a node which has been skipped because of a [`MoonError`].
+///
+/// ```ignore
+/// app.add_observer(|error: On<MoonErrorEvent>| warn!("{}", error.error));
+/// ```
+#[derive(EntityEvent, Debug)]
+pub struct MoonErrorEvent {
+    pub entity: Entity,
+    pub error: MoonError,
+}
+
+/// Logs the error and triggers a [`MoonErrorEvent`] on the offending node.
+pub(crate) fn report_error(commands: &mut Commands, error: MoonError) {
+    tracing::error!("{error}");
+
+    commands.trigger(MoonErrorEvent {
+        entity: error.entity(),
+        error,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{observer::On, resource::Resource, system::ResMut, world::World};
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Reported(Vec<Entity>);
+
+    #[test]
+    fn test_report_error() {
+        let mut world = World::new();
+        world.init_resource::<Reported>();
+        world.add_observer(
+            |error: On<MoonErrorEvent>, mut reported: ResMut<Reported>| {
+                reported.0.push(error.error.entity());
+            },
+        );
+
+        let node = world.spawn_empty().id();
+
+        report_error(
+            &mut world.commands(),
+            MoonError::Text {
+                entity: node,
+                error: TextError::MissingAtlasLayout,
+            },
+        );
+        world.flush();
+
+        assert_eq!(world.resource::<Reported>().0, vec![node]);
+    }
+}
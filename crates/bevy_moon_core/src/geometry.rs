@@ -1,8 +1,32 @@
-use bevy_math::Vec2;
+use bevy_math::{Vec2, Vec4};
 
 pub const FLIP_X: Vec2 = Vec2::new(-1.0, 1.0);
 pub const FLIP_Y: Vec2 = Vec2::new(1.0, -1.0);
 
+/// Returns the index of the corner nearest to the point, relative to the center of a box.
+///
+/// `[TopLeft, TopRight, BottomRight, BottomLeft]`, the same as `get_corner_index` in the shaders.
+#[inline]
+pub fn corner_index(point: Vec2) -> usize {
+    match (point.x >= 0.0, point.y >= 0.0) {
+        (false, true) => 0,
+        (true, true) => 1,
+        (true, false) => 2,
+        (false, false) => 3,
+    }
+}
+
+/// Returns the signed distance from the point to a rounded box, relative to its center.
+///
+/// <https://iquilezles.org/articles/roundedboxes/>
+#[inline]
+pub fn sd_rounded_box(point: Vec2, half_size: Vec2, corner_radii: Vec4) -> f32 {
+    let radius = corner_radii[corner_index(point)].clamp(0.0, half_size.min_element().max(0.0));
+    let q = point.abs() - half_size + radius;
+
+    q.max(Vec2::ZERO).length() + q.max_element().min(0.0) - radius
+}
+
 /// Converts a Taffy unit to a Bevy unit.
 pub trait Convert<T> {
     #[must_use]
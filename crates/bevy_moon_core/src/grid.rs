@@ -0,0 +1,735 @@
+//! The CSS grid builders of the nodes.
+//!
+//! ```ignore
+//! div()
+//!     .grid()
+//!     .grid_cols("[sidebar] 200px [content] repeat(3, minmax(0, 1fr))")
+//!     .grid_rows("auto 1fr")
+//!     .grid_areas(["header header header header", "sidebar main main main"])
+//!     .with_children([div().grid_area("header"), div().col_span(2).row_start(2)]);
+//! ```
+//!
+//! The track lists follow the syntax of `grid-template-columns`: lengths (`200px`, `200`),
+//! percentages (`50%`), flexible lengths (`1fr`), `auto`, `min-content`, `max-content`,
+//! `minmax(min, max)`, `fit-content(length)`, `repeat(count, tracks)` with `auto-fill` and
+//! `auto-fit`, and line names such as `[content]`.
+
+use taffy::{
+    GridAutoFlow, GridPlacement, GridTemplateArea, GridTemplateComponent, GridTemplateRepetition,
+    LengthPercentage, Line, MaxTrackSizingFunction, MinMax, MinTrackSizingFunction,
+    RepetitionCount, TrackSizingFunction,
+    style_helpers::{line, span},
+};
+
+use crate::components::div::Div;
+
+/// An error of a grid template, the template is not applied.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum GridTemplateError {
+    #[error("unbalanced brackets in `{0}`")]
+    Unbalanced(String),
+
+    #[error("invalid track size `{0}`")]
+    InvalidTrack(String),
+
+    #[error("invalid repetition `{0}`")]
+    InvalidRepetition(String),
+
+    #[error("the rows of the areas have different numbers of columns")]
+    UnequalAreaRows,
+
+    #[error("the area `{0}` is not a rectangle")]
+    NonRectangularArea(String),
+}
+
+/// A parsed track list, with the line names of each line.
+pub(crate) type TrackList = (Vec<GridTemplateComponent<String>>, Vec<Vec<String>>);
+
+impl Div {
+    /// Sets the explicit columns, such as `"1fr 200px repeat(3, minmax(0, 1fr))"`.
+    ///
+    /// Warns about an invalid template, see [`Div::try_grid_cols`].
+    pub fn grid_cols(mut self, template: &str) -> Self {
+        if let Err(error) = self.try_grid_cols(template) {
+            tracing::warn!("{error}");
+        }
+        self
+    }
+
+    /// Sets the explicit columns, or leaves the node unchanged and returns the error.
+    pub fn try_grid_cols(&mut self, template: &str) -> Result<(), GridTemplateError> {
+        let (tracks, names) = parse_track_list(template, true)?;
+        self.style.grid_template_columns = tracks;
+        self.style.grid_template_column_names = names;
+        Ok(())
+    }
+
+    /// Sets the explicit rows, such as `"auto 1fr auto"`.
+    ///
+    /// Warns about an invalid template, see [`Div::try_grid_rows`].
+    pub fn grid_rows(mut self, template: &str) -> Self {
+        if let Err(error) = self.try_grid_rows(template) {
+            tracing::warn!("{error}");
+        }
+        self
+    }
+
+    /// Sets the explicit rows, or leaves the node unchanged and returns the error.
+    pub fn try_grid_rows(&mut self, template: &str) -> Result<(), GridTemplateError> {
+        let (tracks, names) = parse_track_list(template, true)?;
+        self.style.grid_template_rows = tracks;
+        self.style.grid_template_row_names = names;
+        Ok(())
+    }
+
+    /// Sets `count` columns of equal width, as `repeat(count, minmax(0, 1fr))`.
+    pub fn grid_cols_n(mut self, count: u16) -> Self {
+        self.style.grid_template_columns = evenly_sized_tracks(count);
+        self.style.grid_template_column_names = Vec::new();
+        self
+    }
+
+    /// Sets `count` rows of equal height, as `repeat(count, minmax(0, 1fr))`.
+    pub fn grid_rows_n(mut self, count: u16) -> Self {
+        self.style.grid_template_rows = evenly_sized_tracks(count);
+        self.style.grid_template_row_names = Vec::new();
+        self
+    }
+
+    /// Sets the sizes of the implicit columns, such as `"minmax(100px, auto)"`.
+    pub fn grid_auto_cols(mut self, template: &str) -> Self {
+        match parse_tracks(template) {
+            Ok(tracks) => self.style.grid_auto_columns = tracks,
+            Err(error) => tracing::warn!("{error}"),
+        }
+        self
+    }
+
+    /// Sets the sizes of the implicit rows, such as `"minmax(100px, auto)"`.
+    pub fn grid_auto_rows(mut self, template: &str) -> Self {
+        match parse_tracks(template) {
+            Ok(tracks) => self.style.grid_auto_rows = tracks,
+            Err(error) => tracing::warn!("{error}"),
+        }
+        self
+    }
+
+    /// Sets the named areas, a string per row and a name per cell, `.` is an empty cell.
+    ///
+    /// Warns about invalid areas, see [`Div::try_grid_areas`].
+    pub fn grid_areas<'a>(mut self, rows: impl IntoIterator<Item = &'a str>) -> Self {
+        if let Err(error) = self.try_grid_areas(rows) {
+            tracing::warn!("{error}");
+        }
+        self
+    }
+
+    /// Sets the named areas, or leaves the node unchanged and returns the error.
+    pub fn try_grid_areas<'a>(
+        &mut self,
+        rows: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), GridTemplateError> {
+        self.style.grid_template_areas = parse_areas(rows)?;
+        Ok(())
+    }
+
+    pub fn grid_flow_row(mut self) -> Self {
+        self.style.grid_auto_flow = GridAutoFlow::Row;
+        self
+    }
+
+    pub fn grid_flow_col(mut self) -> Self {
+        self.style.grid_auto_flow = GridAutoFlow::Column;
+        self
+    }
+
+    pub fn grid_flow_dense(mut self) -> Self {
+        self.style.grid_auto_flow = GridAutoFlow::RowDense;
+        self
+    }
+
+    pub fn grid_flow_col_dense(mut self) -> Self {
+        self.style.grid_auto_flow = GridAutoFlow::ColumnDense;
+        self
+    }
+
+    /// Places the node in the named area of its grid container.
+    pub fn grid_area(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.style.grid_row = named_line(&name);
+        self.style.grid_column = named_line(&name);
+        self
+    }
+
+    pub fn col_span(mut self, span_count: u16) -> Self {
+        self.style.grid_column = Line {
+            start: span(span_count),
+            end: GridPlacement::Auto,
+        };
+        self
+    }
+
+    pub fn col_span_full(mut self) -> Self {
+        self.style.grid_column = full_line();
+        self
+    }
+
+    /// Sets the start line of the column, a negative index counts from the end.
+    pub fn col_start(mut self, index: i16) -> Self {
+        self.style.grid_column.start = line(index);
+        self
+    }
+
+    /// Sets the end line of the column, a negative index counts from the end.
+    pub fn col_end(mut self, index: i16) -> Self {
+        self.style.grid_column.end = line(index);
+        self
+    }
+
+    pub fn col_auto(mut self) -> Self {
+        self.style.grid_column = Line::default();
+        self
+    }
+
+    pub fn row_span(mut self, span_count: u16) -> Self {
+        self.style.grid_row = Line {
+            start: span(span_count),
+            end: GridPlacement::Auto,
+        };
+        self
+    }
+
+    pub fn row_span_full(mut self) -> Self {
+        self.style.grid_row = full_line();
+        self
+    }
+
+    /// Sets the start line of the row, a negative index counts from the end.
+    pub fn row_start(mut self, index: i16) -> Self {
+        self.style.grid_row.start = line(index);
+        self
+    }
+
+    /// Sets the end line of the row, a negative index counts from the end.
+    pub fn row_end(mut self, index: i16) -> Self {
+        self.style.grid_row.end = line(index);
+        self
+    }
+
+    pub fn row_auto(mut self) -> Self {
+        self.style.grid_row = Line::default();
+        self
+    }
+}
+
+/// Returns `repeat(count, minmax(0, 1fr))`.
+pub(crate) fn evenly_sized_tracks(count: u16) -> Vec<GridTemplateComponent<String>> {
+    vec![GridTemplateComponent::Repeat(GridTemplateRepetition {
+        count: RepetitionCount::Count(count),
+        tracks: vec![MinMax {
+            min: MinTrackSizingFunction::length(0.0),
+            max: MaxTrackSizingFunction::fr(1.0),
+        }],
+        line_names: Vec::new(),
+    })]
+}
+
+/// Spans from the first line to the last line.
+pub(crate) fn full_line() -> Line<GridPlacement> {
+    Line {
+        start: line(1),
+        end: line(-1),
+    }
+}
+
+/// Spans the implicit lines of a named area, `{name}-start` and `{name}-end`.
+fn named_line(name: &str) -> Line<GridPlacement> {
+    Line {
+        start: GridPlacement::NamedLine(format!("{name}-start"), 1),
+        end: GridPlacement::NamedLine(format!("{name}-end"), 1),
+    }
+}
+
+/// Parses a track list with line names, the repetitions are allowed at the top level only.
+pub(crate) fn parse_track_list(
+    template: &str,
+    allow_repeat: bool,
+) -> Result<TrackList, GridTemplateError> {
+    let mut tracks = Vec::new();
+    let mut names = Vec::new();
+    let mut pending_names = Vec::new();
+    let mut has_names = false;
+
+    for token in split_top_level(template, char::is_whitespace)? {
+        if let Some(line_names) = token.strip_prefix('[') {
+            let line_names = line_names
+                .strip_suffix(']')
+                .ok_or_else(|| GridTemplateError::Unbalanced(template.to_string()))?;
+            pending_names.extend(line_names.split_whitespace().map(String::from));
+            has_names = true;
+            continue;
+        }
+
+        names.push(core::mem::take(&mut pending_names));
+
+        let component = match token.strip_prefix("repeat(") {
+            Some(_) if !allow_repeat => {
+                return Err(GridTemplateError::InvalidRepetition(token.to_string()));
+            }
+            Some(_) => GridTemplateComponent::Repeat(parse_repetition(token)?),
+            None => GridTemplateComponent::Single(parse_track(token)?),
+        };
+        tracks.push(component);
+    }
+
+    names.push(pending_names);
+
+    // Leaves the names empty without any line names, as taffy expects.
+    if !has_names {
+        names.clear();
+    }
+
+    Ok((tracks, names))
+}
+
+/// Parses a list of track sizes without line names or repetitions.
+fn parse_tracks(template: &str) -> Result<Vec<TrackSizingFunction>, GridTemplateError> {
+    split_top_level(template, char::is_whitespace)?
+        .into_iter()
+        .map(parse_track)
+        .collect()
+}
+
+/// Parses `repeat(3, 1fr)`, `repeat(auto-fill, minmax(100px, 1fr))`.
+fn parse_repetition(token: &str) -> Result<GridTemplateRepetition<String>, GridTemplateError> {
+    let invalid = || GridTemplateError::InvalidRepetition(token.to_string());
+
+    let arguments = function_arguments(token, "repeat")
+        .ok_or_else(invalid)?
+        .map_err(|_| invalid())?;
+
+    let [count, tracks] = arguments.as_slice() else {
+        return Err(invalid());
+    };
+
+    let count = match *count {
+        "auto-fill" => RepetitionCount::AutoFill,
+        "auto-fit" => RepetitionCount::AutoFit,
+        count => RepetitionCount::Count(
+            count
+                .parse()
+                .ok()
+                .filter(|count| *count > 0)
+                .ok_or_else(invalid)?,
+        ),
+    };
+
+    let (tracks, line_names) = parse_track_list(tracks, false)?;
+    let tracks = tracks
+        .into_iter()
+        .map(|component| match component {
+            GridTemplateComponent::Single(track) => Ok(track),
+            GridTemplateComponent::Repeat(_) => Err(invalid()),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if tracks.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(GridTemplateRepetition {
+        count,
+        tracks,
+        line_names,
+    })
+}
+
+/// Parses a track size, such as `1fr`, `200px`, `minmax(0, 1fr)` and `fit-content(50%)`.
+fn parse_track(token: &str) -> Result<TrackSizingFunction, GridTemplateError> {
+    let invalid = || GridTemplateError::InvalidTrack(token.to_string());
+
+    if let Some(arguments) = function_arguments(token, "minmax") {
+        let [min, max] = arguments.map_err(|_| invalid())?[..] else {
+            return Err(invalid());
+        };
+
+        return Ok(MinMax {
+            min: Breadth::parse(min)
+                .and_then(Breadth::min)
+                .ok_or_else(invalid)?,
+            max: Breadth::parse(max).map(Breadth::max).ok_or_else(invalid)?,
+        });
+    }
+
+    if let Some(arguments) = function_arguments(token, "fit-content") {
+        let [limit] = arguments.map_err(|_| invalid())?[..] else {
+            return Err(invalid());
+        };
+
+        let limit = match Breadth::parse(limit) {
+            Some(Breadth::Length(value)) => LengthPercentage::length(value),
+            Some(Breadth::Percent(value)) => LengthPercentage::percent(value),
+            _ => return Err(invalid()),
+        };
+
+        return Ok(MinMax {
+            min: MinTrackSizingFunction::auto(),
+            max: MaxTrackSizingFunction::fit_content(limit),
+        });
+    }
+
+    let breadth = Breadth::parse(token).ok_or_else(invalid)?;
+
+    Ok(MinMax {
+        // A flexible track has an automatic minimum.
+        min: breadth.min().unwrap_or(MinTrackSizingFunction::auto()),
+        max: breadth.max(),
+    })
+}
+
+/// Returns the comma-separated arguments of `name(...)`, or `None` for another token.
+fn function_arguments<'a>(
+    token: &'a str,
+    name: &str,
+) -> Option<Result<Vec<&'a str>, GridTemplateError>> {
+    let arguments = token.strip_prefix(name)?.strip_prefix('(')?;
+    let Some(arguments) = arguments.strip_suffix(')') else {
+        return Some(Err(GridTemplateError::Unbalanced(token.to_string())));
+    };
+
+    Some(split_top_level(arguments, |c| c == ','))
+}
+
+/// Splits the template by the separator, outside of the parentheses and brackets.
+fn split_top_level(
+    template: &str,
+    is_separator: impl Fn(char) -> bool,
+) -> Result<Vec<&str>, GridTemplateError> {
+    let unbalanced = || GridTemplateError::Unbalanced(template.to_string());
+
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (index, c) in template.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.checked_sub(1).ok_or_else(unbalanced)?,
+            c if depth == 0 && is_separator(c) => {
+                tokens.push(template[start..index].trim());
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 {
+        return Err(unbalanced());
+    }
+
+    tokens.push(template[start..].trim());
+    tokens.retain(|token| !token.is_empty());
+
+    Ok(tokens)
+}
+
+/// Parses the named areas, such as `["header header", "sidebar main"]`.
+fn parse_areas<'a>(
+    rows: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<GridTemplateArea<String>>, GridTemplateError> {
+    let rows = rows
+        .into_iter()
+        .map(|row| row.split_whitespace().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let columns = rows.first().map_or(0, Vec::len);
+    if rows.iter().any(|row| row.len() != columns) {
+        return Err(GridTemplateError::UnequalAreaRows);
+    }
+
+    let mut areas: Vec<GridTemplateArea<String>> = Vec::new();
+
+    for (row_index, row) in rows.iter().enumerate() {
+        for (column_index, &name) in row.iter().enumerate() {
+            // A sequence of dots is an empty cell.
+            if name.chars().all(|c| c == '.') {
+                continue;
+            }
+
+            let (row_line, column_line) = (row_index as u16 + 1, column_index as u16 + 1);
+
+            match areas.iter_mut().find(|area| area.name == name) {
+                Some(area) => {
+                    area.row_start = area.row_start.min(row_line);
+                    area.row_end = area.row_end.max(row_line + 1);
+                    area.column_start = area.column_start.min(column_line);
+                    area.column_end = area.column_end.max(column_line + 1);
+                }
+                None => areas.push(GridTemplateArea {
+                    name: name.to_string(),
+                    row_start: row_line,
+                    row_end: row_line + 1,
+                    column_start: column_line,
+                    column_end: column_line + 1,
+                }),
+            }
+        }
+    }
+
+    // Every cell within the bounds of an area belongs to the area.
+    for area in &areas {
+        let is_rectangle = (area.row_start..area.row_end).all(|row_line| {
+            (area.column_start..area.column_end).all(|column_line| {
+                rows[row_line as usize - 1][column_line as usize - 1] == area.name
+            })
+        });
+
+        if !is_rectangle {
+            return Err(GridTemplateError::NonRectangularArea(area.name.clone()));
+        }
+    }
+
+    Ok(areas)
+}
+
+/// A single track breadth.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Breadth {
+    Length(f32),
+    Percent(f32),
+    Fr(f32),
+    Auto,
+    MinContent,
+    MaxContent,
+}
+
+impl Breadth {
+    /// Parses `200px`, `200`, `50%`, `1fr`, `auto`, `min-content` and `max-content`.
+    fn parse(value: &str) -> Option<Self> {
+        let number = |value: &str| {
+            value
+                .parse::<f32>()
+                .ok()
+                .filter(|number| number.is_finite() && *number >= 0.0)
+        };
+
+        Some(match value {
+            "auto" => Self::Auto,
+            "min-content" => Self::MinContent,
+            "max-content" => Self::MaxContent,
+            _ => {
+                if let Some(value) = value.strip_suffix("fr") {
+                    Self::Fr(number(value)?)
+                } else if let Some(value) = value.strip_suffix('%') {
+                    Self::Percent(number(value)? / 100.0)
+                } else {
+                    Self::Length(number(value.strip_suffix("px").unwrap_or(value))?)
+                }
+            }
+        })
+    }
+
+    /// A flexible length is not a valid minimum.
+    fn min(self) -> Option<MinTrackSizingFunction> {
+        Some(match self {
+            Self::Length(value) => MinTrackSizingFunction::length(value),
+            Self::Percent(value) => MinTrackSizingFunction::percent(value),
+            Self::Fr(_) => return None,
+            Self::Auto => MinTrackSizingFunction::auto(),
+            Self::MinContent => MinTrackSizingFunction::min_content(),
+            Self::MaxContent => MinTrackSizingFunction::max_content(),
+        })
+    }
+
+    fn max(self) -> MaxTrackSizingFunction {
+        match self {
+            Self::Length(value) => MaxTrackSizingFunction::length(value),
+            Self::Percent(value) => MaxTrackSizingFunction::percent(value),
+            Self::Fr(value) => MaxTrackSizingFunction::fr(value),
+            Self::Auto => MaxTrackSizingFunction::auto(),
+            Self::MinContent => MaxTrackSizingFunction::min_content(),
+            Self::MaxContent => MaxTrackSizingFunction::max_content(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use taffy::style_helpers::{fr, length, minmax, percent};
+
+    use super::*;
+    use crate::prelude::div;
+
+    #[test]
+    fn test_grid_template() {
+        let node = div().grid_cols("1fr 200px repeat(3, minmax(0, 1fr))");
+
+        assert_eq!(
+            node.style.grid_template_columns,
+            vec![
+                GridTemplateComponent::Single(MinMax {
+                    min: MinTrackSizingFunction::auto(),
+                    max: MaxTrackSizingFunction::fr(1.0),
+                }),
+                GridTemplateComponent::Single(length(200.0)),
+                GridTemplateComponent::Repeat(GridTemplateRepetition {
+                    count: RepetitionCount::Count(3),
+                    tracks: vec![minmax(length(0.0), fr(1.0))],
+                    line_names: Vec::new(),
+                }),
+            ]
+        );
+        assert!(node.style.grid_template_column_names.is_empty());
+
+        let node = div().grid_rows("auto 50% fit-content(120px) repeat(auto-fill, 100px)");
+
+        assert_eq!(
+            node.style.grid_template_rows,
+            vec![
+                GridTemplateComponent::Single(MinMax {
+                    min: MinTrackSizingFunction::auto(),
+                    max: MaxTrackSizingFunction::auto(),
+                }),
+                GridTemplateComponent::Single(percent(0.5)),
+                GridTemplateComponent::Single(MinMax {
+                    min: MinTrackSizingFunction::auto(),
+                    max: MaxTrackSizingFunction::fit_content(LengthPercentage::length(120.0)),
+                }),
+                GridTemplateComponent::Repeat(GridTemplateRepetition {
+                    count: RepetitionCount::AutoFill,
+                    tracks: vec![length(100.0)],
+                    line_names: Vec::new(),
+                }),
+            ]
+        );
+
+        assert_eq!(div().grid_cols_n(4).style.grid_template_columns.len(), 1);
+    }
+
+    #[test]
+    fn test_grid_line_names() {
+        let node = div().grid_cols("[full-start] 200px [content-start main] 1fr [full-end]");
+
+        assert_eq!(node.style.grid_template_columns.len(), 2);
+        assert_eq!(
+            node.style.grid_template_column_names,
+            vec![
+                vec!["full-start".to_string()],
+                vec!["content-start".to_string(), "main".to_string()],
+                vec!["full-end".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grid_invalid_template() {
+        let invalid = [
+            (
+                "1fr (200px",
+                GridTemplateError::Unbalanced("1fr (200px".to_string()),
+            ),
+            (
+                "1fr wide",
+                GridTemplateError::InvalidTrack("wide".to_string()),
+            ),
+            (
+                "minmax(1fr, 2fr)",
+                GridTemplateError::InvalidTrack("minmax(1fr, 2fr)".to_string()),
+            ),
+            (
+                "repeat(0, 1fr)",
+                GridTemplateError::InvalidRepetition("repeat(0, 1fr)".to_string()),
+            ),
+            (
+                "repeat(2, repeat(2, 1fr))",
+                GridTemplateError::InvalidRepetition("repeat(2, 1fr)".to_string()),
+            ),
+        ];
+
+        for (template, expected) in invalid {
+            let mut node = div().grid_cols_n(2);
+            let error = node.try_grid_cols(template).unwrap_err();
+
+            assert_eq!(error, expected, "{template}");
+            // The previous template is kept.
+            assert_eq!(node.style.grid_template_columns, evenly_sized_tracks(2));
+        }
+    }
+
+    #[test]
+    fn test_grid_areas() {
+        let node = div().grid_areas([
+            "header header header",
+            "sidebar main main",
+            ". footer footer",
+        ]);
+
+        assert_eq!(
+            node.style.grid_template_areas,
+            vec![
+                GridTemplateArea {
+                    name: "header".to_string(),
+                    row_start: 1,
+                    row_end: 2,
+                    column_start: 1,
+                    column_end: 4,
+                },
+                GridTemplateArea {
+                    name: "sidebar".to_string(),
+                    row_start: 2,
+                    row_end: 3,
+                    column_start: 1,
+                    column_end: 2,
+                },
+                GridTemplateArea {
+                    name: "main".to_string(),
+                    row_start: 2,
+                    row_end: 3,
+                    column_start: 2,
+                    column_end: 4,
+                },
+                GridTemplateArea {
+                    name: "footer".to_string(),
+                    row_start: 3,
+                    row_end: 4,
+                    column_start: 2,
+                    column_end: 4,
+                },
+            ]
+        );
+
+        assert_eq!(
+            div().try_grid_areas(["a a", "a"]).unwrap_err(),
+            GridTemplateError::UnequalAreaRows
+        );
+        assert_eq!(
+            div().try_grid_areas(["a b", "b a"]).unwrap_err(),
+            GridTemplateError::NonRectangularArea("a".to_string())
+        );
+    }
+
+    #[test]
+    fn test_grid_placement() {
+        let node = div().col_span(2).row_start(1).row_end(-1);
+
+        assert_eq!(node.style.grid_column.start, GridPlacement::Span(2));
+        assert_eq!(node.style.grid_column.end, GridPlacement::Auto);
+        assert_eq!(node.style.grid_row, full_line());
+
+        let node = div().grid_area("main");
+
+        assert_eq!(
+            node.style.grid_column.start,
+            GridPlacement::NamedLine("main-start".to_string(), 1)
+        );
+        assert_eq!(
+            node.style.grid_row.end,
+            GridPlacement::NamedLine("main-end".to_string(), 1)
+        );
+
+        let node = div().grid_flow_col_dense().col_auto();
+
+        assert_eq!(node.style.grid_auto_flow, GridAutoFlow::ColumnDense);
+        assert_eq!(node.style.grid_column, Line::default());
+    }
+}
@@ -0,0 +1,137 @@
+use bevy_ecs::{
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    query::{Has, With, Without},
+    system::{Query, SystemParam},
+};
+use smallvec::SmallVec;
+
+use crate::components::div::Div;
+
+#[cfg(feature = "ghost_nodes")]
+use crate::components::ghost::GhostNode;
+
+#[cfg(feature = "ghost_nodes")]
+pub(crate) type UiNodeFilter = bevy_ecs::query::Or<(With<Div>, With<GhostNode>)>;
+
+#[cfg(not(feature = "ghost_nodes"))]
+pub(crate) type UiNodeFilter = With<Div>;
+
+/// Traverses the UI hierarchy, walks through the ghost nodes.
+///
+/// The children of a ghost node are treated as the children of its nearest [`Div`] ancestor.
+#[derive(SystemParam)]
+pub struct UiChildren<'w, 's> {
+    node_query: Query<'w, 's, (Option<&'static Children>, Has<Div>), UiNodeFilter>,
+    parent_query: Query<'w, 's, &'static ChildOf, UiNodeFilter>,
+}
+
+impl<'w, 's> UiChildren<'w, 's> {
+    /// Iterates the [`Div`] children of the entity, through its ghost children.
+    pub fn iter(&self, entity: Entity) -> UiChildrenIter<'_, 'w, 's> {
+        let mut stack = SmallVec::new();
+
+        if let Ok((Some(children), _)) = self.node_query.get(entity) {
+            stack.push(&**children);
+        }
+
+        UiChildrenIter {
+            node_query: &self.node_query,
+            stack,
+        }
+    }
+
+    /// Returns the nearest [`Div`] ancestor of the entity, through its ghost ancestors.
+    pub fn parent(&self, entity: Entity) -> Option<Entity> {
+        let mut current = entity;
+
+        while let Ok(child_of) = self.parent_query.get(current) {
+            let parent = child_of.parent();
+
+            match self.node_query.get(parent) {
+                Ok((_, true)) => return Some(parent),
+                // A ghost node.
+                Ok((_, false)) => current = parent,
+                Err(_) => return None,
+            }
+        }
+
+        None
+    }
+
+    /// Returns the root [`Div`] of the entity, which may be the entity itself.
+    ///
+    /// Returns `None` if the entity is neither a [`Div`] nor has a [`Div`] ancestor,
+    /// e.g. a root ghost node.
+    pub fn root(&self, entity: Entity) -> Option<Entity> {
+        let mut root = self
+            .node_query
+            .get(entity)
+            .is_ok_and(|(_, is_div)| is_div)
+            .then_some(entity);
+        let mut current = entity;
+
+        while let Some(parent) = self.parent(current) {
+            root = Some(parent);
+            current = parent;
+        }
+
+        root
+    }
+}
+
+/// An iterator over the [`Div`] children of an entity, see [`UiChildren::iter`].
+pub struct UiChildrenIter<'a, 'w, 's> {
+    node_query: &'a Query<'w, 's, (Option<&'static Children>, Has<Div>), UiNodeFilter>,
+    stack: SmallVec<[&'a [Entity]; 8]>,
+}
+
+impl<'a, 'w, 's> Iterator for UiChildrenIter<'a, 'w, 's> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_query = self.node_query;
+
+        loop {
+            let entities = self.stack.last_mut()?;
+            let slice: &'a [Entity] = *entities;
+
+            let Some((&entity, rest)) = slice.split_first() else {
+                self.stack.pop();
+                continue;
+            };
+
+            *entities = rest;
+
+            match node_query.get(entity) {
+                Ok((_, true)) => return Some(entity),
+                // Walks through the ghost node.
+                Ok((Some(children), false)) => self.stack.push(&**children),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Iterates the root [`Div`]s, which have no [`Div`] ancestor.
+///
+/// The [`Div`] children of a root ghost node are roots as well.
+#[derive(SystemParam)]
+pub struct UiRootNodes<'w, 's> {
+    root_query: Query<'w, 's, (Entity, Has<Div>), (UiNodeFilter, Without<ChildOf>)>,
+    ui_children: UiChildren<'w, 's>,
+}
+
+impl<'w, 's> UiRootNodes<'w, 's> {
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.root_query.iter().flat_map(move |(entity, is_div)| {
+            let (root, children) = if is_div {
+                (Some(entity), None)
+            } else {
+                (None, Some(self.ui_children.iter(entity)))
+            };
+
+            root.into_iter().chain(children.into_iter().flatten())
+        })
+    }
+}
@@ -3,36 +3,320 @@ use std::fmt;
 use bevy_ecs::{
     entity::{Entity, EntityHashMap},
     error::Result,
+    reflect::ReflectResource,
     resource::Resource,
     system::Query,
 };
-use bevy_math::{UVec2, Vec2};
-use bevy_platform::collections::hash_map::Entry;
+use bevy_math::Vec2;
+use bevy_platform::{
+    collections::hash_map::Entry,
+    sync::{Mutex, PoisonError},
+};
+use bevy_reflect::{Reflect, prelude::ReflectDefault};
+use bevy_tasks::ComputeTaskPool;
 use bevy_text::{ComputedTextBlock, FontCx};
 use stacksafe::stacksafe;
-use taffy::{Layout, NodeId, Style, TaffyTree};
+use taffy::{Layout, NodeId, Style, TaffyResult, TaffyTree};
 
 use crate::{
     components::text::TextMeasure,
+    error::MoonError,
     measure::{MeasureArgs, NodeContext},
 };
 
-#[derive(Resource)]
+/// Settings of the layout.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct UiLayoutSettings {
+    /// When set to `true`, the roots are laid out in parallel on the [`ComputeTaskPool`].
+    pub parallel: bool,
+}
+
+impl Default for UiLayoutSettings {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl UiLayoutSettings {
+    pub const DEFAULT: Self = Self { parallel: true };
+}
+
+/// The layout trees of the roots.
+///
+/// Each root owns a [`UiLayoutShard`] holding its descendants,
+/// so the independent roots can be laid out in parallel.
+#[derive(Resource, Default)]
 pub struct UiLayoutTree {
-    taffy: TaffyTree<NodeContext>,
-    node_map: EntityHashMap<NodeId>,
+    shards: EntityHashMap<UiLayoutShard>,
+    node_roots: EntityHashMap<Entity>,
 }
 
 impl fmt::Debug for UiLayoutTree {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("LayoutTree")
+            .field("shards", &self.shards)
+            .field("node_roots", &self.node_roots)
+            .finish()
+    }
+}
+
+fn _assert_send_sync_layout_tree_impl_safe() {
+    fn _assert_send_sync<T: Send + Sync>() {}
+    _assert_send_sync::<EntityHashMap<NodeId>>();
+    _assert_send_sync::<UiLayoutShard>();
+    _assert_send_sync::<UiLayoutTree>();
+}
+
+impl UiLayoutTree {
+    /// Inserts or updates the node in the shard of the root.
+    ///
+    /// The node is removed from its previous shard if it has been moved into another root.
+    pub fn upsert_node(
+        &mut self,
+        root_entity: Entity,
+        entity: Entity,
+        style: Style,
+        node_context: Option<NodeContext>,
+    ) -> Result<NodeId, MoonError> {
+        if let Some(previous_root) = self.node_roots.insert(entity, root_entity)
+            && previous_root != root_entity
+        {
+            if previous_root == entity {
+                // A previous root, its descendants are moved as they are visited.
+                self.shards.remove(&entity);
+            } else if let Some(shard) = self.shards.get_mut(&previous_root) {
+                shard
+                    .remove_node(entity)
+                    .map_err(|error| MoonError::Layout { entity, error })?;
+            }
+        }
+
+        self.shards
+            .entry(root_entity)
+            .or_default()
+            .upsert_node(entity, style, node_context)
+            .map_err(|error| MoonError::Layout { entity, error })
+    }
+
+    /// Sets the children of the node in the shard of the root, only if they have been changed.
+    pub fn update_node_children(
+        &mut self,
+        root_entity: Entity,
+        entity: Entity,
+        children: &[NodeId],
+    ) -> Result<(), MoonError> {
+        let Some(shard) = self.shards.get_mut(&root_entity) else {
+            return Ok(());
+        };
+        let Some(node_id) = shard.get_node_id(entity) else {
+            return Ok(());
+        };
+
+        shard
+            .update_node_children(node_id, children)
+            .map_err(|error| MoonError::Layout { entity, error })
+    }
+
+    /// Returns the root whose shard holds the node.
+    pub fn root_of(&self, entity: Entity) -> Option<Entity> {
+        self.node_roots.get(&entity).copied()
+    }
+
+    pub fn get_shard(&self, entity: Entity) -> Option<&UiLayoutShard> {
+        self.node_roots
+            .get(&entity)
+            .and_then(|root_entity| self.shards.get(root_entity))
+    }
+
+    fn get_shard_mut(&mut self, entity: Entity) -> Option<&mut UiLayoutShard> {
+        self.node_roots
+            .get(&entity)
+            .and_then(|root_entity| self.shards.get_mut(root_entity))
+    }
+
+    pub fn get_node_id(&self, entity: Entity) -> Option<NodeId> {
+        self.get_shard(entity)?.get_node_id(entity)
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.get_node_id(entity).is_some()
+    }
+
+    /// Removes the nodes, keeps going on errors and returns them.
+    pub fn remove_nodes(&mut self, entities: impl Iterator<Item = Entity>) -> Vec<MoonError> {
+        entities
+            .filter_map(|entity| self.remove_node(entity).err())
+            .collect()
+    }
+
+    pub fn remove_node(&mut self, entity: Entity) -> Result<(), MoonError> {
+        let Some(root_entity) = self.node_roots.remove(&entity) else {
+            return Ok(());
+        };
+
+        if root_entity == entity {
+            self.shards.remove(&entity);
+        } else if let Some(shard) = self.shards.get_mut(&root_entity) {
+            shard
+                .remove_node(entity)
+                .map_err(|error| MoonError::Layout { entity, error })?;
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_node_children(&mut self, entity: Entity) -> Result<(), MoonError> {
+        let Some(shard) = self.get_shard_mut(entity) else {
+            return Ok(());
+        };
+
+        shard
+            .remove_node_children(entity)
+            .map_err(|error| MoonError::Layout { entity, error })
+    }
+
+    /// Removes the children of the nodes, keeps going on errors and returns them.
+    pub fn remove_nodes_children(
+        &mut self,
+        entities: impl Iterator<Item = Entity>,
+    ) -> Vec<MoonError> {
+        entities
+            .filter_map(|entity| self.remove_node_children(entity).err())
+            .collect()
+    }
+
+    pub fn get_layout(&self, entity: Entity) -> Result<Layout> {
+        let Some(shard) = self.get_shard(entity) else {
+            return Err("Invalid hierarchy".into());
+        };
+
+        shard.get_layout(entity)
+    }
+
+    /// Computes the layouts of the roots within their available sizes.
+    ///
+    /// When `parallel` is `true`, the shards are laid out on the [`ComputeTaskPool`],
+    /// the text nodes are measured one at a time through a shared [`TextMeasureCx`].
+    ///
+    /// A root which fails to be laid out doesn't stop the others, the errors are returned.
+    pub fn compute_layouts(
+        &mut self,
+        available_sizes: &EntityHashMap<Vec2>,
+        text_block_query: &mut Query<'_, '_, &'static mut ComputedTextBlock>,
+        font_system: &mut FontCx,
+        parallel: bool,
+    ) -> Vec<MoonError> {
+        let text_cx = TextMeasureCx::new(text_block_query, font_system);
+
+        let mut shards = self
+            .shards
+            .iter_mut()
+            .filter_map(|(&root_entity, shard)| {
+                Some((root_entity, shard, *available_sizes.get(&root_entity)?))
+            })
+            .collect::<Vec<_>>();
+
+        if !parallel || shards.len() <= 1 {
+            return shards
+                .into_iter()
+                .filter_map(|(root_entity, shard, available_size)| {
+                    shard
+                        .compute_layout(root_entity, available_size, &text_cx)
+                        .err()
+                })
+                .collect();
+        }
+
+        let task_pool = ComputeTaskPool::get();
+        let chunk_size = shards.len().div_ceil(task_pool.thread_num()).max(1);
+
+        task_pool
+            .scope(|scope| {
+                for chunk in shards.chunks_mut(chunk_size) {
+                    let text_cx = &text_cx;
+
+                    scope.spawn(async move {
+                        chunk
+                            .iter_mut()
+                            .filter_map(|(root_entity, shard, available_size)| {
+                                shard
+                                    .compute_layout(*root_entity, *available_size, text_cx)
+                                    .err()
+                            })
+                            .collect::<Vec<_>>()
+                    });
+                }
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+/// Shares the text measurement state between the layout tasks.
+///
+/// [`FontCx`] and the text buffers can't be used by several tasks at once,
+/// so the nodes are measured one at a time, the rest of the layout runs in parallel.
+pub struct TextMeasureCx<'a, 'w, 's> {
+    inner: Mutex<(
+        &'a mut Query<'w, 's, &'static mut ComputedTextBlock>,
+        &'a mut FontCx,
+    )>,
+}
+
+impl<'a, 'w, 's> TextMeasureCx<'a, 'w, 's> {
+    pub fn new(
+        text_block_query: &'a mut Query<'w, 's, &'static mut ComputedTextBlock>,
+        font_system: &'a mut FontCx,
+    ) -> Self {
+        Self {
+            inner: Mutex::new((text_block_query, font_system)),
+        }
+    }
+
+    /// Measures the node with the locked text state.
+    pub fn measure(
+        &self,
+        node_context: &mut NodeContext,
+        known_dimensions: taffy::Size<Option<f32>>,
+        available_space: taffy::Size<taffy::AvailableSpace>,
+        style: &Style,
+    ) -> Vec2 {
+        let mut guard = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let (text_block_query, font_system) = &mut *guard;
+
+        let text_buffer = TextMeasure::needs_buffer(known_dimensions.height, available_space.width)
+            .then(|| node_context.get_text_buffer(text_block_query))
+            .flatten();
+
+        let args = MeasureArgs {
+            known_dimensions,
+            available_space,
+            font_system,
+            text_buffer,
+        };
+
+        node_context.measure(args, style)
+    }
+}
+
+/// The layout tree of a root and its descendants.
+pub struct UiLayoutShard {
+    taffy: TaffyTree<NodeContext>,
+    node_map: EntityHashMap<NodeId>,
+}
+
+impl fmt::Debug for UiLayoutShard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LayoutShard")
             .field("taffy", &self.taffy)
             .field("node_map", &self.node_map)
             .finish()
     }
 }
 
-impl Default for UiLayoutTree {
+impl Default for UiLayoutShard {
     fn default() -> Self {
         Self {
             taffy: TaffyTree::new(),
@@ -41,90 +325,93 @@ impl Default for UiLayoutTree {
     }
 }
 
-unsafe impl Send for UiLayoutTree {}
-unsafe impl Sync for UiLayoutTree {}
-
-fn _assert_send_sync_layout_tree_impl_safe() {
-    fn _assert_send_sync<T: Send + Sync>() {}
-    _assert_send_sync::<EntityHashMap<NodeId>>();
-    _assert_send_sync::<UiLayoutTree>();
-}
-
-const EXPECT_MESSAGE: &str = "we should avoid taffy layout errors";
+unsafe impl Send for UiLayoutShard {}
+unsafe impl Sync for UiLayoutShard {}
 
-impl UiLayoutTree {
+impl UiLayoutShard {
     pub fn upsert_node(
         &mut self,
         entity: Entity,
         style: Style,
         node_context: Option<NodeContext>,
-    ) -> NodeId {
+    ) -> TaffyResult<NodeId> {
         let taffy = &mut self.taffy;
 
         match self.node_map.entry(entity) {
             Entry::Occupied(entry) => {
                 let node_id = *entry.get();
-                self.set_node_style(node_id, style);
-                self.set_node_context(node_id, node_context);
-                node_id
+                self.set_node_style(node_id, style)?;
+                self.set_node_context(node_id, node_context)?;
+                Ok(node_id)
             }
             Entry::Vacant(entry) => {
                 let node_id = if let Some(context) = node_context {
                     taffy.new_leaf_with_context(style, context)
                 } else {
                     taffy.new_leaf(style)
-                }
-                .expect(EXPECT_MESSAGE);
+                }?;
                 entry.insert(node_id);
-                node_id
+                Ok(node_id)
             }
         }
     }
 
-    pub fn set_node_style(&mut self, id: NodeId, style: Style) {
-        self.taffy.set_style(id, style).expect(EXPECT_MESSAGE);
+    pub fn set_node_style(&mut self, id: NodeId, style: Style) -> TaffyResult<()> {
+        self.taffy.set_style(id, style)
     }
 
-    pub fn set_node_context(&mut self, id: NodeId, node_context: Option<NodeContext>) {
-        self.taffy
-            .set_node_context(id, node_context)
-            .expect(EXPECT_MESSAGE);
+    pub fn set_node_context(
+        &mut self,
+        id: NodeId,
+        node_context: Option<NodeContext>,
+    ) -> TaffyResult<()> {
+        self.taffy.set_node_context(id, node_context)
     }
 
-    pub fn set_node_children(&mut self, node_id: NodeId, children: &[NodeId]) {
-        self.taffy
-            .set_children(node_id, children)
-            .expect(EXPECT_MESSAGE);
+    pub fn set_node_children(&mut self, node_id: NodeId, children: &[NodeId]) -> TaffyResult<()> {
+        self.taffy.set_children(node_id, children)
     }
 
-    pub fn contains(&self, entity: Entity) -> bool {
-        self.node_map.contains_key(&entity)
+    /// Sets the children of the node, only if they have been changed.
+    pub fn update_node_children(
+        &mut self,
+        node_id: NodeId,
+        children: &[NodeId],
+    ) -> TaffyResult<()> {
+        let unchanged = self.taffy.child_count(node_id) == children.len()
+            && children.iter().enumerate().all(|(index, &child)| {
+                self.taffy
+                    .child_at_index(node_id, index)
+                    .is_ok_and(|id| id == child)
+            });
+
+        if unchanged {
+            return Ok(());
+        }
+
+        self.set_node_children(node_id, children)
     }
 
-    pub fn remove_nodes(&mut self, entities: impl Iterator<Item = Entity>) {
-        for entity in entities {
-            self.remove_node(entity);
-        }
+    pub fn get_node_id(&self, entity: Entity) -> Option<NodeId> {
+        self.node_map.get(&entity).copied()
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.node_map.contains_key(&entity)
     }
 
-    pub fn remove_node(&mut self, entity: Entity) {
+    pub fn remove_node(&mut self, entity: Entity) -> TaffyResult<()> {
         let Some(node_id) = self.node_map.remove(&entity) else {
-            return;
+            return Ok(());
         };
-        self.taffy.remove(node_id).expect(EXPECT_MESSAGE);
+        self.taffy.remove(node_id).map(|_| ())
     }
 
-    pub fn remove_node_children(&mut self, entity: Entity) {
+    pub fn remove_node_children(&mut self, entity: Entity) -> TaffyResult<()> {
         let Some(&node_id) = self.node_map.get(&entity) else {
-            return;
+            return Ok(());
         };
-        self.set_node_children(node_id, &[]);
-    }
-
-    pub fn remove_nodes_children(&mut self, entities: impl Iterator<Item = Entity>) {
-        for entity in entities {
-            self.remove_node_children(entity);
-        }
+        self.set_node_children(node_id, &[])
     }
 
     pub fn get_layout(&self, entity: Entity) -> Result<Layout> {
@@ -138,23 +425,36 @@ impl UiLayoutTree {
     }
 
     #[stacksafe]
-    pub fn compute_layout<'a>(
+    pub fn compute_layout(
         &mut self,
         root_node_entity: Entity,
-        physical_size: UVec2,
-        text_block_query: &'a mut Query<&mut ComputedTextBlock>,
-        font_system: &'a mut FontCx,
-    ) {
-        let node_id = *self
-            .node_map
-            .entry(root_node_entity)
-            .or_insert_with(|| self.taffy.new_leaf(Style::DEFAULT).expect(EXPECT_MESSAGE));
+        available_size: Vec2,
+        text_cx: &TextMeasureCx,
+    ) -> Result<(), MoonError> {
+        let to_error = |error| MoonError::Layout {
+            entity: root_node_entity,
+            error,
+        };
+
+        let node_id = match self.node_map.entry(root_node_entity) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                *entry.insert(self.taffy.new_leaf(Style::DEFAULT).map_err(to_error)?)
+            }
+        };
 
-        let physical_size = physical_size.as_vec2();
+        // An infinite axis is sized to fit its content.
+        let to_available_space = |value: f32| {
+            if value.is_finite() {
+                taffy::AvailableSpace::Definite(value.max(0.0))
+            } else {
+                taffy::AvailableSpace::MaxContent
+            }
+        };
 
         let available_space = taffy::Size {
-            width: taffy::AvailableSpace::Definite(physical_size.x),
-            height: taffy::AvailableSpace::Definite(physical_size.y),
+            width: to_available_space(available_size.x),
+            height: to_available_space(available_size.y),
         };
 
         self.taffy
@@ -166,19 +466,8 @@ impl UiLayoutTree {
                         return taffy::Size::ZERO;
                     };
 
-                    let text_buffer =
-                        TextMeasure::needs_buffer(known_dimensions.height, available_space.width)
-                            .then(|| node_context.get_text_buffer(text_block_query))
-                            .flatten();
-
-                    let args = MeasureArgs {
-                        known_dimensions,
-                        available_space,
-                        font_system,
-                        text_buffer,
-                    };
-
-                    let Vec2 { x, y } = node_context.measure(args, style);
+                    let Vec2 { x, y } =
+                        text_cx.measure(node_context, known_dimensions, available_space, style);
 
                     taffy::Size {
                         width: x,
@@ -186,13 +475,13 @@ impl UiLayoutTree {
                     }
                 },
             )
-            .expect(EXPECT_MESSAGE);
+            .map_err(to_error)
     }
 }
 
 // Debug and perf
 #[allow(dead_code)]
-impl UiLayoutTree {
+impl UiLayoutShard {
     fn count_all_children(&self, parent: NodeId) -> Result<u32> {
         let mut count = 0;
 
@@ -210,7 +499,7 @@ impl UiLayoutTree {
     fn max_depth(&self, depth: u32, parent: NodeId) -> Result<u32> {
         use taffy::TraversePartialTree;
 
-        println!(
+        tracing::debug!(
             "{parent:?} at depth {depth} has {} children",
             self.taffy.child_count(parent)
         );
@@ -235,15 +524,167 @@ impl UiLayoutTree {
 
         Ok(edges)
     }
+}
 
-    pub fn print_tree(&mut self) {
-        for (entity, &node_id) in &self.node_map {
-            println!("Entity: {entity}");
-            self.taffy.print_tree(node_id);
-            // self.get_edges(node_id);
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        system::{ResMut, RunSystemOnce},
+        world::World,
+    };
+    use bevy_tasks::TaskPool;
+    use taffy::{
+        AvailableSpace, FlexDirection, Size,
+        style_helpers::{length, percent},
+    };
+
+    use super::*;
+
+    const AVAILABLE_SIZE: Vec2 = Vec2::new(300.0, 200.0);
+
+    /// The styles of two roots, each one followed by the styles of its children.
+    fn root_styles() -> [[Style; 3]; 2] {
+        [
+            [
+                Style {
+                    size: Size {
+                        width: length(200.0),
+                        height: length(100.0),
+                    },
+                    padding: length(10.0),
+                    gap: length(4.0),
+                    ..Style::DEFAULT
+                },
+                Style {
+                    flex_grow: 1.0,
+                    ..Style::DEFAULT
+                },
+                Style {
+                    size: Size {
+                        width: length(50.0),
+                        height: percent(0.5),
+                    },
+                    ..Style::DEFAULT
+                },
+            ],
+            [
+                Style {
+                    flex_direction: FlexDirection::Column,
+                    size: percent(1.0),
+                    padding: length(8.0),
+                    ..Style::DEFAULT
+                },
+                Style {
+                    size: Size {
+                        width: percent(1.0),
+                        height: length(30.0),
+                    },
+                    margin: length(2.0),
+                    ..Style::DEFAULT
+                },
+                Style {
+                    flex_grow: 1.0,
+                    ..Style::DEFAULT
+                },
+            ],
+        ]
+    }
+
+    /// Lays out the roots one after the other in a single tree.
+    fn single_tree_layouts() -> Vec<Layout> {
+        let mut taffy = TaffyTree::<()>::new();
+        let mut layouts = Vec::new();
+
+        for [root, children @ ..] in root_styles() {
+            let children = children
+                .into_iter()
+                .map(|style| taffy.new_leaf(style).unwrap())
+                .collect::<Vec<_>>();
+            let root = taffy.new_with_children(root, &children).unwrap();
+
+            taffy
+                .compute_layout(
+                    root,
+                    Size {
+                        width: AvailableSpace::Definite(AVAILABLE_SIZE.x),
+                        height: AvailableSpace::Definite(AVAILABLE_SIZE.y),
+                    },
+                )
+                .unwrap();
+
+            layouts.push(*taffy.layout(root).unwrap());
+            layouts.extend(children.iter().map(|&child| *taffy.layout(child).unwrap()));
         }
+
+        layouts
     }
-}
 
-#[cfg(test)]
-mod tests {}
+    /// Lays out the roots in their own shards.
+    fn sharded_layouts(parallel: bool) -> Vec<Layout> {
+        ComputeTaskPool::get_or_init(TaskPool::default);
+
+        let mut world = World::new();
+        world.init_resource::<FontCx>();
+
+        let roots =
+            root_styles().map(|styles| styles.map(|style| (world.spawn_empty().id(), style)));
+
+        let mut ui_layout_tree = UiLayoutTree::default();
+        let mut available_sizes = EntityHashMap::default();
+
+        for nodes in &roots {
+            let root_entity = nodes[0].0;
+            let node_ids = nodes
+                .iter()
+                .map(|(entity, style)| {
+                    ui_layout_tree
+                        .upsert_node(root_entity, *entity, style.clone(), None)
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+
+            ui_layout_tree
+                .update_node_children(root_entity, root_entity, &node_ids[1..])
+                .unwrap();
+            available_sizes.insert(root_entity, AVAILABLE_SIZE);
+        }
+
+        world.insert_resource(ui_layout_tree);
+
+        let errors = world
+            .run_system_once(
+                move |mut ui_layout_tree: ResMut<UiLayoutTree>,
+                      mut text_block_query: Query<&mut ComputedTextBlock>,
+                      mut font_cx: ResMut<FontCx>| {
+                    ui_layout_tree.compute_layouts(
+                        &available_sizes,
+                        &mut text_block_query,
+                        &mut font_cx,
+                        parallel,
+                    )
+                },
+            )
+            .unwrap();
+
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let ui_layout_tree = world.resource::<UiLayoutTree>();
+
+        roots
+            .iter()
+            .flatten()
+            .map(|(entity, _)| ui_layout_tree.get_layout(*entity).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_sharded_layout_matches_single_tree() {
+        let layouts = single_tree_layouts();
+
+        // The growing child fills the row, less the padding, the gap and the fixed child.
+        assert_eq!(layouts[1].size.width, 200.0 - 2.0 * 10.0 - 4.0 - 50.0);
+
+        assert_eq!(sharded_layouts(false), layouts);
+        assert_eq!(sharded_layouts(true), layouts);
+    }
+}
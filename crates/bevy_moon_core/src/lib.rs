@@ -1,25 +1,69 @@
 // Copyright © Fangdun Tsai <fundon@pindash.io>
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+#[cfg(feature = "animation")]
+pub mod animation;
+mod class;
 mod components;
+mod dirty;
+mod error;
 pub mod geometry;
+mod grid;
+pub mod hierarchy;
 mod layout;
 mod measure;
-mod picking;
+#[cfg(feature = "picking")]
+pub mod picking;
 mod plugin;
 mod stack;
 mod style;
+mod style_sheet;
 mod systems;
+mod theme;
+mod units;
 
 pub mod prelude {
-    pub use crate::components::computed::ComputedLayout;
+    #[cfg(feature = "animation")]
+    pub use crate::animation::{
+        AnimatedDiv, DivBackground, DivBorderColor, DivCornerRadii, DivImageColor,
+        DivShadowBlurRadius, DivShadowColor, DivShadowOffset, DivShadowSpreadRadius, DivSize,
+        DivTextColor,
+    };
+    pub use crate::components::computed::{ClipRegion, ComputedClip, ComputedDiv, ComputedLayout};
     pub use crate::components::div::{Div, div};
+    #[cfg(feature = "ghost_nodes")]
+    pub use crate::components::ghost::GhostNode;
     pub use crate::components::image::{Image, img};
+    pub use crate::components::interaction::InteractionState;
+    pub use crate::components::layout_animation::{
+        ExitCommandsExt, Exiting, LayoutAnimation, LayoutAnimationState, LayoutEnter, LayoutExit,
+        LayoutMotion,
+    };
+    pub use crate::components::root::UiRoot;
+    pub use crate::components::scroll::{ScrollIntoView, ScrollPosition};
     pub use crate::components::text::{Text, text};
+    pub use crate::components::transition::{Transition, TransitionProperties, TransitionState};
+    pub use crate::dirty::UiRecomputeCounter;
+    pub use crate::error::{MoonError, MoonErrorEvent};
     pub use crate::geometry;
-    pub use crate::layout::UiLayoutTree;
+    pub use crate::grid::GridTemplateError;
+    pub use crate::hierarchy::{UiChildren, UiRootNodes};
+    pub use crate::layout::{UiLayoutSettings, UiLayoutTree};
     pub use crate::measure::{Measure, MeasureArgs};
+    #[cfg(feature = "picking")]
+    pub use crate::picking::{
+        MoonPickingCamera, MoonPickingPlugin, MoonPickingSettings, RelativePointerPosition,
+    };
     pub use crate::plugin::MoonCorePlugin;
-    pub use crate::stack::UiStackMap;
-    pub use crate::style::{BoxShadow, Corners, ObjectFit, ObjectPosition};
+    pub use crate::stack::{UiStack, UiStackMap};
+    pub use crate::style::{
+        BorderStyle, BoxShadow, ColorSpace, ColorStop, Corners, Edges, Gradient, GradientKind,
+        LayoutStyle, ObjectFit, ObjectPosition, Outline,
+    };
+    pub use crate::style_sheet::{
+        Class, StyleSheet, StyleSheetError, StyleSheetLoader, UiStyleSheets,
+    };
+    pub use crate::systems::UiSystems;
+    pub use crate::theme::{MoonTheme, ThemeMode, ThemeProperty};
+    pub use crate::units::{LengthProperty, RelativeLength, UnitContext};
 }
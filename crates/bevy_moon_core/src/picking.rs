@@ -1 +1,500 @@
+//! A [`bevy_picking`] backend for the nodes.
+//!
+//! The rays of the pointers are casted against the quad of each node, through its
+//! [`GlobalTransform`] and [`ComputedLayout`], so the nodes can be picked with both 2D and 3D cameras
+//! whatever their transforms are.
+//!
+//! Since the nodes are drawn on top of the scene in their stack order, the hits are reported
+//! from the topmost node to the bottommost one, and ordered above the other backends of the same camera.
 
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_camera::Camera;
+use bevy_camera::visibility::InheritedVisibility;
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    component::Component,
+    entity::{Entity, EntityHashSet},
+    message::MessageWriter,
+    prelude::ReflectComponent,
+    query::Has,
+    reflect::ReflectResource,
+    resource::Resource,
+    schedule::IntoScheduleConfigs,
+    system::{Local, Query, Res},
+};
+use bevy_math::{Ray3d, Vec2, Vec3, Vec4, primitives::InfinitePlane3d};
+use bevy_picking::{
+    Pickable, PickingSystems,
+    backend::{HitData, PointerHits, ray::RayMap},
+};
+use bevy_reflect::{Reflect, prelude::ReflectDefault};
+use bevy_transform::components::GlobalTransform;
+
+use crate::{
+    components::computed::{ComputedClip, ComputedDiv, ComputedLayout},
+    geometry::{FLIP_Y, sd_rounded_box},
+    stack::UiStackMap,
+};
+
+/// Adds the picking backend of the nodes.
+pub struct MoonPickingPlugin;
+
+impl Plugin for MoonPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MoonPickingSettings>()
+            .add_systems(PreUpdate, ui_picking.in_set(PickingSystems::Backend));
+    }
+}
+
+/// Settings of the picking backend.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct MoonPickingSettings {
+    /// When set to `true`, only the cameras marked with [`MoonPickingCamera`] are considered.
+    pub require_markers: bool,
+}
+
+impl Default for MoonPickingSettings {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl MoonPickingSettings {
+    pub const DEFAULT: Self = Self {
+        require_markers: false,
+    };
+}
+
+/// Marks a camera which should be used by the picking backend,
+/// requires [`MoonPickingSettings::require_markers`] to be `true`.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct MoonPickingCamera;
+
+/// The position of a pointer over the node, updated by the picking backend.
+///
+/// It is opt-in, inserts it on the nodes which need it.
+/// When several pointers are over the node, the first one is kept.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct RelativePointerPosition {
+    /// The position relative to the top-left corner of the node, in layout units.
+    pub local: Option<Vec2>,
+
+    /// The normalized position, `[0, 0]` is the top-left corner and `[1, 1]` is the bottom-right corner.
+    pub uv: Option<Vec2>,
+
+    /// The distance from the origin of the ray to the hit point, in world units.
+    pub distance: Option<f32>,
+}
+
+impl RelativePointerPosition {
+    pub const NONE: Self = Self {
+        local: None,
+        uv: None,
+        distance: None,
+    };
+
+    /// Returns `true` if a pointer is over the node.
+    #[inline]
+    pub fn is_over(&self) -> bool {
+        self.local.is_some()
+    }
+}
+
+/// A hit of a ray on a node.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UiRayHit {
+    /// The distance from the origin of the ray to the hit point.
+    pub distance: f32,
+
+    /// The hit point in world space.
+    pub position: Vec3,
+
+    /// The normal of the node, facing the origin of the ray.
+    pub normal: Vec3,
+
+    /// The position relative to the top-left corner of the node, in layout units.
+    pub local: Vec2,
+
+    /// The normalized position, `[0, 0]` is the top-left corner and `[1, 1]` is the bottom-right corner.
+    pub uv: Vec2,
+}
+
+/// Casts the ray against the rounded quad of the node.
+///
+/// Returns `None` if the ray misses the node, or if the hit point is clipped out.
+pub fn ray_cast_div(
+    ray: Ray3d,
+    transform: &GlobalTransform,
+    computed_layout: &ComputedLayout,
+    corner_radii: Vec4,
+    computed_clip: Option<&ComputedClip>,
+) -> Option<UiRayHit> {
+    let size = computed_layout.size;
+
+    if computed_layout.is_empty() {
+        return None;
+    }
+
+    let normal = transform.back();
+    let plane = InfinitePlane3d::new(normal);
+    let distance = ray.intersect_plane(transform.translation(), plane)?;
+    let position = ray.get_point(distance);
+
+    // y-up, relative to the center of the node.
+    let point = transform
+        .affine()
+        .inverse()
+        .transform_point3(position)
+        .truncate();
+
+    if sd_rounded_box(point, size * 0.5, corner_radii) > 0.0 {
+        return None;
+    }
+
+    if computed_clip.is_some_and(|clip| !clip.contains(position)) {
+        return None;
+    }
+
+    let local = point * FLIP_Y + size * 0.5;
+
+    Some(UiRayHit {
+        distance,
+        position,
+        normal: if normal.dot(*ray.direction) > 0.0 {
+            -normal
+        } else {
+            normal
+        },
+        local,
+        uv: local / size,
+    })
+}
+
+pub fn ui_picking(
+    settings: Res<MoonPickingSettings>,
+    ray_map: Res<RayMap>,
+    ui_stack_map: Res<UiStackMap>,
+    camera_query: Query<(&Camera, Has<MoonPickingCamera>)>,
+    div_query: Query<(
+        &ComputedDiv,
+        &GlobalTransform,
+        &ComputedLayout,
+        &InheritedVisibility,
+        Option<&ComputedClip>,
+        Option<&Pickable>,
+    )>,
+    mut relative_query: Query<(Entity, &mut RelativePointerPosition)>,
+    mut pointer_hits_writer: MessageWriter<PointerHits>,
+    mut updated: Local<EntityHashSet>,
+) {
+    updated.clear();
+
+    for (&ray_id, &ray) in ray_map.iter() {
+        let Ok((camera, marked)) = camera_query.get(ray_id.camera) else {
+            continue;
+        };
+
+        if !camera.is_active || (settings.require_markers && !marked) {
+            continue;
+        }
+
+        let Some(ui_stack) = ui_stack_map.get(&ray_id.camera) else {
+            continue;
+        };
+
+        let mut picks = Vec::new();
+
+        // from top to bottom
+        for &entity in ui_stack.entities.iter().rev() {
+            let Ok((
+                div,
+                transform,
+                computed_layout,
+                inherited_visibility,
+                computed_clip,
+                pickable,
+            )) = div_query.get(entity)
+            else {
+                continue;
+            };
+
+            if !inherited_visibility.get() {
+                continue;
+            }
+
+            let corner_radii = Vec4::from_array(div.corner_radii.to_array());
+
+            let Some(hit) =
+                ray_cast_div(ray, transform, computed_layout, corner_radii, computed_clip)
+            else {
+                continue;
+            };
+
+            let blocks_lower = pickable.is_none_or(|pickable| pickable.should_block_lower);
+
+            // A node which is not hoverable is not reported, but it still blocks the lower nodes.
+            if pickable.is_some_and(|pickable| !pickable.is_hoverable) {
+                if blocks_lower {
+                    break;
+                }
+                continue;
+            }
+
+            if updated.insert(entity)
+                && let Ok((_, mut relative)) = relative_query.get_mut(entity)
+            {
+                relative.set_if_neq(RelativePointerPosition {
+                    local: Some(hit.local),
+                    uv: Some(hit.uv),
+                    distance: Some(hit.distance),
+                });
+            }
+
+            picks.push((
+                entity,
+                HitData::new(
+                    ray_id.camera,
+                    hit.distance,
+                    Some(hit.position),
+                    Some(hit.normal),
+                ),
+            ));
+
+            if blocks_lower {
+                break;
+            }
+        }
+
+        if picks.is_empty() {
+            continue;
+        }
+
+        // Draws on top of the other backends of the same camera.
+        let order = camera.order as f32 + 0.5;
+
+        pointer_hits_writer.write(PointerHits::new(ray_id.pointer, picks, order));
+    }
+
+    for (entity, mut relative) in &mut relative_query {
+        if !updated.contains(&entity) {
+            relative.set_if_neq(RelativePointerPosition::NONE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{message::Messages, system::RunSystemOnce, world::World};
+    use bevy_math::{Dir3, Quat};
+    use bevy_picking::{backend::ray::RayId, pointer::PointerId};
+    use bevy_transform::components::Transform;
+    use smallvec::SmallVec;
+
+    use super::*;
+    use crate::{components::computed::ClipRegion, stack::UiStack};
+
+    fn ray_at(x: f32, y: f32) -> Ray3d {
+        Ray3d::new(Vec3::new(x, y, 10.0), Dir3::NEG_Z)
+    }
+
+    #[test]
+    fn test_ray_cast_div() {
+        let transform = GlobalTransform::from(Transform::from_xyz(100.0, 0.0, 0.0));
+        let computed_layout = ComputedLayout {
+            size: Vec2::new(100.0, 50.0),
+            ..Default::default()
+        };
+
+        let hit = ray_cast_div(
+            ray_at(75.0, 0.0),
+            &transform,
+            &computed_layout,
+            Vec4::ZERO,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(hit.distance, 10.0);
+        assert_eq!(hit.local, Vec2::new(25.0, 25.0));
+        assert_eq!(hit.uv, Vec2::new(0.25, 0.5));
+        assert_eq!(hit.normal, Vec3::Z);
+
+        // outside
+        assert!(
+            ray_cast_div(
+                ray_at(0.0, 0.0),
+                &transform,
+                &computed_layout,
+                Vec4::ZERO,
+                None
+            )
+            .is_none()
+        );
+
+        // the top-left corner is rounded
+        let corner_radii = Vec4::new(20.0, 0.0, 0.0, 0.0);
+
+        assert!(
+            ray_cast_div(
+                ray_at(51.0, 24.0),
+                &transform,
+                &computed_layout,
+                corner_radii,
+                None
+            )
+            .is_none()
+        );
+        assert!(
+            ray_cast_div(
+                ray_at(149.0, 24.0),
+                &transform,
+                &computed_layout,
+                corner_radii,
+                None
+            )
+            .is_some()
+        );
+    }
+
+    #[test]
+    fn test_ray_cast_rotated_div() {
+        let transform = GlobalTransform::from(Transform::from_rotation(Quat::from_rotation_z(
+            core::f32::consts::FRAC_PI_2,
+        )));
+        let computed_layout = ComputedLayout {
+            size: Vec2::new(100.0, 20.0),
+            ..Default::default()
+        };
+
+        // The long side is vertical after the rotation.
+        assert!(
+            ray_cast_div(
+                ray_at(0.0, 40.0),
+                &transform,
+                &computed_layout,
+                Vec4::ZERO,
+                None
+            )
+            .is_some()
+        );
+        assert!(
+            ray_cast_div(
+                ray_at(40.0, 0.0),
+                &transform,
+                &computed_layout,
+                Vec4::ZERO,
+                None
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_ray_cast_clipped_div() {
+        let transform = GlobalTransform::IDENTITY;
+        let computed_layout = ComputedLayout {
+            size: Vec2::new(100.0, 100.0),
+            ..Default::default()
+        };
+        let computed_clip = ComputedClip::from(ClipRegion {
+            clip_from_world: transform.affine().inverse(),
+            center: Vec2::ZERO,
+            half_size: Vec2::new(10.0, 10.0),
+            corner_radii: Vec4::ZERO,
+        });
+
+        assert!(
+            ray_cast_div(
+                ray_at(5.0, 5.0),
+                &transform,
+                &computed_layout,
+                Vec4::ZERO,
+                Some(&computed_clip)
+            )
+            .is_some()
+        );
+        assert!(
+            ray_cast_div(
+                ray_at(20.0, 5.0),
+                &transform,
+                &computed_layout,
+                Vec4::ZERO,
+                Some(&computed_clip)
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_ui_picking_not_hoverable() {
+        let mut world = World::new();
+        world.init_resource::<MoonPickingSettings>();
+        world.init_resource::<Messages<PointerHits>>();
+
+        let camera = world.spawn(Camera::default()).id();
+
+        let mut spawn_node = |pickable: Option<Pickable>| {
+            let mut node = world.spawn((
+                ComputedDiv::default(),
+                GlobalTransform::IDENTITY,
+                ComputedLayout {
+                    size: Vec2::new(100.0, 100.0),
+                    ..Default::default()
+                },
+                InheritedVisibility::VISIBLE,
+            ));
+            if let Some(pickable) = pickable {
+                node.insert(pickable);
+            }
+            node.id()
+        };
+
+        let lower = spawn_node(None);
+        let overlay = spawn_node(Some(Pickable {
+            should_block_lower: true,
+            is_hoverable: false,
+        }));
+
+        let mut ui_stack_map = UiStackMap::default();
+        ui_stack_map.insert(
+            camera,
+            UiStack {
+                entities: SmallVec::from_slice(&[lower, overlay]),
+                ..Default::default()
+            },
+        );
+        world.insert_resource(ui_stack_map);
+
+        let mut ray_map = RayMap::default();
+        ray_map
+            .map
+            .insert(RayId::new(camera, PointerId::Mouse), ray_at(0.0, 0.0));
+        world.insert_resource(ray_map);
+
+        let picks = |world: &mut World| {
+            world.run_system_once(ui_picking).unwrap();
+
+            let mut messages = world.resource_mut::<Messages<PointerHits>>();
+            let picks = messages
+                .iter_current_update_messages()
+                .flat_map(|hits| hits.picks.iter().map(|(entity, hit)| (*entity, hit.depth)))
+                .collect::<Vec<_>>();
+            messages.clear();
+            picks
+        };
+
+        // The overlay is not reported, but it still blocks the lower node.
+        assert_eq!(picks(&mut world), []);
+
+        world.entity_mut(overlay).insert(Pickable {
+            should_block_lower: false,
+            is_hoverable: false,
+        });
+
+        // The depth is the distance of the hit.
+        assert_eq!(picks(&mut world), [(lower, 10.0)]);
+    }
+}
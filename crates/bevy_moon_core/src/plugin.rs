@@ -1,4 +1,5 @@
 use bevy_app::{AnimationSystems, Plugin, PostUpdate};
+use bevy_asset::AssetApp;
 use bevy_camera::{
     CameraUpdateSystems,
     visibility::{Visibility, VisibilityClass, add_visibility_class},
@@ -7,10 +8,16 @@ use bevy_ecs::schedule::IntoScheduleConfigs;
 use bevy_transform::TransformSystems;
 
 use crate::{
-    components::{div::Div, text},
-    layout::UiLayoutTree,
+    components::{computed, div::Div, interaction, layout_animation, scroll, text, transition},
+    dirty::{UiDirtyRoots, UiRecomputeCounter},
+    layout::{UiLayoutSettings, UiLayoutTree},
     stack::UiStackMap,
-    systems::{UiSystems, ui_layout_system, ui_stack_system, ui_target_info_system},
+    style_sheet::{self, StyleSheet, StyleSheetLoader, UiStyleSheets},
+    systems::{
+        UiSystems, mark_dirty_roots_system, place_ui_roots_system, ui_layout_system,
+        ui_stack_system, ui_target_info_system, update_clipping_system,
+    },
+    theme::MoonTheme,
 };
 
 pub struct MoonCorePlugin;
@@ -21,7 +28,15 @@ impl Plugin for MoonCorePlugin {
             .register_required_components::<Div, VisibilityClass>();
 
         app.init_resource::<UiStackMap>()
-            .init_resource::<UiLayoutTree>();
+            .init_resource::<UiLayoutTree>()
+            .init_resource::<UiLayoutSettings>()
+            .init_resource::<UiDirtyRoots>()
+            .init_resource::<UiRecomputeCounter>()
+            .init_resource::<MoonTheme>()
+            .init_resource::<UiStyleSheets>();
+
+        app.init_asset::<StyleSheet>()
+            .init_asset_loader::<StyleSheetLoader>();
 
         app.configure_sets(
             PostUpdate,
@@ -39,7 +54,11 @@ impl Plugin for MoonCorePlugin {
         app.add_systems(
             PostUpdate,
             (
-                (ui_stack_system, ui_target_info_system)
+                (
+                    mark_dirty_roots_system,
+                    ui_stack_system,
+                    ui_target_info_system,
+                )
                     .chain()
                     .in_set(UiSystems::Stack)
                     // These systems don't care about stack index
@@ -50,6 +69,54 @@ impl Plugin for MoonCorePlugin {
                     .before(TransformSystems::Propagate)
                     // Text and Text2D operate on disjoint sets of entities
                     .ambiguous_with(bevy_sprite::update_text2d_layout),
+                (place_ui_roots_system, update_clipping_system)
+                    .chain()
+                    .in_set(UiSystems::PostLayout)
+                    .after(TransformSystems::Propagate),
+            ),
+        );
+
+        // stylesheet, interaction, theme and transition components
+        app.add_systems(
+            PostUpdate,
+            (
+                style_sheet::apply_style_sheets,
+                interaction::update_interaction_states,
+                computed::update_computed_divs,
+                transition::update_transitions,
+            )
+                .chain()
+                .in_set(UiSystems::Prepare),
+        );
+
+        // animation component
+        #[cfg(feature = "animation")]
+        {
+            app.add_systems(
+                PostUpdate,
+                (
+                    crate::animation::restore_div_animations
+                        .in_set(UiSystems::Prepare)
+                        .before(computed::update_computed_divs),
+                    crate::animation::apply_div_animations
+                        .in_set(UiSystems::Prepare)
+                        .after(transition::update_transitions),
+                ),
+            );
+        }
+
+        // layout animation component
+        app.add_systems(
+            PostUpdate,
+            (
+                layout_animation::update_exits.in_set(UiSystems::Prepare),
+                layout_animation::restore_layout_animations
+                    .in_set(UiSystems::Layout)
+                    .before(ui_layout_system),
+                layout_animation::update_layout_animations
+                    .in_set(UiSystems::Layout)
+                    .after(ui_layout_system)
+                    .before(TransformSystems::Propagate),
             ),
         );
 
@@ -90,6 +157,18 @@ impl Plugin for MoonCorePlugin {
             );
         }
 
+        // scroll component
+        {
+            app.add_observer(scroll::scroll_into_view);
+
+            #[cfg(feature = "picking")]
+            app.add_observer(scroll::scroll_on_wheel)
+                .add_observer(scroll::scroll_on_drag);
+        }
+
+        #[cfg(feature = "picking")]
+        app.add_plugins(crate::picking::MoonPickingPlugin);
+
         app.world_mut()
             .register_component_hooks::<Div>()
             .on_add(add_visibility_class::<Div>);
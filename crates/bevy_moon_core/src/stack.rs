@@ -13,13 +13,35 @@ use smallvec::SmallVec;
 
 #[derive(Default, Clone, Debug)]
 pub struct UiStack {
+    /// The nodes which are visible in the camera.
     pub bitset: FixedBitSet,
+    /// The visible roots, from back to front.
     pub roots: SmallVec<[Entity; 8]>,
+    /// The nodes from back to front.
     pub entities: SmallVec<[Entity; 24]>,
-    pub ranges: SmallVec<[Range<usize>; 16]>,
+    /// The range of each root's subtree in `entities`, in the order of `roots`.
+    pub ranges: SmallVec<[Range<usize>; 8]>,
 }
 
 impl UiStack {
+    /// Returns `true` if the entity is rendered by the camera of this stack.
+    #[inline]
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.bitset.contains(entity.index_u32() as usize)
+    }
+
+    /// Iterates the entities from back to front, with their stack indexes in this camera.
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (usize, Entity)> + '_ {
+        self.entities.iter().copied().enumerate()
+    }
+
+    /// Returns the subtree of the root, from back to front.
+    pub fn subtree(&self, root: Entity) -> Option<&[Entity]> {
+        let index = self.roots.iter().position(|&entity| entity == root)?;
+        self.entities.get(self.ranges[index].clone())
+    }
+
     pub fn clear(&mut self) {
         self.bitset.clear();
         self.roots.clear();
@@ -60,7 +82,19 @@ impl ExtractResource for UiStackMap {
 }
 
 impl UiStackMap {
+    /// Returns the subtree of the root in the stack of the camera, from back to front.
+    pub fn subtree(&self, camera_entity: Entity, root: Entity) -> Option<&[Entity]> {
+        self.get(&camera_entity)?.subtree(root)
+    }
+
     pub fn clear(&mut self) {
         self.as_mut().clear();
     }
+
+    /// Iterates the cameras which render the entity.
+    pub fn cameras_of(&self, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.iter()
+            .filter(move |(_, ui_stack)| ui_stack.contains(entity))
+            .map(|(&camera_entity, _)| camera_entity)
+    }
 }
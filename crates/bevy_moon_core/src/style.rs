@@ -1,15 +1,51 @@
 use std::fmt::Debug;
 
-use bevy_color::Color;
-use bevy_derive::Deref;
-use bevy_math::Vec2;
+use bevy_color::{Color, Hsla, LinearRgba, Mix, Oklaba, Oklcha, Srgba};
+use bevy_derive::{Deref, DerefMut};
+use bevy_math::{FloatExt, Vec2};
 use bevy_reflect::{Reflect, prelude::ReflectDefault};
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use smallvec::SmallVec;
+use taffy::Style;
+
+mod remote;
+
+/// The layout style of a node.
+///
+/// Its fields are reflected through remote wrappers of the taffy types, so they can be edited one
+/// by one, such as in an inspector, while the lengths and the grid tracks are reflected as opaque
+/// values. It is serialized by serde with the `serialize` feature, so a
+/// [`Div`](crate::prelude::Div) round-trips through scenes.
+#[derive(Clone, Debug, Default, PartialEq, Deref, DerefMut, Reflect)]
+#[reflect(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent),
+    reflect(Serialize, Deserialize)
+)]
+pub struct LayoutStyle(#[reflect(remote = remote::StyleRemote)] pub Style);
+
+unsafe impl Send for LayoutStyle {}
+unsafe impl Sync for LayoutStyle {}
+
+impl LayoutStyle {
+    pub const DEFAULT: Self = Self(Style::DEFAULT);
+}
+
+impl From<Style> for LayoutStyle {
+    fn from(value: Style) -> Self {
+        Self(value)
+    }
+}
 
 /// Rounds the corners of an element's outer border edge.
 ///
 /// <https://developer.mozilla.org/ocs/Web/CSS/Reference/Properties/border-radius>
 #[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
 #[reflect(Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Corners<T>
 where
     T: Clone + Copy + Debug + Default + PartialEq + Reflect,
@@ -87,19 +123,124 @@ where
 impl Corners<f32> {
     pub const DEFAULT: Self = Self::all(0.0);
 
-    // should resolve radius based on its container size
-    // const fn resolve_single(radius: f32, min_length: f32) -> f32 {
-    //     radius.clamp(0.0, 0.5 * min_length)
-    // }
+    /// Fully rounds the corners, such as a pill or a circle.
+    pub const FULL: Self = Self::all(f32::MAX);
+
+    /// Resolves the radii against the size of the box, clamps them to half of its shortest side.
+    ///
+    /// Returns `[top_left, top_right, bottom_right, bottom_left]`.
+    #[inline]
+    pub fn resolve(self, size: Vec2) -> [f32; 4] {
+        let max_radius = 0.5 * size.min_element().max(0.0);
+        self.to_array().map(|radius| radius.clamp(0.0, max_radius))
+    }
 }
 
+/// The values of the four sides of an element.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
 #[reflect(Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edges<T>
+where
+    T: Clone + Copy + Debug + Default + PartialEq + Reflect,
+{
+    pub top: T,
+    pub right: T,
+    pub bottom: T,
+    pub left: T,
+}
+
+impl<T> Edges<T>
+where
+    T: Clone + Copy + Debug + Default + PartialEq + Reflect,
+{
+    #[inline]
+    pub const fn all(value: T) -> Self {
+        Self {
+            top: value,
+            right: value,
+            bottom: value,
+            left: value,
+        }
+    }
+
+    #[inline]
+    pub const fn top(mut self, value: T) -> Self {
+        self.top = value;
+        self
+    }
+
+    #[inline]
+    pub const fn right(mut self, value: T) -> Self {
+        self.right = value;
+        self
+    }
+
+    #[inline]
+    pub const fn bottom(mut self, value: T) -> Self {
+        self.bottom = value;
+        self
+    }
+
+    #[inline]
+    pub const fn left(mut self, value: T) -> Self {
+        self.left = value;
+        self
+    }
+
+    /// Converts `self` to `[top, right, bottom, left]`.
+    #[inline]
+    pub const fn to_array(self) -> [T; 4] {
+        [self.top, self.right, self.bottom, self.left]
+    }
+}
+
+/// The style of the border lines.
+///
+/// <https://developer.mozilla.org/docs/Web/CSS/Reference/Properties/border-style>
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum BorderStyle {
+    #[default]
+    Solid,
+    /// Dashes along the perimeter, a zero `length` or `gap` is three times the border width.
+    Dashed { length: f32, gap: f32 },
+    /// Round dots as wide as the border, a zero `gap` is the border width.
+    Dotted { gap: f32 },
+    /// Two lines, each a third of the border width.
+    Double,
+}
+
+impl BorderStyle {
+    pub const DASHED: Self = Self::Dashed {
+        length: 0.0,
+        gap: 0.0,
+    };
+
+    pub const DOTTED: Self = Self::Dotted { gap: 0.0 };
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub struct BoxShadow {
     pub color: Color,
     pub offset: Vec2,
     pub blur_radius: f32,
     pub spread_radius: f32,
+    /// Draws the shadow inside the padding box instead of around the border box, where a positive
+    /// spread grows the shadow inwards.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub inset: bool,
 }
 
 /// Sets the box shadow of the element.
@@ -111,6 +252,7 @@ impl BoxShadow {
         offset: Vec2::new(0.0, 1.0),
         blur_radius: 0.0,
         spread_radius: 0.0,
+        inset: false,
     }];
 
     pub const XS: [Self; 1] = [Self {
@@ -118,6 +260,7 @@ impl BoxShadow {
         offset: Vec2::new(0.0, 1.0),
         blur_radius: 2.0,
         spread_radius: 0.0,
+        inset: false,
     }];
 
     pub const SM: [Self; 2] = [
@@ -126,12 +269,14 @@ impl BoxShadow {
             offset: Vec2::new(0.0, 1.0),
             blur_radius: 3.0,
             spread_radius: 0.0,
+            inset: false,
         },
         Self {
             color: Color::srgba(0.0, 0.0, 0.0, 0.1),
             offset: Vec2::new(0.0, 1.0),
             blur_radius: 2.0,
             spread_radius: -1.0,
+            inset: false,
         },
     ];
 
@@ -141,12 +286,14 @@ impl BoxShadow {
             offset: Vec2::new(0.0, 4.0),
             blur_radius: 6.0,
             spread_radius: -1.0,
+            inset: false,
         },
         Self {
             color: Color::srgba(0.0, 0.0, 0.0, 0.1),
             offset: Vec2::new(0.0, 2.0),
             blur_radius: 4.0,
             spread_radius: -2.0,
+            inset: false,
         },
     ];
 
@@ -156,12 +303,14 @@ impl BoxShadow {
             offset: Vec2::new(0.0, 10.0),
             blur_radius: 15.0,
             spread_radius: -3.0,
+            inset: false,
         },
         Self {
             color: Color::srgba(0.0, 0.0, 0.0, 0.1),
             offset: Vec2::new(0.0, 4.0),
             blur_radius: 6.0,
             spread_radius: -4.0,
+            inset: false,
         },
     ];
 
@@ -171,12 +320,14 @@ impl BoxShadow {
             offset: Vec2::new(0.0, 20.0),
             blur_radius: 25.0,
             spread_radius: -5.0,
+            inset: false,
         },
         Self {
             color: Color::srgba(0.0, 0.0, 0.0, 0.1),
             offset: Vec2::new(0.0, 8.0),
             blur_radius: 10.0,
             spread_radius: -6.0,
+            inset: false,
         },
     ];
 
@@ -185,7 +336,333 @@ impl BoxShadow {
         offset: Vec2::new(0.0, 25.0),
         blur_radius: 50.0,
         spread_radius: -12.0,
+        inset: false,
     }];
+
+    pub const INSET_XS2: [Self; 1] = [Self {
+        color: Color::srgba(0.0, 0.0, 0.0, 0.05),
+        offset: Vec2::new(0.0, 1.0),
+        blur_radius: 0.0,
+        spread_radius: 0.0,
+        inset: true,
+    }];
+
+    pub const INSET_XS: [Self; 1] = [Self {
+        color: Color::srgba(0.0, 0.0, 0.0, 0.05),
+        offset: Vec2::new(0.0, 1.0),
+        blur_radius: 1.0,
+        spread_radius: 0.0,
+        inset: true,
+    }];
+
+    pub const INSET_SM: [Self; 1] = [Self {
+        color: Color::srgba(0.0, 0.0, 0.0, 0.05),
+        offset: Vec2::new(0.0, 2.0),
+        blur_radius: 4.0,
+        spread_radius: 0.0,
+        inset: true,
+    }];
+}
+
+/// A line drawn around the border box of a node, which does not take up space in the layout.
+///
+/// It follows the rounded corners, and a negative offset draws it inside the border box. It is
+/// drawn above the node and its content, the images, the texts and the descendants.
+///
+/// <https://developer.mozilla.org/docs/Web/CSS/Reference/Properties/outline>
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Outline {
+    pub width: f32,
+    pub color: Color,
+    /// The space between the outline and the border box.
+    pub offset: f32,
+    /// The style of the line, which is required.
+    ///
+    /// Unlike CSS, where an outline without a style is not drawn, [`Outline::new`] draws a
+    /// [`BorderStyle::Solid`] line.
+    pub style: BorderStyle,
+}
+
+impl Default for Outline {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Outline {
+    pub const DEFAULT: Self = Self::new(1.0, Color::BLACK);
+
+    #[inline]
+    pub const fn new(width: f32, color: Color) -> Self {
+        Self {
+            width,
+            color,
+            offset: 0.0,
+            style: BorderStyle::Solid,
+        }
+    }
+
+    #[inline]
+    pub const fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    #[inline]
+    pub const fn with_style(mut self, style: BorderStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// A color space in which the colors are interpolated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Clone, Default, Debug, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum ColorSpace {
+    Srgba,
+    LinearRgba,
+    Hsla,
+    #[default]
+    Oklaba,
+    Oklcha,
+}
+
+impl ColorSpace {
+    /// Mixes the colors in this color space.
+    pub fn mix(self, from: Color, to: Color, factor: f32) -> Color {
+        match self {
+            Self::Srgba => Srgba::from(from).mix(&to.into(), factor).into(),
+            Self::LinearRgba => LinearRgba::from(from).mix(&to.into(), factor).into(),
+            Self::Hsla => Hsla::from(from).mix(&to.into(), factor).into(),
+            Self::Oklaba => Oklaba::from(from).mix(&to.into(), factor).into(),
+            Self::Oklcha => Oklcha::from(from).mix(&to.into(), factor).into(),
+        }
+    }
+}
+
+/// A color stop of a [`Gradient`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ColorStop {
+    pub color: Color,
+    /// The position along the gradient line, from `0.0` to `1.0`.
+    ///
+    /// When it is `None`, the stop is placed halfway between its neighbours.
+    pub position: Option<f32>,
+}
+
+impl ColorStop {
+    #[inline]
+    pub const fn new(color: Color, position: f32) -> Self {
+        Self {
+            color,
+            position: Some(position),
+        }
+    }
+
+    #[inline]
+    pub const fn auto(color: Color) -> Self {
+        Self {
+            color,
+            position: None,
+        }
+    }
+}
+
+impl From<Color> for ColorStop {
+    fn from(color: Color) -> Self {
+        Self::auto(color)
+    }
+}
+
+impl From<(Color, f32)> for ColorStop {
+    fn from((color, position): (Color, f32)) -> Self {
+        Self::new(color, position)
+    }
+}
+
+/// The shape of a [`Gradient`].
+///
+/// The angles are in radians, clockwise from the top, the centers are the fractions of the box
+/// from its top-left corner.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum GradientKind {
+    /// <https://developer.mozilla.org/docs/Web/CSS/Reference/Values/gradient/linear-gradient>
+    Linear { angle: f32 },
+    /// A circle which reaches the farthest corner of the box.
+    ///
+    /// <https://developer.mozilla.org/docs/Web/CSS/Reference/Values/gradient/radial-gradient>
+    Radial { center: Vec2 },
+    /// <https://developer.mozilla.org/docs/Web/CSS/Reference/Values/gradient/conic-gradient>
+    Conic { center: Vec2, start_angle: f32 },
+}
+
+/// A gradient of colors, for the background or the border of an element.
+///
+/// ```ignore
+/// div().background_gradient(Gradient::linear(
+///     FRAC_PI_2,
+///     [Color::WHITE.into(), (Color::BLACK, 0.75).into()],
+/// ))
+/// ```
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    /// The color stops, more than [`Gradient::MAX_STOPS`] stops are resampled when rendered.
+    pub stops: SmallVec<[ColorStop; 4]>,
+    /// The color space in which the colors are interpolated.
+    pub color_space: ColorSpace,
+}
+
+impl Gradient {
+    /// The maximum count of the stops which are rendered, a gradient with more stops is
+    /// resampled into this count of evenly spaced stops.
+    pub const MAX_STOPS: usize = 4;
+
+    /// Warns when the gradient has more than [`Gradient::MAX_STOPS`] stops.
+    pub fn new(kind: GradientKind, stops: impl IntoIterator<Item = ColorStop>) -> Self {
+        let stops = stops.into_iter().collect::<SmallVec<_>>();
+
+        if stops.len() > Self::MAX_STOPS {
+            tracing::warn!(
+                "a gradient has {} stops, it is resampled into {} stops",
+                stops.len(),
+                Self::MAX_STOPS
+            );
+        }
+
+        Self {
+            kind,
+            stops,
+            color_space: ColorSpace::default(),
+        }
+    }
+
+    /// A linear gradient, an angle of `0.0` goes from the bottom to the top.
+    pub fn linear(angle: f32, stops: impl IntoIterator<Item = ColorStop>) -> Self {
+        Self::new(GradientKind::Linear { angle }, stops)
+    }
+
+    pub fn radial(center: Vec2, stops: impl IntoIterator<Item = ColorStop>) -> Self {
+        Self::new(GradientKind::Radial { center }, stops)
+    }
+
+    pub fn conic(
+        center: Vec2,
+        start_angle: f32,
+        stops: impl IntoIterator<Item = ColorStop>,
+    ) -> Self {
+        Self::new(
+            GradientKind::Conic {
+                center,
+                start_angle,
+            },
+            stops,
+        )
+    }
+
+    #[inline]
+    pub const fn with_color_space(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
+    /// Returns the colors and the positions of the stops.
+    ///
+    /// As in CSS, the first and the last stops default to `0.0` and `1.0`, a position is never
+    /// less than the ones before it, and the stops without a position are evenly spaced.
+    pub fn resolved_stops(&self) -> SmallVec<[(Color, f32); 4]> {
+        let last = self.stops.len().saturating_sub(1);
+        let mut positions = self
+            .stops
+            .iter()
+            .enumerate()
+            .map(|(index, stop)| match (index, stop.position) {
+                (_, Some(position)) => Some(position),
+                (0, None) => Some(0.0),
+                (index, None) if index == last => Some(1.0),
+                _ => None,
+            })
+            .collect::<SmallVec<[_; 4]>>();
+
+        let mut max_position = f32::MIN;
+        for position in positions.iter_mut().flatten() {
+            max_position = max_position.max(*position);
+            *position = max_position;
+        }
+
+        let mut start = 0;
+        for index in 1..positions.len() {
+            let Some(end_position) = positions[index] else {
+                continue;
+            };
+            let start_position = positions[start].unwrap_or_default();
+            let count = (index - start) as f32;
+            for (step, position) in positions[start + 1..index].iter_mut().enumerate() {
+                *position = Some(start_position.lerp(end_position, (step + 1) as f32 / count));
+            }
+            start = index;
+        }
+
+        self.stops
+            .iter()
+            .zip(positions)
+            .map(|(stop, position)| (stop.color, position.unwrap_or_default()))
+            .collect()
+    }
+
+    /// Returns the color at the position along the gradient line.
+    pub fn sample(&self, position: f32) -> Color {
+        let stops = self.resolved_stops();
+
+        let Some(&(first, first_position)) = stops.first() else {
+            return Color::NONE;
+        };
+        if position <= first_position {
+            return first;
+        }
+
+        for window in stops.windows(2) {
+            let [(start, start_position), (end, end_position)] = [window[0], window[1]];
+            if position <= end_position {
+                let factor = match end_position > start_position {
+                    true => (position - start_position) / (end_position - start_position),
+                    false => 1.0,
+                };
+                return self.color_space.mix(start, end, factor);
+            }
+        }
+
+        stops.last().map_or(first, |&(color, _)| color)
+    }
 }
 
 /// How an image should fit within its container.
@@ -202,6 +679,12 @@ impl BoxShadow {
 ///
 /// <https://developer.mozilla.org/docs/Web/CSS/Reference/Properties/object-fit>
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub enum ObjectFit {
     /// Stretch to fill the container (ignores aspect ratio).
     Fill,
@@ -220,6 +703,12 @@ pub enum ObjectFit {
 ///
 /// <https://developer.mozilla.org/docs/Web/CSS/Reference/Properties/object-position>
 #[derive(Debug, Clone, Copy, PartialEq, Default, Reflect, Deref)]
+#[reflect(Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub struct ObjectPosition(Vec2);
 
 impl ObjectPosition {
@@ -245,3 +734,88 @@ impl From<ObjectPosition> for Vec2 {
         value.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradient_stops() {
+        let gradient = Gradient::linear(
+            0.0,
+            [
+                Color::WHITE.into(),
+                Color::BLACK.into(),
+                (Color::WHITE, 0.8).into(),
+                (Color::BLACK, 0.4).into(),
+                Color::WHITE.into(),
+            ],
+        );
+
+        let positions = gradient
+            .resolved_stops()
+            .into_iter()
+            .map(|(_, position)| position)
+            .collect::<Vec<_>>();
+        // The missing positions are spaced evenly, and a position never goes backwards.
+        assert_eq!(positions, [0.0, 0.4, 0.8, 0.8, 1.0]);
+
+        let gradient = Gradient::linear(0.0, [Color::BLACK.into(), Color::WHITE.into()])
+            .with_color_space(ColorSpace::LinearRgba);
+
+        assert_eq!(gradient.sample(-1.0), Color::BLACK);
+        assert_eq!(gradient.sample(2.0), Color::WHITE);
+        assert_eq!(
+            gradient.sample(0.5).to_linear(),
+            LinearRgba::rgb(0.5, 0.5, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_layout_style_reflect_fields() {
+        use bevy_reflect::{GetPath, PartialReflect};
+        use taffy::{AlignItems, Dimension, Display, FlexDirection};
+
+        use super::remote::{
+            DimensionRemote, DisplayRemote, FlexDirectionRemote, OptionAlignItemsRemote,
+        };
+
+        let mut style = LayoutStyle::DEFAULT;
+
+        // The fields are reflected as the remote wrappers of the taffy types.
+        style
+            .path_mut::<FlexDirectionRemote>(".0.flex_direction")
+            .unwrap()
+            .0 = FlexDirection::Column;
+        style
+            .path_mut::<DimensionRemote>(".0.size.width")
+            .unwrap()
+            .0 = Dimension::length(120.0);
+        style
+            .path_mut::<OptionAlignItemsRemote>(".0.align_items")
+            .unwrap()
+            .0 = Some(AlignItems::Center);
+
+        assert_eq!(style.flex_direction, FlexDirection::Column);
+        assert_eq!(style.size.width, Dimension::length(120.0));
+        assert_eq!(style.align_items, Some(AlignItems::Center));
+
+        // A dynamic value of a field is applied, as an inspector does.
+        let mut display = style.reflect_path(".0.display").unwrap().to_dynamic();
+        display.apply(&DisplayRemote(Display::Grid));
+        style
+            .reflect_path_mut(".0.display")
+            .unwrap()
+            .apply(display.as_ref());
+
+        assert_eq!(style.display, Display::Grid);
+        assert_eq!(
+            style
+                .reflect_clone()
+                .unwrap()
+                .take::<LayoutStyle>()
+                .unwrap(),
+            style
+        );
+    }
+}
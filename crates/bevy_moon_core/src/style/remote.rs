@@ -0,0 +1,339 @@
+//! The reflection of the fields of [`taffy::Style`], see [`LayoutStyle`](super::LayoutStyle).
+//!
+//! The enums and the geometry types are reflected field by field, the lengths and the grid
+//! tracks are reflected as opaque values.
+
+use bevy_reflect::reflect_remote;
+use taffy::{
+    AlignContent, AlignItems, BoxSizing, Dimension, Display, FlexDirection, FlexWrap, GridAutoFlow,
+    GridPlacement, GridTemplateArea, GridTemplateComponent, LengthPercentage, LengthPercentageAuto,
+    Line, Overflow, Point, Position, Rect, Size, Style, TextAlign, TrackSizingFunction,
+};
+
+#[reflect_remote(Style)]
+#[derive(Clone, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct StyleRemote {
+    #[reflect(ignore)]
+    pub dummy: core::marker::PhantomData<String>,
+    #[reflect(remote = DisplayRemote)]
+    pub display: Display,
+    pub item_is_table: bool,
+    pub item_is_replaced: bool,
+    #[reflect(remote = BoxSizingRemote)]
+    pub box_sizing: BoxSizing,
+
+    #[reflect(remote = OverflowPointRemote)]
+    pub overflow: Point<Overflow>,
+    pub scrollbar_width: f32,
+
+    #[reflect(remote = PositionRemote)]
+    pub position: Position,
+    #[reflect(remote = LengthPercentageAutoRectRemote)]
+    pub inset: Rect<LengthPercentageAuto>,
+
+    #[reflect(remote = DimensionSizeRemote)]
+    pub size: Size<Dimension>,
+    #[reflect(remote = DimensionSizeRemote)]
+    pub min_size: Size<Dimension>,
+    #[reflect(remote = DimensionSizeRemote)]
+    pub max_size: Size<Dimension>,
+    pub aspect_ratio: Option<f32>,
+
+    #[reflect(remote = LengthPercentageAutoRectRemote)]
+    pub margin: Rect<LengthPercentageAuto>,
+    #[reflect(remote = LengthPercentageRectRemote)]
+    pub padding: Rect<LengthPercentage>,
+    #[reflect(remote = LengthPercentageRectRemote)]
+    pub border: Rect<LengthPercentage>,
+
+    #[reflect(remote = OptionAlignItemsRemote)]
+    pub align_items: Option<AlignItems>,
+    #[reflect(remote = OptionAlignItemsRemote)]
+    pub align_self: Option<AlignItems>,
+    #[reflect(remote = OptionAlignItemsRemote)]
+    pub justify_items: Option<AlignItems>,
+    #[reflect(remote = OptionAlignItemsRemote)]
+    pub justify_self: Option<AlignItems>,
+    #[reflect(remote = OptionAlignContentRemote)]
+    pub align_content: Option<AlignContent>,
+    #[reflect(remote = OptionAlignContentRemote)]
+    pub justify_content: Option<AlignContent>,
+    #[reflect(remote = LengthPercentageSizeRemote)]
+    pub gap: Size<LengthPercentage>,
+
+    #[reflect(remote = TextAlignRemote)]
+    pub text_align: TextAlign,
+
+    #[reflect(remote = FlexDirectionRemote)]
+    pub flex_direction: FlexDirection,
+    #[reflect(remote = FlexWrapRemote)]
+    pub flex_wrap: FlexWrap,
+
+    #[reflect(remote = DimensionRemote)]
+    pub flex_basis: Dimension,
+    pub flex_grow: f32,
+    pub flex_shrink: f32,
+
+    #[reflect(remote = GridTemplateComponentsRemote)]
+    pub grid_template_rows: Vec<GridTemplateComponent<String>>,
+    #[reflect(remote = GridTemplateComponentsRemote)]
+    pub grid_template_columns: Vec<GridTemplateComponent<String>>,
+    #[reflect(remote = TrackSizingFunctionsRemote)]
+    pub grid_auto_rows: Vec<TrackSizingFunction>,
+    #[reflect(remote = TrackSizingFunctionsRemote)]
+    pub grid_auto_columns: Vec<TrackSizingFunction>,
+    #[reflect(remote = GridAutoFlowRemote)]
+    pub grid_auto_flow: GridAutoFlow,
+
+    #[reflect(remote = GridTemplateAreasRemote)]
+    pub grid_template_areas: Vec<GridTemplateArea<String>>,
+    pub grid_template_column_names: Vec<Vec<String>>,
+    pub grid_template_row_names: Vec<Vec<String>>,
+
+    #[reflect(remote = GridPlacementLineRemote)]
+    pub grid_row: Line<GridPlacement<String>>,
+    #[reflect(remote = GridPlacementLineRemote)]
+    pub grid_column: Line<GridPlacement<String>>,
+}
+
+#[reflect_remote(Display)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum DisplayRemote {
+    Block,
+    Flex,
+    Grid,
+    None,
+}
+
+#[reflect_remote(BoxSizing)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum BoxSizingRemote {
+    BorderBox,
+    ContentBox,
+}
+
+#[reflect_remote(Overflow)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum OverflowRemote {
+    Visible,
+    Clip,
+    Hidden,
+    Scroll,
+}
+
+#[reflect_remote(Point<Overflow>)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct OverflowPointRemote {
+    #[reflect(remote = OverflowRemote)]
+    pub x: Overflow,
+    #[reflect(remote = OverflowRemote)]
+    pub y: Overflow,
+}
+
+#[reflect_remote(Position)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum PositionRemote {
+    Relative,
+    Absolute,
+}
+
+#[reflect_remote(AlignItems)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum AlignItemsRemote {
+    Start,
+    End,
+    FlexStart,
+    FlexEnd,
+    Center,
+    Baseline,
+    Stretch,
+}
+
+#[reflect_remote(Option<AlignItems>)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum OptionAlignItemsRemote {
+    None,
+    Some(#[reflect(remote = AlignItemsRemote)] AlignItems),
+}
+
+#[reflect_remote(AlignContent)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum AlignContentRemote {
+    Start,
+    End,
+    FlexStart,
+    FlexEnd,
+    Center,
+    Stretch,
+    SpaceBetween,
+    SpaceEvenly,
+    SpaceAround,
+}
+
+#[reflect_remote(Option<AlignContent>)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum OptionAlignContentRemote {
+    None,
+    Some(#[reflect(remote = AlignContentRemote)] AlignContent),
+}
+
+#[reflect_remote(TextAlign)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum TextAlignRemote {
+    Auto,
+    LegacyLeft,
+    LegacyRight,
+    LegacyCenter,
+}
+
+#[reflect_remote(FlexDirection)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum FlexDirectionRemote {
+    Row,
+    Column,
+    RowReverse,
+    ColumnReverse,
+}
+
+#[reflect_remote(FlexWrap)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum FlexWrapRemote {
+    NoWrap,
+    Wrap,
+    WrapReverse,
+}
+
+#[reflect_remote(GridAutoFlow)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum GridAutoFlowRemote {
+    Row,
+    Column,
+    RowDense,
+    ColumnDense,
+}
+
+#[reflect_remote(Dimension)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(opaque)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct DimensionRemote;
+
+#[reflect_remote(LengthPercentage)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(opaque)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct LengthPercentageRemote;
+
+#[reflect_remote(LengthPercentageAuto)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(opaque)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct LengthPercentageAutoRemote;
+
+#[reflect_remote(Size<Dimension>)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct DimensionSizeRemote {
+    #[reflect(remote = DimensionRemote)]
+    pub width: Dimension,
+    #[reflect(remote = DimensionRemote)]
+    pub height: Dimension,
+}
+
+#[reflect_remote(Size<LengthPercentage>)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct LengthPercentageSizeRemote {
+    #[reflect(remote = LengthPercentageRemote)]
+    pub width: LengthPercentage,
+    #[reflect(remote = LengthPercentageRemote)]
+    pub height: LengthPercentage,
+}
+
+#[reflect_remote(Rect<LengthPercentage>)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct LengthPercentageRectRemote {
+    #[reflect(remote = LengthPercentageRemote)]
+    pub left: LengthPercentage,
+    #[reflect(remote = LengthPercentageRemote)]
+    pub right: LengthPercentage,
+    #[reflect(remote = LengthPercentageRemote)]
+    pub top: LengthPercentage,
+    #[reflect(remote = LengthPercentageRemote)]
+    pub bottom: LengthPercentage,
+}
+
+#[reflect_remote(Rect<LengthPercentageAuto>)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct LengthPercentageAutoRectRemote {
+    #[reflect(remote = LengthPercentageAutoRemote)]
+    pub left: LengthPercentageAuto,
+    #[reflect(remote = LengthPercentageAutoRemote)]
+    pub right: LengthPercentageAuto,
+    #[reflect(remote = LengthPercentageAutoRemote)]
+    pub top: LengthPercentageAuto,
+    #[reflect(remote = LengthPercentageAutoRemote)]
+    pub bottom: LengthPercentageAuto,
+}
+
+#[reflect_remote(Vec<GridTemplateComponent<String>>)]
+#[derive(Clone, Debug, PartialEq)]
+#[reflect(opaque)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct GridTemplateComponentsRemote;
+
+#[reflect_remote(Vec<TrackSizingFunction>)]
+#[derive(Clone, Debug, PartialEq)]
+#[reflect(opaque)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct TrackSizingFunctionsRemote;
+
+#[reflect_remote(Vec<GridTemplateArea<String>>)]
+#[derive(Clone, Debug, PartialEq)]
+#[reflect(opaque)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct GridTemplateAreasRemote;
+
+#[reflect_remote(Line<GridPlacement<String>>)]
+#[derive(Clone, Debug, PartialEq)]
+#[reflect(opaque)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct GridPlacementLineRemote;
+
+// The lengths hold a tagged pointer for the `calc` values, which are not used, see `LayoutStyle`.
+unsafe impl Send for StyleRemote {}
+unsafe impl Sync for StyleRemote {}
+unsafe impl Send for DimensionRemote {}
+unsafe impl Sync for DimensionRemote {}
+unsafe impl Send for LengthPercentageRemote {}
+unsafe impl Sync for LengthPercentageRemote {}
+unsafe impl Send for LengthPercentageAutoRemote {}
+unsafe impl Sync for LengthPercentageAutoRemote {}
+unsafe impl Send for DimensionSizeRemote {}
+unsafe impl Sync for DimensionSizeRemote {}
+unsafe impl Send for LengthPercentageSizeRemote {}
+unsafe impl Sync for LengthPercentageSizeRemote {}
+unsafe impl Send for LengthPercentageRectRemote {}
+unsafe impl Sync for LengthPercentageRectRemote {}
+unsafe impl Send for LengthPercentageAutoRectRemote {}
+unsafe impl Sync for LengthPercentageAutoRectRemote {}
+unsafe impl Send for GridTemplateComponentsRemote {}
+unsafe impl Sync for GridTemplateComponentsRemote {}
+unsafe impl Send for TrackSizingFunctionsRemote {}
+unsafe impl Sync for TrackSizingFunctionsRemote {}
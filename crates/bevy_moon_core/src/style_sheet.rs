@@ -0,0 +1,619 @@
+//! The stylesheets, which style the nodes by their classes like a web page.
+//!
+//! ```css
+//! /* assets/ui/main.moon.css */
+//! .card { flex flex-col p-4 gap-2 bg-white rounded-lg shadow-md }
+//! .card:hover { bg-slate-50 shadow-lg }
+//! .list .item { px-3 py-2 border-b border-slate-200 }
+//! text { px-1 }
+//! img.avatar { size-8 rounded-full }
+//! ```
+//!
+//! ```ignore
+//! commands.insert_resource(UiStyleSheets(vec![asset_server.load("ui/main.moon.css")]));
+//! commands.spawn((div(), Class::new("card")));
+//! ```
+//!
+//! The declarations of a rule are the Tailwind classes of [`Div::class`]. A selector is a list of
+//! compound selectors separated by whitespace, which match the node and its ancestors. A compound
+//! selector is made of an optional type, `div`, `text`, `img` or `*`, of classes, and of an
+//! optional state, `:hover`, `:focus`, `:active` or `:disabled`, which selects a style variant of
+//! the node, see [`Div::hover`].
+//!
+//! Only the nodes with a [`Class`] are styled. The matching rules are applied by specificity, then
+//! by order, on top of the [`Div`] of the node when its
+//! [`ComputedDiv`](crate::prelude::ComputedDiv) is resolved, so the [`Div`] is left as the user
+//! has set it, and is displayed again when the [`Class`] is removed. The nodes
+//! are restyled when the stylesheets are changed, such as when a file is edited with the
+//! `file_watcher` feature of `bevy_asset`, and when the classes or the parents of the nodes or of
+//! their ancestors are changed. A state rule replaces the variant of the same state set by the
+//! builders.
+
+use bevy_asset::{Asset, AssetEvent, AssetLoader, Assets, Handle, LoadContext, io::Reader};
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
+    component::Component,
+    entity::{Entity, EntityHashSet},
+    hierarchy::{ChildOf, Children},
+    lifecycle::RemovedComponents,
+    message::MessageReader,
+    prelude::{ReflectComponent, ReflectResource},
+    query::{Changed, Has, Or, With, Without},
+    resource::Resource,
+    system::{Commands, Query, Res},
+};
+use bevy_reflect::{Reflect, TypePath, prelude::ReflectDefault};
+use smallvec::SmallVec;
+use thiserror::Error;
+
+use crate::components::{div::Div, image::Image, text::Text};
+
+/// The classes of a node, which select the rules of the [`UiStyleSheets`].
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct Class(pub Vec<String>);
+
+impl Class {
+    /// Creates the classes from a whitespace-separated list.
+    pub fn new(classes: &str) -> Self {
+        Self(classes.split_whitespace().map(String::from).collect())
+    }
+
+    pub fn contains(&self, class: &str) -> bool {
+        self.0.iter().any(|other| other == class)
+    }
+}
+
+/// The stylesheets which are applied to the nodes, the later ones win.
+#[derive(Resource, Clone, Debug, Default, Reflect)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct UiStyleSheets(pub Vec<Handle<StyleSheet>>);
+
+/// The declarations of the rules which match a node, see [`apply_style_sheets`].
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub(crate) struct MatchedRules {
+    /// The declarations of the rules without a state, by specificity then by order.
+    declarations: String,
+
+    /// The declarations of the state rules, in the order of the states.
+    variants: SmallVec<[(State, String); 1]>,
+}
+
+impl MatchedRules {
+    /// Applies the rules on the node, the state rules replace the variants of the same states.
+    pub(crate) fn apply(&self, div: &mut Div) {
+        div.apply_classes(&self.declarations, |_| {});
+
+        for (state, declarations) in &self.variants {
+            let apply = |mut d: Div| {
+                d.apply_classes(declarations, |_| {});
+                d
+            };
+
+            let styled = core::mem::take(div);
+            *div = match state {
+                State::Hover => styled.hover(apply),
+                State::Focus => styled.focus(apply),
+                State::Active => styled.active(apply),
+                State::Disabled => styled.disabled(apply),
+            };
+        }
+    }
+}
+
+/// An error while parsing a stylesheet.
+#[derive(Debug, Error)]
+pub enum StyleSheetError {
+    #[error("failed to read the stylesheet: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("the stylesheet is not valid UTF-8: {0}")]
+    Utf8(#[from] core::str::Utf8Error),
+    #[error("expected a rule `selector {{ declarations }}`, found `{0}`")]
+    Syntax(String),
+    #[error("invalid selector `{0}`")]
+    InvalidSelector(String),
+    #[error("unknown declaration `{0}`")]
+    UnknownDeclaration(String),
+}
+
+/// A list of style rules, loaded from the `.moon.css` files.
+#[derive(Asset, TypePath, Clone, Debug, Default, PartialEq)]
+pub struct StyleSheet {
+    rules: Vec<StyleRule>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct StyleRule {
+    selectors: SmallVec<[Selector; 1]>,
+
+    /// The Tailwind classes of the rule.
+    declarations: String,
+}
+
+/// Compound selectors, from the outermost ancestor to the node.
+#[derive(Clone, Debug, PartialEq)]
+struct Selector(SmallVec<[Compound; 2]>);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Compound {
+    node_type: Option<NodeType>,
+    classes: SmallVec<[String; 2]>,
+    state: Option<State>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NodeType {
+    Div,
+    Text,
+    Image,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Hover,
+    Focus,
+    Active,
+    Disabled,
+}
+
+impl StyleSheet {
+    /// Parses the rules of a stylesheet.
+    pub fn parse(source: &str) -> Result<Self, StyleSheetError> {
+        let source = strip_comments(source);
+        let mut rules = Vec::new();
+
+        let mut rest = source.trim();
+        while !rest.is_empty() {
+            let (rule, next) = rest
+                .split_once('}')
+                .ok_or_else(|| StyleSheetError::Syntax(String::from(rest)))?;
+            let (selectors, declarations) = rule
+                .split_once('{')
+                .filter(|(selectors, _)| !selectors.trim().is_empty())
+                .ok_or_else(|| StyleSheetError::Syntax(String::from(rule.trim())))?;
+
+            let selectors = selectors
+                .split(',')
+                .map(Selector::parse)
+                .collect::<Result<_, _>>()?;
+            let declarations = declarations
+                .split(|c: char| c.is_whitespace() || c == ';')
+                .filter(|declaration| !declaration.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let mut unknown = None;
+            let mut probe = Div::DEFAULT;
+            probe.apply_classes(&declarations, |declaration| {
+                unknown.get_or_insert(declaration);
+            });
+            if let Some(declaration) = unknown {
+                return Err(StyleSheetError::UnknownDeclaration(String::from(
+                    declaration,
+                )));
+            }
+
+            rules.push(StyleRule {
+                selectors,
+                declarations,
+            });
+            rest = next.trim();
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Returns the number of rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+fn strip_comments(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut rest = source;
+
+    while let Some((before, after)) = rest.split_once("/*") {
+        stripped.push_str(before);
+        rest = after.split_once("*/").map_or("", |(_, after)| after);
+    }
+    stripped.push_str(rest);
+
+    stripped
+}
+
+impl Selector {
+    fn parse(selector: &str) -> Result<Self, StyleSheetError> {
+        let invalid = || StyleSheetError::InvalidSelector(String::from(selector.trim()));
+
+        let compounds = selector
+            .split_whitespace()
+            .map(|compound| Compound::parse(compound).ok_or_else(invalid))
+            .collect::<Result<SmallVec<_>, _>>()?;
+
+        // The states of the ancestors are not supported.
+        let (_, ancestors) = compounds.split_last().ok_or_else(invalid)?;
+        if ancestors.iter().any(|compound| compound.state.is_some()) {
+            return Err(invalid());
+        }
+
+        Ok(Self(compounds))
+    }
+
+    /// The number of classes and states, then the number of types.
+    fn specificity(&self) -> (usize, usize) {
+        self.0.iter().fold((0, 0), |(classes, types), compound| {
+            (
+                classes + compound.classes.len() + usize::from(compound.state.is_some()),
+                types + usize::from(compound.node_type.is_some()),
+            )
+        })
+    }
+
+    fn state(&self) -> Option<State> {
+        self.0.last().and_then(|compound| compound.state)
+    }
+}
+
+impl Compound {
+    fn parse(compound: &str) -> Option<Self> {
+        let start = compound.find(['.', ':']).unwrap_or(compound.len());
+        let (node_type, mut rest) = compound.split_at(start);
+
+        let mut parsed = Self {
+            node_type: match node_type {
+                "" | "*" => None,
+                "div" => Some(NodeType::Div),
+                "text" => Some(NodeType::Text),
+                "img" => Some(NodeType::Image),
+                _ => return None,
+            },
+            ..Default::default()
+        };
+
+        while let Some(prefix) = rest.chars().next() {
+            let end = rest[1..].find(['.', ':']).map_or(rest.len(), |end| end + 1);
+            let name = &rest[1..end];
+            rest = &rest[end..];
+
+            if name.is_empty() {
+                return None;
+            }
+
+            match prefix {
+                '.' => parsed.classes.push(String::from(name)),
+                _ if parsed.state.is_some() => return None,
+                _ => {
+                    parsed.state = Some(match name {
+                        "hover" => State::Hover,
+                        "focus" => State::Focus,
+                        "active" => State::Active,
+                        "disabled" => State::Disabled,
+                        _ => return None,
+                    })
+                }
+            }
+        }
+
+        Some(parsed)
+    }
+
+    fn matches(&self, class: Option<&Class>, node_type: NodeType) -> bool {
+        self.node_type
+            .is_none_or(|expected| expected == node_type || expected == NodeType::Div)
+            && self
+                .classes
+                .iter()
+                .all(|name| class.is_some_and(|class| class.contains(name)))
+    }
+}
+
+/// Loads the `.moon.css` files.
+#[derive(Default, TypePath)]
+pub struct StyleSheetLoader;
+
+impl AssetLoader for StyleSheetLoader {
+    type Asset = StyleSheet;
+    type Settings = ();
+    type Error = StyleSheetError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        StyleSheet::parse(core::str::from_utf8(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["moon.css"]
+    }
+}
+
+type NodeQuery<'w, 's> = Query<'w, 's, (Option<&'static Class>, Has<Text>, Has<Image>), With<Div>>;
+
+/// Matches the rules of the stylesheets with the nodes with a [`Class`], when the stylesheets,
+/// or the classes or the parents of the nodes or of their ancestors have been changed.
+///
+/// The rules are applied when the [`ComputedDiv`](crate::prelude::ComputedDiv) of the nodes is
+/// resolved.
+pub fn apply_style_sheets(
+    mut commands: Commands,
+    style_sheets: Res<UiStyleSheets>,
+    assets: Res<Assets<StyleSheet>>,
+    mut asset_events: MessageReader<AssetEvent<StyleSheet>>,
+    mut removed_classes: RemovedComponents<Class>,
+    node_query: NodeQuery,
+    parent_query: Query<&ChildOf>,
+    children_query: Query<&Children>,
+    changed_query: Query<Entity, (With<Div>, Or<(Changed<Class>, Changed<ChildOf>)>)>,
+    all_query: Query<Entity, With<Class>>,
+    mut rules_query: Query<Option<&mut MatchedRules>, With<Class>>,
+    mut unclassed_query: Query<&mut Div, (With<MatchedRules>, Without<Class>)>,
+) {
+    let sheets_changed = asset_events.read().fold(false, |changed, event| {
+        changed
+            || style_sheets.0.iter().any(|handle| {
+                event.is_modified(handle) || event.is_loaded_with_dependencies(handle)
+            })
+    }) || style_sheets.is_changed();
+
+    let mut entities = EntityHashSet::default();
+
+    // The nodes whose classes have been removed are displayed as they have been set.
+    for entity in removed_classes.read() {
+        if let Ok(mut div) = unclassed_query.get_mut(entity) {
+            commands.entity(entity).remove::<MatchedRules>();
+            div.set_changed();
+        }
+        entities.insert(entity);
+    }
+
+    if sheets_changed {
+        entities.extend(&all_query);
+    } else {
+        entities.extend(&changed_query);
+    }
+
+    if entities.is_empty() {
+        return;
+    }
+
+    // The selectors of the descendants match the classes of their ancestors.
+    if !sheets_changed {
+        for entity in entities.clone() {
+            entities.extend(children_query.iter_descendants(entity));
+        }
+    }
+
+    let rules = style_sheets
+        .0
+        .iter()
+        .filter_map(|handle| assets.get(handle))
+        .flat_map(|sheet| &sheet.rules)
+        .collect::<Vec<_>>();
+
+    for entity in entities {
+        let Ok(matched_rules) = rules_query.get_mut(entity) else {
+            continue;
+        };
+
+        // The matching selectors with their rules, by specificity then by order.
+        let mut matched = rules
+            .iter()
+            .enumerate()
+            .flat_map(|(order, &rule)| {
+                rule.selectors
+                    .iter()
+                    .filter(|selector| matches(selector, entity, &node_query, &parent_query))
+                    .map(move |selector| (selector.specificity(), order, selector.state(), rule))
+            })
+            .collect::<Vec<_>>();
+        matched.sort_by_key(|&(specificity, order, ..)| (specificity, order));
+
+        let declarations = |state| {
+            matched
+                .iter()
+                .filter(|(.., other, _)| *other == state)
+                .map(|(.., rule)| rule.declarations.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        let styled = MatchedRules {
+            declarations: declarations(None),
+            variants: [State::Hover, State::Focus, State::Active, State::Disabled]
+                .into_iter()
+                .map(|state| (state, declarations(Some(state))))
+                .filter(|(_, declarations)| !declarations.is_empty())
+                .collect(),
+        };
+
+        match matched_rules {
+            Some(mut matched_rules) => {
+                matched_rules.set_if_neq(styled);
+            }
+            None => {
+                commands.entity(entity).insert(styled);
+            }
+        }
+    }
+}
+
+/// Returns `true` if the selector matches the node and its ancestors.
+fn matches(
+    selector: &Selector,
+    entity: Entity,
+    node_query: &NodeQuery,
+    parent_query: &Query<&ChildOf>,
+) -> bool {
+    let node = |entity| {
+        node_query
+            .get(entity)
+            .ok()
+            .map(|(class, is_text, is_image)| {
+                let node_type = match (is_text, is_image) {
+                    (true, _) => NodeType::Text,
+                    (_, true) => NodeType::Image,
+                    _ => NodeType::Div,
+                };
+                (class, node_type)
+            })
+    };
+
+    let Some((subject, ancestors)) = selector.0.split_last() else {
+        return false;
+    };
+    let Some((class, node_type)) = node(entity) else {
+        return false;
+    };
+    if !subject.matches(class, node_type) {
+        return false;
+    }
+
+    // Matches the ancestors from the nearest one, walks through the ghost nodes.
+    let mut ancestors = ancestors.iter().rev().peekable();
+    for ancestor in parent_query.iter_ancestors(entity) {
+        let Some(compound) = ancestors.peek() else {
+            break;
+        };
+        if node(ancestor).is_some_and(|(class, node_type)| compound.matches(class, node_type)) {
+            ancestors.next();
+        }
+    }
+
+    ancestors.peek().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_color::{Color, palettes::tailwind::*};
+    use bevy_ecs::{message::Messages, schedule::Schedule, world::World};
+
+    use super::*;
+    use crate::{
+        components::{
+            computed::{ComputedDiv, update_computed_divs},
+            interaction::InteractionState,
+        },
+        prelude::div,
+        theme::MoonTheme,
+    };
+
+    const SOURCE: &str = "
+        /* The items of the lists. */
+        .item { p-2 bg-white }
+        .list .item { bg-slate-100 }
+        .item:hover { bg-slate-200 }
+        text, img.avatar { rounded-full }
+    ";
+
+    #[test]
+    fn test_parse_style_sheet() {
+        let sheet = StyleSheet::parse(SOURCE).unwrap();
+        assert_eq!(sheet.len(), 4);
+
+        let rule = &sheet.rules[1];
+        assert_eq!(rule.declarations, "bg-slate-100");
+        assert_eq!(rule.selectors[0].specificity(), (2, 0));
+
+        let rule = &sheet.rules[2];
+        assert_eq!(rule.selectors[0].state(), Some(State::Hover));
+
+        let rule = &sheet.rules[3];
+        assert_eq!(rule.selectors.len(), 2);
+        assert_eq!(rule.selectors[1].specificity(), (1, 1));
+
+        assert!(matches!(
+            StyleSheet::parse(".item { p-2 "),
+            Err(StyleSheetError::Syntax(_))
+        ));
+        assert!(matches!(
+            StyleSheet::parse(".list:hover .item { p-2 }"),
+            Err(StyleSheetError::InvalidSelector(_))
+        ));
+        assert!(matches!(
+            StyleSheet::parse("button { p-2 }"),
+            Err(StyleSheetError::InvalidSelector(_))
+        ));
+        assert!(matches!(
+            StyleSheet::parse(".item { p-2 unknown }"),
+            Err(StyleSheetError::UnknownDeclaration(name)) if name == "unknown"
+        ));
+    }
+
+    #[test]
+    fn test_apply_style_sheets() {
+        let mut world = World::new();
+        world.init_resource::<Messages<AssetEvent<StyleSheet>>>();
+        world.init_resource::<MoonTheme>();
+
+        let mut assets = Assets::<StyleSheet>::default();
+        let handle = assets.add(StyleSheet::parse(SOURCE).unwrap());
+        world.insert_resource(assets);
+        world.insert_resource(UiStyleSheets(vec![handle]));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((apply_style_sheets, update_computed_divs).chain());
+
+        let list = world.spawn((div(), Class::new("list"))).id();
+        let nested = world
+            .spawn((div().w(10.0), Class::new("item"), ChildOf(list)))
+            .id();
+        let item = world.spawn((div().w(10.0), Class::new("item"))).id();
+
+        schedule.run(&mut world);
+
+        let current = world.get::<ComputedDiv>(nested).unwrap();
+        assert_eq!(current.background, Some(SLATE_100.into()));
+        assert_eq!(current.style().padding, taffy::Rect::length(8.0));
+        // The style of the node is kept.
+        assert_eq!(current.style().size.width, taffy::Dimension::length(10.0));
+        assert_eq!(world.get::<Div>(nested), Some(&div().w(10.0)));
+        assert!(world.get::<InteractionState>(nested).is_some());
+
+        let current = world.get::<ComputedDiv>(item).unwrap();
+        assert_eq!(current.background, Some(Color::WHITE));
+
+        // Restyles the descendants when the classes of their ancestors are changed.
+        world.get_mut::<Class>(list).unwrap().0.clear();
+        schedule.run(&mut world);
+
+        let current = world.get::<ComputedDiv>(nested).unwrap();
+        assert_eq!(current.background, Some(Color::WHITE));
+
+        // Restyles the node when it is reparented.
+        world.entity_mut(item).insert(ChildOf(list));
+        world.entity_mut(list).insert(Class::new("list"));
+        schedule.run(&mut world);
+
+        let current = world.get::<ComputedDiv>(item).unwrap();
+        assert_eq!(current.background, Some(SLATE_100.into()));
+
+        // Restyles the node when its classes are changed.
+        world.get_mut::<Class>(item).unwrap().0.clear();
+        schedule.run(&mut world);
+
+        let current = world.get::<ComputedDiv>(item).unwrap();
+        assert_eq!(current.background, None);
+        assert!(!current.has_variants());
+
+        // Displays the node as it has been set when its classes are removed.
+        world.entity_mut(nested).remove::<Class>();
+        schedule.run(&mut world);
+
+        assert!(world.get::<MatchedRules>(nested).is_none());
+        let current = world.get::<ComputedDiv>(nested).unwrap();
+        assert_eq!(current.background, None);
+        assert_eq!(current.style().padding, taffy::Rect::length(0.0));
+        assert_eq!(current.style().size.width, taffy::Dimension::length(10.0));
+    }
+}
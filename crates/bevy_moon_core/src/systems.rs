@@ -1,18 +1,18 @@
-use std::{any::TypeId, ops::DerefMut};
+use std::{any::TypeId, ops::Range};
 
 use bevy_camera::{Camera, visibility::VisibleEntities};
 use bevy_ecs::{
     change_detection::{DetectChanges, DetectChangesMut},
-    entity::Entity,
+    entity::{Entity, EntityHashMap, EntityHashSet},
     hierarchy::{ChildOf, Children},
     lifecycle::RemovedComponents,
-    query::{Changed, With, Without},
+    query::{Changed, Or, With},
     schedule::SystemSet,
     system::{Commands, Local, Query, Res, ResMut},
     world::Ref,
 };
-use bevy_math::{UVec2, Vec2};
-use bevy_text::{ComputedTextBlock, FontCx};
+use bevy_math::{Affine3A, UVec2, Vec2, Vec4};
+use bevy_text::{ComputedTextBlock, FontCx, RemSize, TextFont};
 use bevy_transform::components::{GlobalTransform, Transform};
 use fixedbitset::FixedBitSet;
 use smallvec::SmallVec;
@@ -20,13 +20,19 @@ use taffy::NodeId;
 
 use crate::{
     components::{
-        computed::{ComputedLayout, ComputedTargetInfo},
+        computed::{ComputedClip, ComputedDiv, ComputedLayout, ComputedTargetInfo},
         content_size::ContentSize,
+        root::UiRoot,
+        scroll::ScrollPosition,
     },
+    dirty::{UiDirtyRoots, UiRecomputeCounter},
+    error::{MoonError, report_error},
     geometry::FLIP_Y,
-    layout::UiLayoutTree,
+    hierarchy::{UiChildren, UiNodeFilter, UiRootNodes},
+    layout::{UiLayoutSettings, UiLayoutTree},
     prelude::Div,
     stack::{UiStack, UiStackMap},
+    units::UnitContext,
 };
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
@@ -40,11 +46,11 @@ pub enum UiSystems {
 
 pub fn ui_target_info_system(
     mut commands: Commands,
-    mut camera_query: Query<&Camera>,
+    mut camera_query: Query<(&Camera, Option<&mut ComputedTargetInfo>)>,
     ui_stack_map: Res<UiStackMap>,
 ) {
     for &camera_entity in ui_stack_map.keys() {
-        let Ok(camera) = camera_query.get_mut(camera_entity) else {
+        let Ok((camera, maybe_target_info)) = camera_query.get_mut(camera_entity) else {
             continue;
         };
 
@@ -54,25 +60,122 @@ pub fn ui_target_info_system(
             .unwrap_or(UVec2::ZERO)
             .as_vec2();
 
-        commands.entity(camera_entity).insert(ComputedTargetInfo {
+        let target_info = ComputedTargetInfo {
             scale_factor,
             physical_size,
-        });
+        };
+
+        // Only triggers change detection when the target has been changed.
+        match maybe_target_info {
+            Some(mut computed_target_info) => {
+                computed_target_info.set_if_neq(target_info);
+            }
+            None => {
+                commands.entity(camera_entity).insert(target_info);
+            }
+        }
+    }
+}
+
+/// Marks the roots whose subtrees have been changed since the last frame.
+pub fn mark_dirty_roots_system(
+    ui_children: UiChildren,
+    node_query: Query<(), UiNodeFilter>,
+    changed_query: Query<
+        Entity,
+        (
+            UiNodeFilter,
+            Or<(
+                Changed<ComputedDiv>,
+                Changed<ContentSize>,
+                Changed<Children>,
+                Changed<ChildOf>,
+                Changed<ScrollPosition>,
+                Changed<UiRoot>,
+                Changed<TextFont>,
+            )>,
+        ),
+    >,
+    moved_query: Query<Entity, (UiNodeFilter, Changed<Transform>)>,
+    mut removed_div: RemovedComponents<Div>,
+    mut removed_children: RemovedComponents<Children>,
+    mut removed_child_of: RemovedComponents<ChildOf>,
+    mut dirty_roots: ResMut<UiDirtyRoots>,
+) {
+    for entity in &changed_query {
+        mark_dirty_root(&ui_children, &mut dirty_roots, entity, true);
+    }
+
+    // The depth of a node only changes its order in the stack.
+    for entity in &moved_query {
+        mark_dirty_root(&ui_children, &mut dirty_roots, entity, false);
+    }
+
+    for entity in removed_children.read() {
+        if node_query.contains(entity) {
+            mark_dirty_root(&ui_children, &mut dirty_roots, entity, true);
+        }
+    }
+
+    // A node without parent becomes a root.
+    for entity in removed_child_of.read() {
+        if node_query.contains(entity) {
+            mark_dirty_root(&ui_children, &mut dirty_roots, entity, true);
+            dirty_roots.restack_all = true;
+        }
+    }
+
+    // A removed root must be dropped from the stacks.
+    if removed_div.read().count() > 0 {
+        dirty_roots.restack_all = true;
+    }
+}
+
+fn mark_dirty_root(
+    ui_children: &UiChildren,
+    dirty_roots: &mut UiDirtyRoots,
+    entity: Entity,
+    needs_layout: bool,
+) {
+    let mut mark = |root| {
+        if needs_layout {
+            dirty_roots.mark(root);
+        } else {
+            dirty_roots.mark_stack(root);
+        }
+    };
+
+    match ui_children.root(entity) {
+        Some(root) => mark(root),
+        // A root ghost node, its children are roots.
+        None => {
+            ui_children.iter(entity).for_each(mark);
+            dirty_roots.restack_all = true;
+        }
     }
 }
 
 pub fn ui_stack_system(
     render_targets: Query<(Entity, &VisibleEntities), With<Camera>>,
-    root_div_query: Query<
-        (Entity, &GlobalTransform, Option<&Children>),
-        (With<Div>, Without<ChildOf>),
-    >,
-    div_query: Query<(Entity, &GlobalTransform, Option<&Children>), With<Div>>,
+    ui_root_nodes: UiRootNodes,
+    ui_children: UiChildren,
+    div_query: Query<(Entity, &GlobalTransform), With<Div>>,
     mut update_query: Query<&mut Div>,
     mut ui_stack_map: ResMut<UiStackMap>,
+    mut dirty_roots: ResMut<UiDirtyRoots>,
+    mut counter: ResMut<UiRecomputeCounter>,
     mut view_entities: Local<FixedBitSet>,
+    mut previous_ranges: Local<EntityHashMap<Range<usize>>>,
 ) {
-    ui_stack_map.clear();
+    counter.restacked_roots = 0;
+
+    // Drops the stacks of the removed cameras.
+    if ui_stack_map
+        .keys()
+        .any(|camera_entity| !render_targets.contains(*camera_entity))
+    {
+        ui_stack_map.retain(|camera_entity, _| render_targets.contains(*camera_entity));
+    }
 
     for (camera_entity, visiable_entities) in render_targets {
         view_entities.clear();
@@ -83,185 +186,279 @@ pub fn ui_stack_system(
                 .map(|e| e.index_u32() as usize),
         );
 
+        let previous = ui_stack_map.get(&camera_entity);
+
         if view_entities.is_clear() {
+            if previous.is_some() {
+                ui_stack_map.remove(&camera_entity);
+            }
             continue;
         }
 
-        let ui_stack = ui_stack_map
-            .as_mut()
-            .deref_mut()
-            .entry(camera_entity)
-            .or_default();
+        // Only re-stacks the cameras whose visible nodes or roots have been changed.
+        let is_dirty = previous.is_none_or(|ui_stack| {
+            dirty_roots.restack_all
+                || !ui_stack.bitset.ones().eq(view_entities.ones())
+                || dirty_roots
+                    .stack
+                    .iter()
+                    .any(|root| view_entities.contains(root.index_u32() as usize))
+        });
 
-        ui_stack.bitset.union_with(&view_entities);
+        if !is_dirty {
+            continue;
+        }
+
+        previous_ranges.clear();
+        if let Some(previous) = previous {
+            previous_ranges.extend(
+                previous
+                    .roots
+                    .iter()
+                    .copied()
+                    .zip(previous.ranges.iter().cloned()),
+            );
+        }
+
+        let mut ui_stack = UiStack {
+            bitset: view_entities.clone(),
+            ..Default::default()
+        };
 
         // Only filter root divs.
-        let divs = root_div_query
-            .iter()
+        let mut roots = div_query
+            .iter_many(ui_root_nodes.iter())
             .filter(|entity| ui_stack.bitset.contains(entity.0.index_u32() as usize))
             .collect::<Vec<_>>();
 
-        ui_stack.roots.extend(divs.iter().map(|e| e.0));
+        // back to front
+        radsort::sort_by_key(&mut roots, |e| (e.1.translation().z, e.0.index_u32()));
+
+        for (root_entity, _) in roots {
+            let start = ui_stack.entities.len();
+
+            // Reuses the subtree of a clean root.
+            match previous_ranges
+                .get(&root_entity)
+                .filter(|_| !dirty_roots.stack.contains(&root_entity))
+                .zip(previous)
+            {
+                Some((range, previous)) => {
+                    ui_stack
+                        .entities
+                        .extend_from_slice(&previous.entities[range.clone()]);
+                }
+                None => {
+                    ui_stack.entities.push(root_entity);
+                    update_ui_stack_recursive(&div_query, &ui_children, &mut ui_stack, root_entity);
+                    counter.restacked_roots += 1;
+                }
+            }
+
+            ui_stack.roots.push(root_entity);
+            ui_stack.ranges.push(start..ui_stack.entities.len());
+        }
+
+        tracing::debug!("camera: {} {:?}", camera_entity, ui_stack.roots);
 
         // Make sure ui transparency phases' `sort_key` is correct.
-        let mut depth = 0;
+        for (stack_index, entity) in ui_stack.iter() {
+            if let Ok(mut div) = update_query.get_mut(entity) {
+                div.bypass_change_detection().stack_index = stack_index;
+            }
+        }
 
-        update_ui_stack_recursive(
-            &div_query,
-            &mut update_query,
-            ui_stack,
-            &mut depth,
-            divs,
-            camera_entity,
-        );
+        ui_stack_map.insert(camera_entity, ui_stack);
     }
+
+    dirty_roots.stack.clear();
+    dirty_roots.restack_all = false;
 }
 
 fn update_ui_stack_recursive(
-    div_query: &Query<(Entity, &GlobalTransform, Option<&Children>), With<Div>>,
-    update_query: &mut Query<&mut Div>,
+    div_query: &Query<(Entity, &GlobalTransform), With<Div>>,
+    ui_children: &UiChildren,
     ui_stack: &mut UiStack,
-    depth: &mut usize,
-    mut sorted_divs: Vec<(Entity, &GlobalTransform, Option<&Children>)>,
-    camera_entity: Entity,
+    entity: Entity,
 ) {
-    if sorted_divs.is_empty() {
-        return;
-    }
+    // Walks through the ghost nodes.
+    let mut sorted_divs = div_query
+        .iter_many(ui_children.iter(entity))
+        .collect::<Vec<_>>();
 
     // back to front
     radsort::sort_by_key(&mut sorted_divs, |e| (e.1.translation().z, e.0.index_u32()));
 
-    tracing::debug!(
-        "camera: {} {} {:?}",
-        camera_entity,
-        &depth,
-        sorted_divs
-            .iter()
-            .map(|e| (e.0, e.1.translation().z))
-            .collect::<Vec<_>>()
-    );
-
-    let start = ui_stack
-        .ranges
-        .last()
-        .map(|range| range.end)
-        .unwrap_or_default();
-    let end = start + sorted_divs.len();
-    ui_stack.ranges.push(start..end);
-
-    for (entity, _transform, children) in sorted_divs {
-        if let Ok(mut div) = update_query.get_mut(entity) {
-            div.bypass_change_detection().stack_index = *depth;
-        }
-
-        ui_stack.entities.push(entity);
-
-        *depth += 1;
-
-        let Some(children) = children.filter(|c| !c.is_empty()) else {
-            continue;
-        };
-
-        let divs = div_query.iter_many(children).collect::<Vec<_>>();
+    for (child, _transform) in sorted_divs {
+        ui_stack.entities.push(child);
 
-        update_ui_stack_recursive(
-            &div_query,
-            update_query,
-            ui_stack,
-            depth,
-            divs,
-            camera_entity,
-        );
+        update_ui_stack_recursive(div_query, ui_children, ui_stack, child);
     }
 }
 
 pub fn ui_layout_system(
-    camera_query: Query<&Camera>,
-    root_div_query: Query<
-        (
-            Entity,
-            Ref<Div>,
-            Option<Ref<ContentSize>>,
-            Option<Ref<Children>>,
-        ),
-        Without<ChildOf>,
-    >,
-    div_query: Query<
-        (
-            Entity,
-            Ref<Div>,
-            Option<Ref<ContentSize>>,
-            Option<Ref<Children>>,
-        ),
-        With<ChildOf>,
-    >,
+    mut commands: Commands,
+    target_info_query: Query<&ComputedTargetInfo>,
+    div_query: Query<(
+        Entity,
+        Ref<ComputedDiv>,
+        Option<Ref<ContentSize>>,
+        Option<Ref<TextFont>>,
+    )>,
+    ui_children: UiChildren,
+    ui_root_query: Query<&UiRoot>,
     ui_stack_map: Res<UiStackMap>,
+    layout_settings: Res<UiLayoutSettings>,
+    rem_size: Res<RemSize>,
     mut ui_layout_tree: ResMut<UiLayoutTree>,
-    mut layouts: Local<SmallVec<[taffy::NodeId; 4]>>,
+    mut dirty_roots: ResMut<UiDirtyRoots>,
+    mut counter: ResMut<UiRecomputeCounter>,
+    mut root_targets: Local<EntityHashMap<ComputedTargetInfo>>,
+    mut next_root_targets: Local<EntityHashMap<ComputedTargetInfo>>,
+    mut available_sizes: Local<EntityHashMap<Vec2>>,
+    mut root_contexts: Local<EntityHashMap<UnitContext>>,
+    mut units_changed: Local<EntityHashSet>,
+    mut errors: Local<Vec<MoonError>>,
 
     mut removed_children: RemovedComponents<Children>,
     mut removed_div: RemovedComponents<Div>,
 
-    changed_children_query: Query<(), (Changed<Children>, With<Div>)>,
-
     mut text_block_query: Query<&mut ComputedTextBlock>,
     mut font_system: ResMut<FontCx>,
-    mut update_div_query: Query<(&mut Transform, &mut ComputedLayout), With<Div>>,
+    mut update_div_query: Query<(&mut Transform, &mut ComputedLayout, &ScrollPosition), With<Div>>,
 ) {
-    for (_camera_entity, ui_stack) in ui_stack_map.as_ref().iter() {
-        for item in root_div_query.iter_many(&ui_stack.roots) {
-            update_ui_layout_recursive(
-                &div_query,
-                &changed_children_query,
-                &mut ui_layout_tree,
-                &mut layouts,
-                item,
-            );
+    counter.relayout_roots = 0;
+    available_sizes.clear();
+
+    // A root is laid out once for all the cameras which render it,
+    // within the preferred target of these cameras.
+    next_root_targets.clear();
+
+    for (camera_entity, ui_stack) in ui_stack_map.as_ref().iter() {
+        let Ok(&target_info) = target_info_query.get(*camera_entity) else {
+            continue;
+        };
+
+        for &root_entity in &ui_stack.roots {
+            next_root_targets
+                .entry(root_entity)
+                .and_modify(|preferred| {
+                    if target_info.preference(preferred).is_gt() {
+                        *preferred = target_info;
+                    }
+                })
+                .or_insert(target_info);
+        }
+    }
+
+    // The roots whose preferred targets have been changed, or which have never been laid out.
+    for (&root_entity, target_info) in next_root_targets.iter() {
+        if root_targets.get(&root_entity) != Some(target_info)
+            || ui_layout_tree.get_node_id(root_entity).is_none()
+        {
+            dirty_roots.layout.insert(root_entity);
+        }
+    }
+
+    core::mem::swap(&mut *root_targets, &mut *next_root_targets);
+
+    // The roots whose relative lengths must be resolved again,
+    // because the root font size or their available sizes have been changed.
+    units_changed.clear();
+    root_contexts.retain(|root_entity, _| root_targets.contains_key(root_entity));
+
+    for (&root_entity, target_info) in root_targets.iter() {
+        // An explicit root is laid out independently of the camera viewport.
+        let available_size = ui_root_query.get(root_entity).map_or_else(
+            |_| target_info.logical_size(),
+            UiRoot::available_layout_size,
+        );
+        let context = UnitContext::new(rem_size.0, available_size);
+
+        if root_contexts.insert(root_entity, context) != Some(context) {
+            dirty_roots.layout.insert(root_entity);
+            units_changed.insert(root_entity);
         }
 
-        layouts.clear();
+        if dirty_roots.needs_layout(root_entity) {
+            available_sizes.insert(root_entity, available_size);
+        }
+    }
+
+    let dirty_root_entities = available_sizes
+        .keys()
+        .copied()
+        .collect::<SmallVec<[Entity; 8]>>();
+
+    for item in div_query.iter_many(&dirty_root_entities) {
+        let root_entity = item.0;
+        update_ui_layout_recursive(
+            &div_query,
+            &ui_children,
+            &mut ui_layout_tree,
+            &mut errors,
+            root_entity,
+            &root_contexts[&root_entity],
+            units_changed.contains(&root_entity),
+            item,
+        );
     }
 
     {
         // Updates and remove children.
-        ui_layout_tree.remove_nodes_children(
-            removed_children
-                .read()
-                .filter(|&entity| div_query.contains(entity)),
+        errors.extend(
+            ui_layout_tree.remove_nodes_children(
+                removed_children
+                    .read()
+                    .filter(|&entity| div_query.contains(entity)),
+            ),
         );
         // Cleans up removed divs after syncing children.
-        ui_layout_tree.remove_nodes(
-            removed_div
-                .read()
-                .filter(|&entity| !div_query.contains(entity)),
+        errors.extend(
+            ui_layout_tree.remove_nodes(
+                removed_div
+                    .read()
+                    .filter(|&entity| !div_query.contains(entity)),
+            ),
         );
     }
 
-    for (&camera_entity, ui_stack) in ui_stack_map.as_ref().iter() {
-        let Ok(camera) = camera_query.get(camera_entity) else {
+    // The roots are independent, they are laid out in parallel.
+    errors.extend(ui_layout_tree.compute_layouts(
+        &available_sizes,
+        &mut text_block_query,
+        &mut font_system,
+        layout_settings.parallel,
+    ));
+
+    for item in div_query.iter_many(&dirty_root_entities) {
+        if !available_sizes.contains_key(&item.0) {
             continue;
-        };
-        let physical_size = camera.physical_viewport_size().unwrap_or(UVec2::ZERO);
+        }
 
-        for item in root_div_query.iter_many(&ui_stack.roots) {
-            ui_layout_tree.compute_layout(
-                item.0,
-                physical_size,
-                &mut text_block_query,
-                &mut font_system,
-            );
+        let maybe_root = ui_root_query.get(item.0).ok();
 
-            update_ui_geometry_recursive(
-                &div_query,
-                &mut update_div_query,
-                &mut ui_layout_tree,
-                item,
-                None,
-            );
-        }
+        update_ui_geometry_recursive(
+            &div_query,
+            &ui_children,
+            &mut update_div_query,
+            &mut ui_layout_tree,
+            item,
+            None,
+            maybe_root,
+        );
+
+        counter.relayout_roots += 1;
+    }
+
+    // Keeps the dirty roots which are not rendered yet.
+    dirty_roots.layout.retain(|&root_entity| {
+        !root_targets.contains_key(&root_entity) && div_query.contains(root_entity)
+    });
 
-        layouts.clear();
+    // The offending nodes have been skipped, the others are laid out.
+    for error in errors.drain(..) {
+        report_error(&mut commands, error);
     }
 
     // #[cfg(debug_assertions)]
@@ -269,92 +466,109 @@ pub fn ui_layout_system(
 }
 
 fn update_ui_layout_recursive(
-    div_query: &Query<
-        (
-            Entity,
-            Ref<Div>,
-            Option<Ref<ContentSize>>,
-            Option<Ref<Children>>,
-        ),
-        With<ChildOf>,
-    >,
-    changed_children_query: &Query<(), (Changed<Children>, With<Div>)>,
+    div_query: &Query<(
+        Entity,
+        Ref<ComputedDiv>,
+        Option<Ref<ContentSize>>,
+        Option<Ref<TextFont>>,
+    )>,
+    ui_children: &UiChildren,
     ui_layout_tree: &mut UiLayoutTree,
-    layouts: &mut SmallVec<[NodeId; 4]>,
-    (entity, div, content_size, children): (
+    errors: &mut Vec<MoonError>,
+    root_entity: Entity,
+    context: &UnitContext,
+    units_changed: bool,
+    (entity, div, content_size, text_font): (
         Entity,
-        Ref<Div>,
+        Ref<ComputedDiv>,
         Option<Ref<ContentSize>>,
-        Option<Ref<Children>>,
+        Option<Ref<TextFont>>,
     ),
-) {
-    // Stores current node's layout id and index.
-    let mut node = Option::<(NodeId, usize)>::None;
-
+) -> Option<NodeId> {
     let is_changed = div.is_added()
         || div.is_changed()
-        || changed_children_query.contains(entity)
         || content_size
             .as_ref()
             .is_some_and(|c| c.is_changed() && c.measure.is_some())
-        || !ui_layout_tree.contains(entity);
+        || (div.has_relative_lengths()
+            && (units_changed || text_font.as_ref().is_some_and(Ref::is_changed)));
+
+    // A node which has been moved from another root is inserted into the shard of this root.
+    let is_moved = ui_layout_tree.root_of(entity) != Some(root_entity);
 
-    if is_changed {
-        let node_id = ui_layout_tree.upsert_node(
+    let node_id = match ui_layout_tree.get_node_id(entity) {
+        Some(node_id) if !is_changed && !is_moved => node_id,
+        _ => match ui_layout_tree.upsert_node(
+            root_entity,
             entity,
-            div.style.clone(),
+            // The relative lengths are resolved against the font of the node.
+            div.resolve_style(&text_font.map_or(*context, |text_font| {
+                context.with_em(text_font.font_size.eval(context.root_size, context.rem))
+            })),
             content_size.and_then(|c| c.measure.clone()),
-        );
-
-        layouts.push(node_id);
-
-        node = Some((node_id, layouts.len()));
-    }
+        ) {
+            Ok(node_id) => node_id,
+            Err(error) => {
+                // Skips the node and its descendants.
+                errors.push(error);
+                return None;
+            }
+        },
+    };
 
-    if let Some(children) = children {
-        for div in div_query.iter_many(&children) {
+    // Walks through the ghost nodes, their children are laid out as the children of this node.
+    let mut children = div_query
+        .iter_many(ui_children.iter(entity))
+        .filter_map(|item| {
+            let order = item.1.order;
             update_ui_layout_recursive(
                 div_query,
-                changed_children_query,
+                ui_children,
                 ui_layout_tree,
-                layouts,
-                div,
-            );
-        }
-    }
+                errors,
+                root_entity,
+                context,
+                units_changed,
+                item,
+            )
+            .map(|node_id| (order, node_id))
+        })
+        .collect::<SmallVec<[(i32, NodeId); 8]>>();
 
-    // The current node is created, so we can update its children.
-    let Some((node_id, node_children)) = node
-        .map(|(node_id, index)| (node_id, layouts.drain(index..)))
-        .filter(|(_, node_children)| node_children.len() > 0)
-    else {
-        return;
-    };
+    // Lays out the children in the order-modified document order, the sort is stable.
+    children.sort_by_key(|&(order, _)| order);
+    let children = children
+        .into_iter()
+        .map(|(_, node_id)| node_id)
+        .collect::<SmallVec<[NodeId; 8]>>();
+
+    if let Err(error) = ui_layout_tree.update_node_children(root_entity, entity, &children) {
+        errors.push(error);
+    }
 
-    ui_layout_tree.set_node_children(node_id, &node_children.collect::<Vec<_>>());
+    Some(node_id)
 }
 
 fn update_ui_geometry_recursive(
-    div_query: &Query<
-        (
-            Entity,
-            Ref<Div>,
-            Option<Ref<ContentSize>>,
-            Option<Ref<Children>>,
-        ),
-        With<ChildOf>,
-    >,
-    update_div_query: &mut Query<(&mut Transform, &mut ComputedLayout), With<Div>>,
+    div_query: &Query<(
+        Entity,
+        Ref<ComputedDiv>,
+        Option<Ref<ContentSize>>,
+        Option<Ref<TextFont>>,
+    )>,
+    ui_children: &UiChildren,
+    update_div_query: &mut Query<(&mut Transform, &mut ComputedLayout, &ScrollPosition), With<Div>>,
     ui_layout_tree: &mut UiLayoutTree,
-    (entity, _, _, children): (
+    (entity, div, ..): (
         Entity,
-        Ref<Div>,
+        Ref<ComputedDiv>,
         Option<Ref<ContentSize>>,
-        Option<Ref<Children>>,
+        Option<Ref<TextFont>>,
     ),
-    mut maybe_inherited: Option<(Transform, Vec2)>,
+    mut maybe_inherited: Option<(Vec2, Vec2)>,
+    maybe_root: Option<&UiRoot>,
 ) {
-    let (Ok(layout), Ok((mut transform, mut computed_layout))) = (
+    let (Ok(layout), Ok((mut transform, mut computed_layout, scroll_position))) = (
         ui_layout_tree.get_layout(entity),
         update_div_query.get_mut(entity),
     ) else {
@@ -365,22 +579,27 @@ fn update_ui_geometry_recursive(
         let bypass_computed_layout = computed_layout.bypass_change_detection();
         let prev_location = bypass_computed_layout.location;
         let prev_size = bypass_computed_layout.size;
+        let prev_scroll_position = bypass_computed_layout.scroll_position;
 
         bypass_computed_layout.update(layout);
-        // bypass_computed_node.set_corner_radii(style.corner_radii);
 
-        // if let Some(outline) = style.outline {
-        //     bypass_computed_node.set_outline(outline);
-        // }
+        // Only scrolls along the axes with `Overflow::Scroll`.
+        bypass_computed_layout.scroll_position =
+            Vec2::select(div.scrollable(), scroll_position.0, Vec2::ZERO)
+                .clamp(Vec2::ZERO, bypass_computed_layout.max_scroll_position());
+        // bypass_computed_node.set_corner_radii(style.corner_radii);
 
-        if prev_location != computed_layout.location || prev_size != computed_layout.size {
+        if prev_location != computed_layout.location
+            || prev_size != computed_layout.size
+            || prev_scroll_position != computed_layout.scroll_position
+        {
             computed_layout.set_changed();
         }
     }
 
-    if let Some((_parent_transform, parent_size)) = maybe_inherited {
-        // @TODO(fundon): scrolling
-        let local_center = computed_layout.location + 0.5 * (computed_layout.size - parent_size);
+    if let Some((parent_size, parent_scroll_position)) = maybe_inherited {
+        let local_center = computed_layout.location - parent_scroll_position
+            + 0.5 * (computed_layout.size - parent_size);
         let local_center_flipped = local_center * FLIP_Y;
 
         let mut local_affine = computed_layout.affine;
@@ -398,22 +617,622 @@ fn update_ui_geometry_recursive(
             transform.translation.x = new_affine.translation.x;
             transform.translation.y = new_affine.translation.y;
 
+            computed_layout.affine = local_affine;
+        }
+    } else {
+        // The layout box of the root is placed on its transform by `place_ui_roots_system`,
+        // so the transform of the root stays as the user has set it.
+        let local_affine =
+            maybe_root.map_or(Affine3A::IDENTITY, |root| root.affine(computed_layout.size));
+
+        if local_affine != computed_layout.affine {
             computed_layout.affine = local_affine;
         }
     }
 
-    if let Some(children) = children {
-        // Updates its children.
-        maybe_inherited = Some((*transform, computed_layout.size));
+    // Updates its children, walks through the ghost nodes.
+    {
+        maybe_inherited = Some((computed_layout.size, computed_layout.scroll_position));
 
-        for item in div_query.iter_many(&children) {
+        for item in div_query.iter_many(ui_children.iter(entity)) {
             update_ui_geometry_recursive(
                 div_query,
+                ui_children,
                 update_div_query,
                 ui_layout_tree,
                 item,
                 maybe_inherited,
+                None,
             );
         }
     }
 }
+
+/// Places the layout box of each root on its transform, see [`UiRoot::affine`].
+///
+/// The affine of the root is applied to the propagated [`GlobalTransform`]s of the root and its
+/// descendants, instead of the [`Transform`] of the root, which is owned by the user.
+pub fn place_ui_roots_system(
+    ui_root_nodes: UiRootNodes,
+    root_query: Query<(&Transform, &ComputedLayout, Option<&ChildOf>)>,
+    children_query: Query<&Children>,
+    mut global_query: Query<&mut GlobalTransform>,
+) {
+    for root_entity in ui_root_nodes.iter() {
+        let Ok((transform, computed_layout, maybe_parent)) = root_query.get(root_entity) else {
+            continue;
+        };
+        let Ok(current_affine) = global_query.get(root_entity).map(GlobalTransform::affine) else {
+            continue;
+        };
+
+        // The propagated transform of the root, with its layout box placed on it.
+        let parent_affine = maybe_parent
+            .and_then(|child_of| global_query.get(child_of.parent()).ok())
+            .map_or(Affine3A::IDENTITY, GlobalTransform::affine);
+        let target_affine = parent_affine * transform.compute_affine() * computed_layout.affine;
+
+        // Already placed, and not propagated again since.
+        if current_affine == target_affine {
+            continue;
+        }
+
+        // The descendants are either propagated from the current transform of the root,
+        // or have been placed with it.
+        let delta = target_affine * current_affine.inverse();
+
+        if let Ok(mut global_transform) = global_query.get_mut(root_entity) {
+            *global_transform = GlobalTransform::from(target_affine);
+        }
+
+        for &child in children_query.get(root_entity).into_iter().flatten() {
+            place_ui_root_recursive(&children_query, &mut global_query, child, delta);
+        }
+    }
+}
+
+fn place_ui_root_recursive(
+    children_query: &Query<&Children>,
+    global_query: &mut Query<&mut GlobalTransform>,
+    entity: Entity,
+    delta: Affine3A,
+) {
+    if let Ok(mut global_transform) = global_query.get_mut(entity) {
+        *global_transform = GlobalTransform::from(delta * global_transform.affine());
+    }
+
+    for &child in children_query.get(entity).into_iter().flatten() {
+        place_ui_root_recursive(children_query, global_query, child, delta);
+    }
+}
+
+pub fn update_clipping_system(
+    mut commands: Commands,
+    ui_root_nodes: UiRootNodes,
+    ui_children: UiChildren,
+    mut div_query: Query<(
+        &ComputedDiv,
+        &GlobalTransform,
+        &ComputedLayout,
+        Option<&mut ComputedClip>,
+    )>,
+) {
+    for root_entity in ui_root_nodes.iter() {
+        update_clipping_recursive(
+            &mut commands,
+            &ui_children,
+            &mut div_query,
+            root_entity,
+            None,
+        );
+    }
+}
+
+fn update_clipping_recursive(
+    commands: &mut Commands,
+    ui_children: &UiChildren,
+    div_query: &mut Query<(
+        &ComputedDiv,
+        &GlobalTransform,
+        &ComputedLayout,
+        Option<&mut ComputedClip>,
+    )>,
+    entity: Entity,
+    maybe_inherited_clip: Option<&ComputedClip>,
+) {
+    let Ok((div, transform, computed_layout, maybe_computed_clip)) = div_query.get_mut(entity)
+    else {
+        return;
+    };
+
+    // Updates the clip inherited from its ancestors.
+    match (maybe_inherited_clip, maybe_computed_clip) {
+        (Some(inherited_clip), Some(mut computed_clip)) => {
+            computed_clip.set_if_neq(inherited_clip.clone());
+        }
+        (Some(inherited_clip), None) => {
+            commands.entity(entity).try_insert(inherited_clip.clone());
+        }
+        (None, Some(_)) => {
+            commands.entity(entity).try_remove::<ComputedClip>();
+        }
+        (None, None) => {}
+    }
+
+    let clip = div.clipped();
+
+    // Clips its children to its padding box.
+    let children_clip = clip.any().then(|| {
+        let clip = ComputedClip::from_padding_box(
+            transform.affine(),
+            computed_layout,
+            Vec4::from_array(div.corner_radii.resolve(computed_layout.size)),
+            clip,
+        );
+
+        match maybe_inherited_clip {
+            Some(inherited_clip) => inherited_clip.intersect(&clip),
+            None => clip,
+        }
+    });
+    let maybe_children_clip = children_clip.as_ref().or(maybe_inherited_clip);
+
+    for child in ui_children.iter(entity) {
+        update_clipping_recursive(commands, ui_children, div_query, child, maybe_children_clip);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{
+        components::{computed::update_computed_divs, root::UiRoot},
+        prelude::div,
+        theme::MoonTheme,
+    };
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    pub(crate) fn setup_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<UiLayoutTree>();
+        world.init_resource::<UiLayoutSettings>();
+        world.init_resource::<UiStackMap>();
+        world.init_resource::<UiDirtyRoots>();
+        world.init_resource::<UiRecomputeCounter>();
+        world.init_resource::<FontCx>();
+        world.init_resource::<MoonTheme>();
+        world.insert_resource(RemSize(16.0));
+        world
+    }
+
+    pub(crate) fn spawn_camera(
+        world: &mut World,
+        scale_factor: f32,
+        physical_size: Vec2,
+    ) -> Entity {
+        world
+            .spawn(ComputedTargetInfo {
+                scale_factor,
+                physical_size,
+            })
+            .id()
+    }
+
+    /// Renders the roots with the cameras, in the given order.
+    pub(crate) fn render_with(world: &mut World, roots: &[Entity], cameras: &[Entity]) {
+        let mut ui_stack_map = world.resource_mut::<UiStackMap>();
+        ui_stack_map.clear();
+
+        for &camera in cameras {
+            let ui_stack = ui_stack_map.entry(camera).or_default();
+
+            for &root in roots {
+                let index = root.index_u32() as usize;
+                ui_stack.bitset.grow(index + 1);
+                ui_stack.bitset.insert(index);
+                ui_stack.roots.push(root);
+                ui_stack.entities.push(root);
+            }
+        }
+    }
+
+    /// Resolves the styles of the nodes, and lays them out.
+    fn run_layout(world: &mut World) {
+        world.run_system_once(update_computed_divs).unwrap();
+        world.run_system_once(ui_layout_system).unwrap();
+    }
+
+    fn layout_size(world: &mut World, root: Entity) -> Vec2 {
+        run_layout(world);
+        world.get::<ComputedLayout>(root).unwrap().size
+    }
+
+    #[test]
+    fn test_layout_with_two_cameras() {
+        let mut world = setup_world();
+
+        let main_camera = spawn_camera(&mut world, 1.0, Vec2::new(800.0, 600.0));
+        let hidpi_camera = spawn_camera(&mut world, 2.0, Vec2::new(800.0, 600.0));
+        let root = world.spawn(div().w_full().h_full()).id();
+
+        // The camera with the higher scale factor is preferred.
+        render_with(&mut world, &[root], &[main_camera, hidpi_camera]);
+        assert_eq!(layout_size(&mut world, root), Vec2::new(400.0, 300.0));
+
+        render_with(&mut world, &[root], &[hidpi_camera, main_camera]);
+        assert_eq!(layout_size(&mut world, root), Vec2::new(400.0, 300.0));
+
+        // The larger viewport is preferred with the same scale factor.
+        let small_camera = spawn_camera(&mut world, 1.0, Vec2::new(320.0, 240.0));
+
+        render_with(&mut world, &[root], &[small_camera, main_camera]);
+        assert_eq!(layout_size(&mut world, root), Vec2::new(800.0, 600.0));
+
+        render_with(&mut world, &[root], &[main_camera, small_camera]);
+        assert_eq!(layout_size(&mut world, root), Vec2::new(800.0, 600.0));
+
+        render_with(&mut world, &[root], &[small_camera]);
+        assert_eq!(layout_size(&mut world, root), Vec2::new(320.0, 240.0));
+    }
+
+    #[test]
+    fn test_layout_of_ui_root_with_two_cameras() {
+        let mut world = setup_world();
+
+        let main_camera = spawn_camera(&mut world, 1.0, Vec2::new(800.0, 600.0));
+        let small_camera = spawn_camera(&mut world, 2.0, Vec2::new(320.0, 240.0));
+        let root = world
+            .spawn((
+                div().w_full().h_full(),
+                UiRoot::new(Vec2::new(100.0, 50.0)).with_units_per_pixel(0.5),
+            ))
+            .id();
+
+        render_with(&mut world, &[root], &[main_camera]);
+        assert_eq!(layout_size(&mut world, root), Vec2::new(200.0, 100.0));
+
+        render_with(&mut world, &[root], &[main_camera, small_camera]);
+        assert_eq!(layout_size(&mut world, root), Vec2::new(200.0, 100.0));
+
+        render_with(&mut world, &[root], &[small_camera]);
+        assert_eq!(layout_size(&mut world, root), Vec2::new(200.0, 100.0));
+
+        // The transform of the root is left to the user.
+        assert_eq!(*world.get::<Transform>(root).unwrap(), Transform::IDENTITY);
+        assert_eq!(
+            world.get::<ComputedLayout>(root).unwrap().affine,
+            Affine3A::from_scale(bevy_math::Vec3::splat(0.5))
+        );
+    }
+
+    #[test]
+    fn test_place_ui_roots() {
+        use bevy_ecs::{children, schedule::Schedule};
+        use bevy_math::Vec3;
+        use bevy_sprite::Anchor;
+
+        let mut world = setup_world();
+
+        let camera = spawn_camera(&mut world, 1.0, Vec2::new(800.0, 600.0));
+        let root = world
+            .spawn((
+                div().w(200.0).h(100.0),
+                UiRoot::new(Vec2::new(2.0, 1.0))
+                    .with_anchor(Anchor::TOP_LEFT)
+                    .with_units_per_pixel(0.01),
+                Transform::from_xyz(5.0, 0.0, 0.0),
+                children![div().w(20.0).h(10.0)],
+            ))
+            .id();
+        let child = world.get::<Children>(root).unwrap()[0];
+
+        render_with(&mut world, &[root], &[camera]);
+        run_layout(&mut world);
+
+        // Propagates the transforms, as `TransformSystems::Propagate` does.
+        let propagate = |world: &mut World| {
+            let root_transform = GlobalTransform::from(*world.get::<Transform>(root).unwrap());
+            let child_transform = root_transform * *world.get::<Transform>(child).unwrap();
+            *world.get_mut::<GlobalTransform>(root).unwrap() = root_transform;
+            *world.get_mut::<GlobalTransform>(child).unwrap() = child_transform;
+        };
+        let top_left = |world: &World, entity: Entity| {
+            let size = world.get::<ComputedLayout>(entity).unwrap().size;
+            world
+                .get::<GlobalTransform>(entity)
+                .unwrap()
+                .transform_point((size * Vec2::new(-0.5, 0.5)).extend(0.0))
+        };
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(place_ui_roots_system);
+
+        propagate(&mut world);
+        schedule.run(&mut world);
+
+        // The top-left corners of the root and its first child sit on the transform of the root.
+        assert!(top_left(&world, root).abs_diff_eq(Vec3::new(5.0, 0.0, 0.0), 1e-5));
+        assert!(top_left(&world, child).abs_diff_eq(Vec3::new(5.0, 0.0, 0.0), 1e-5));
+
+        // The placement does not compound, whether the transforms are propagated again or not.
+        let placed = *world.get::<GlobalTransform>(child).unwrap();
+        schedule.run(&mut world);
+        assert_eq!(*world.get::<GlobalTransform>(child).unwrap(), placed);
+
+        propagate(&mut world);
+        schedule.run(&mut world);
+        assert!(top_left(&world, child).abs_diff_eq(Vec3::new(5.0, 0.0, 0.0), 1e-5));
+
+        assert_eq!(
+            *world.get::<Transform>(root).unwrap(),
+            Transform::from_xyz(5.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_relayout_dirty_roots_only() {
+        use bevy_ecs::{children, hierarchy::Children, schedule::Schedule};
+
+        let mut world = setup_world();
+
+        let camera = spawn_camera(&mut world, 1.0, Vec2::new(800.0, 600.0));
+        let root_a = world.spawn(div().w(10.0).h(10.0)).id();
+        let root_b = world
+            .spawn((div().w(20.0).h(20.0), children![div().w(5.0).h(5.0)]))
+            .id();
+        let child = world.get::<Children>(root_b).unwrap()[0];
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                update_computed_divs,
+                mark_dirty_roots_system,
+                ui_layout_system,
+            )
+                .chain(),
+        );
+
+        let mut relayout_roots = |world: &mut World| {
+            schedule.run(world);
+            world.resource::<UiRecomputeCounter>().relayout_roots
+        };
+
+        render_with(&mut world, &[root_a, root_b], &[camera]);
+        assert_eq!(relayout_roots(&mut world), 2);
+
+        // Nothing has been changed.
+        assert_eq!(relayout_roots(&mut world), 0);
+
+        // Only the root of the changed node is laid out.
+        *world.get_mut::<Div>(child).unwrap() = div().w(8.0).h(5.0);
+        assert_eq!(relayout_roots(&mut world), 1);
+        assert_eq!(
+            world.get::<ComputedLayout>(child).unwrap().size,
+            Vec2::new(8.0, 5.0)
+        );
+        assert_eq!(relayout_roots(&mut world), 0);
+
+        // The target of the camera has been changed.
+        world
+            .get_mut::<ComputedTargetInfo>(camera)
+            .unwrap()
+            .physical_size = Vec2::new(400.0, 300.0);
+        assert_eq!(relayout_roots(&mut world), 2);
+
+        // A new root is rendered.
+        let root_c = world.spawn(div().w(30.0).h(30.0)).id();
+        assert_eq!(relayout_roots(&mut world), 0);
+
+        render_with(&mut world, &[root_a, root_b, root_c], &[camera]);
+        assert_eq!(relayout_roots(&mut world), 1);
+        assert_eq!(
+            world.get::<ComputedLayout>(root_c).unwrap().size,
+            Vec2::new(30.0, 30.0)
+        );
+    }
+
+    #[test]
+    fn test_relayout_relative_lengths() {
+        use bevy_ecs::{children, hierarchy::Children, schedule::Schedule};
+
+        let mut world = setup_world();
+
+        let camera = spawn_camera(&mut world, 1.0, Vec2::new(800.0, 600.0));
+        let panel = world
+            .spawn((
+                div().flex().p_rem(1.0),
+                children![div().w_rem(10.0).h_vh(10.0)],
+            ))
+            .id();
+        let fixed = world.spawn(div().w(10.0).h(10.0)).id();
+        let child = world.get::<Children>(panel).unwrap()[0];
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                update_computed_divs,
+                mark_dirty_roots_system,
+                ui_layout_system,
+            )
+                .chain(),
+        );
+
+        let mut relayout_roots = |world: &mut World| {
+            schedule.run(world);
+            world.resource::<UiRecomputeCounter>().relayout_roots
+        };
+        let size = |world: &World, entity| world.get::<ComputedLayout>(entity).unwrap().size;
+
+        render_with(&mut world, &[panel, fixed], &[camera]);
+        assert_eq!(relayout_roots(&mut world), 2);
+        assert_eq!(size(&world, child), Vec2::new(160.0, 60.0));
+        assert_eq!(size(&world, panel).y, 92.0);
+        assert_eq!(relayout_roots(&mut world), 0);
+
+        // The whole panel is scaled by the root font size.
+        world.resource_mut::<RemSize>().0 = 8.0;
+        assert_eq!(relayout_roots(&mut world), 2);
+        assert_eq!(size(&world, child), Vec2::new(80.0, 60.0));
+        assert_eq!(size(&world, panel).y, 76.0);
+        assert_eq!(size(&world, fixed), Vec2::new(10.0, 10.0));
+
+        // The root-relative lengths follow the size of the root.
+        world
+            .get_mut::<ComputedTargetInfo>(camera)
+            .unwrap()
+            .physical_size = Vec2::new(400.0, 300.0);
+        assert_eq!(relayout_roots(&mut world), 2);
+        assert_eq!(size(&world, child), Vec2::new(80.0, 30.0));
+    }
+
+    #[test]
+    fn test_parallel_layout_matches_serial() {
+        use bevy_ecs::children;
+        use bevy_tasks::{ComputeTaskPool, TaskPool};
+
+        use crate::measure::FixedMeasure;
+
+        ComputeTaskPool::get_or_init(TaskPool::default);
+
+        let layouts = |parallel: bool| {
+            let mut world = setup_world();
+            world.insert_resource(UiLayoutSettings { parallel });
+
+            let camera = spawn_camera(&mut world, 2.0, Vec2::new(1600.0, 1200.0));
+            let roots = (0..16)
+                .map(|index| {
+                    let width = 40.0 + index as f32 * 10.0;
+
+                    let mut content_size = ContentSize::default();
+                    content_size.set(FixedMeasure {
+                        size: Vec2::new(width * 0.5, 12.0),
+                    });
+
+                    let mut root = world.spawn((
+                        div().flex().flex_col().w(width).p_px(),
+                        children![
+                            div().w_full().h(10.0),
+                            (
+                                div().flex().flex_wrap(),
+                                children![
+                                    div().w(15.0).h(5.0),
+                                    div().w(15.0).h(5.0),
+                                    div().w(15.0).h(5.0),
+                                ],
+                            ),
+                            (div(), content_size),
+                        ],
+                    ));
+
+                    if index % 2 == 0 {
+                        root.insert(UiRoot::new(Vec2::new(200.0, 100.0)));
+                    }
+
+                    root.id()
+                })
+                .collect::<Vec<_>>();
+
+            render_with(&mut world, &roots, &[camera]);
+            run_layout(&mut world);
+
+            let mut layouts = world
+                .query::<(Entity, &ComputedLayout, &Transform)>()
+                .iter(&world)
+                .map(|(entity, layout, transform)| (entity, *layout, *transform))
+                .collect::<Vec<_>>();
+            layouts.sort_by_key(|(entity, ..)| *entity);
+            layouts
+        };
+
+        let serial = layouts(false);
+        let parallel = layouts(true);
+
+        assert_eq!(serial.len(), 16 * 7);
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn test_layout_order() {
+        use bevy_ecs::children;
+
+        let mut world = setup_world();
+
+        let camera = spawn_camera(&mut world, 1.0, Vec2::new(800.0, 600.0));
+        let first = world.spawn(div().w(10.0).h(10.0)).id();
+        let last = world.spawn(div().w(20.0).h(10.0).order_last()).id();
+        let root = world
+            .spawn((
+                div().flex().w(100.0).h(10.0),
+                children![div().w(30.0).h(10.0).order(-1)],
+            ))
+            .add_children(&[last, first])
+            .id();
+
+        render_with(&mut world, &[root], &[camera]);
+        run_layout(&mut world);
+
+        let location =
+            |world: &World, entity| world.get::<ComputedLayout>(entity).unwrap().location;
+        let lowest = world.get::<Children>(root).unwrap()[0];
+
+        assert_eq!(location(&world, lowest), Vec2::new(0.0, 0.0));
+        assert_eq!(location(&world, first), Vec2::new(30.0, 0.0));
+        assert_eq!(location(&world, last), Vec2::new(40.0, 0.0));
+    }
+
+    #[cfg(feature = "ghost_nodes")]
+    #[test]
+    fn test_layout_through_ghost_nodes() {
+        use bevy_ecs::{children, hierarchy::Children};
+
+        use crate::components::ghost::GhostNode;
+
+        let mut world = setup_world();
+
+        let camera = spawn_camera(&mut world, 1.0, Vec2::new(800.0, 600.0));
+        let root = world
+            .spawn((
+                div().flex().w(100.0).h(10.0),
+                children![
+                    div().w(10.0).h(10.0),
+                    (
+                        GhostNode,
+                        children![
+                            div().w(20.0).h(10.0),
+                            (GhostNode, children![div().w(30.0).h(10.0)]),
+                        ],
+                    ),
+                ],
+            ))
+            .id();
+
+        render_with(&mut world, &[root], &[camera]);
+        run_layout(&mut world);
+
+        let mut divs = Vec::new();
+        let mut stack = vec![root];
+
+        while let Some(entity) = stack.pop() {
+            if let Some(layout) = world.get::<ComputedLayout>(entity)
+                && entity != root
+            {
+                divs.push((layout.location, layout.size));
+            }
+            if let Some(children) = world.get::<Children>(entity) {
+                stack.extend(children.iter().rev());
+            }
+        }
+
+        divs.sort_by(|a, b| a.0.x.total_cmp(&b.0.x));
+
+        // The children of the ghost nodes are laid out as the children of the root.
+        assert_eq!(
+            divs,
+            vec![
+                (Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0)),
+                (Vec2::new(10.0, 0.0), Vec2::new(20.0, 10.0)),
+                (Vec2::new(30.0, 0.0), Vec2::new(30.0, 10.0)),
+            ]
+        );
+    }
+}
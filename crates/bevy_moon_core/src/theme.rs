@@ -0,0 +1,506 @@
+//! The design tokens of the nodes.
+//!
+//! ```ignore
+//! commands.spawn((
+//!     div()
+//!         .p_token("4")
+//!         .background_token("surface")
+//!         .rounded_token("lg")
+//!         .shadow_token("md")
+//!         .hover(|d| d.background_token("muted"))
+//!         .text_color_token("foreground")
+//!         .font_token("body"),
+//!     text("Hello Moon!"),
+//! ));
+//!
+//! // Switches to the dark theme at runtime.
+//! *theme = MoonTheme::dark();
+//! ```
+//!
+//! The tokens are looked up in the [`MoonTheme`] resource, and the nodes are restyled whenever
+//! the theme is changed. The color tokens fall back on the Tailwind palette, such as `sky-500`.
+
+use std::borrow::Cow;
+
+use bevy_color::{Color, palettes::tailwind::*};
+use bevy_ecs::{
+    change_detection::{DetectChangesMut, Mut},
+    prelude::ReflectResource,
+    resource::Resource,
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{Reflect, prelude::ReflectDefault};
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use bevy_text::{TextColor, TextFont};
+use smallvec::SmallVec;
+use taffy::{LengthPercentage, Rect, Size};
+
+use crate::{
+    class::parse_color,
+    components::div::Div,
+    style::{BoxShadow, Corners},
+    units::LengthProperty,
+};
+
+/// Whether a theme is light or dark.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Clone, Debug, Default, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum ThemeMode {
+    #[default]
+    Light,
+    Dark,
+}
+
+/// The named design tokens, which are referenced by the nodes.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct MoonTheme {
+    pub mode: ThemeMode,
+    pub colors: HashMap<String, Color>,
+    pub radii: HashMap<String, f32>,
+    pub spacing: HashMap<String, f32>,
+    pub shadows: HashMap<String, SmallVec<[BoxShadow; 2]>>,
+    pub fonts: HashMap<String, TextFont>,
+}
+
+impl Default for MoonTheme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl MoonTheme {
+    /// Returns the default theme of the mode.
+    pub fn new(mode: ThemeMode) -> Self {
+        match mode {
+            ThemeMode::Light => Self::light(),
+            ThemeMode::Dark => Self::dark(),
+        }
+    }
+
+    /// The default light theme.
+    pub fn light() -> Self {
+        Self::with_colors(
+            ThemeMode::Light,
+            [
+                ("background", Color::WHITE),
+                ("foreground", SLATE_950.into()),
+                ("surface", Color::WHITE),
+                ("surface-foreground", SLATE_950.into()),
+                ("muted", SLATE_100.into()),
+                ("muted-foreground", SLATE_500.into()),
+                ("border", SLATE_200.into()),
+                ("ring", SLATE_400.into()),
+                ("primary", SLATE_900.into()),
+                ("primary-foreground", SLATE_50.into()),
+                ("secondary", SLATE_100.into()),
+                ("secondary-foreground", SLATE_900.into()),
+                ("accent", SKY_500.into()),
+                ("accent-foreground", Color::WHITE),
+                ("destructive", RED_500.into()),
+                ("destructive-foreground", SLATE_50.into()),
+            ],
+        )
+    }
+
+    /// The default dark theme.
+    pub fn dark() -> Self {
+        Self::with_colors(
+            ThemeMode::Dark,
+            [
+                ("background", SLATE_950.into()),
+                ("foreground", SLATE_50.into()),
+                ("surface", SLATE_900.into()),
+                ("surface-foreground", SLATE_50.into()),
+                ("muted", SLATE_800.into()),
+                ("muted-foreground", SLATE_400.into()),
+                ("border", SLATE_800.into()),
+                ("ring", SLATE_600.into()),
+                ("primary", SLATE_50.into()),
+                ("primary-foreground", SLATE_900.into()),
+                ("secondary", SLATE_800.into()),
+                ("secondary-foreground", SLATE_50.into()),
+                ("accent", SKY_400.into()),
+                ("accent-foreground", SLATE_950.into()),
+                ("destructive", RED_700.into()),
+                ("destructive-foreground", SLATE_50.into()),
+            ],
+        )
+    }
+
+    fn with_colors<const N: usize>(mode: ThemeMode, colors: [(&str, Color); N]) -> Self {
+        fn named<T>((name, value): (&str, T)) -> (String, T) {
+            (String::from(name), value)
+        }
+
+        Self {
+            mode,
+            colors: colors.into_iter().map(named).collect(),
+            radii: [
+                ("none", 0.0),
+                ("xs", 2.0),
+                ("sm", 4.0),
+                ("md", 6.0),
+                ("lg", 8.0),
+                ("xl", 12.0),
+                ("2xl", 16.0),
+                ("3xl", 24.0),
+                ("4xl", 32.0),
+                ("full", Corners::FULL.top_left),
+            ]
+            .into_iter()
+            .map(named)
+            .collect(),
+            // The Tailwind spacing scale, such as `4` for 16 pixels.
+            spacing: [
+                0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0,
+                12.0, 14.0, 16.0, 20.0, 24.0, 28.0, 32.0, 36.0, 40.0, 44.0, 48.0, 52.0, 56.0, 60.0,
+                64.0, 72.0, 80.0, 96.0,
+            ]
+            .into_iter()
+            .map(|step: f32| (step.to_string(), 4.0 * step))
+            .chain([(String::from("px"), 1.0)])
+            .collect(),
+            shadows: [
+                ("none", &[][..]),
+                ("2xs", &BoxShadow::XS2[..]),
+                ("xs", &BoxShadow::XS[..]),
+                ("sm", &BoxShadow::SM[..]),
+                ("md", &BoxShadow::MD[..]),
+                ("lg", &BoxShadow::LG[..]),
+                ("xl", &BoxShadow::XL[..]),
+                ("2xl", &BoxShadow::XL2[..]),
+            ]
+            .into_iter()
+            .map(|(name, shadows)| (String::from(name), SmallVec::from_slice(shadows)))
+            .collect(),
+            fonts: [
+                ("body", TextFont::default().with_font_size(16.0)),
+                ("small", TextFont::default().with_font_size(14.0)),
+                ("heading", TextFont::default().with_font_size(24.0)),
+            ]
+            .into_iter()
+            .map(named)
+            .collect(),
+        }
+    }
+
+    /// Returns the color of the token, or of the Tailwind palette, such as `sky-500` or `white/50`.
+    pub fn color(&self, name: &str) -> Option<Color> {
+        self.colors.get(name).copied().or_else(|| parse_color(name))
+    }
+
+    pub fn radius(&self, name: &str) -> Option<f32> {
+        self.radii.get(name).copied()
+    }
+
+    pub fn spacing(&self, name: &str) -> Option<f32> {
+        self.spacing.get(name).copied()
+    }
+
+    pub fn shadow(&self, name: &str) -> Option<&SmallVec<[BoxShadow; 2]>> {
+        self.shadows.get(name)
+    }
+
+    pub fn font(&self, name: &str) -> Option<&TextFont> {
+        self.fonts.get(name)
+    }
+}
+
+/// The properties of a node which reference the tokens of the [`MoonTheme`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Clone, Debug, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum ThemeProperty {
+    Background,
+    BorderColor,
+    CornerRadii,
+    BoxShadow,
+    Padding,
+    Margin,
+    Gap,
+
+    /// The [`TextColor`] of the node.
+    TextColor,
+
+    /// The [`TextFont`] of the node.
+    Font,
+}
+
+impl ThemeProperty {
+    /// Returns `true` if the field of the property differs between the nodes.
+    fn differs(self, a: &Div, b: &Div) -> bool {
+        match self {
+            Self::Background => a.background != b.background,
+            Self::BorderColor => a.border_color != b.border_color,
+            Self::CornerRadii => a.corner_radii != b.corner_radii,
+            Self::BoxShadow => a.box_shadow != b.box_shadow,
+            Self::Padding => a.style.padding != b.style.padding,
+            Self::Margin => a.style.margin != b.style.margin,
+            Self::Gap => a.style.gap != b.style.gap,
+            Self::TextColor | Self::Font => false,
+        }
+    }
+
+    /// Applies the token on the node, returns `false` if the token is missing.
+    fn apply(
+        self,
+        theme: &MoonTheme,
+        name: &str,
+        div: &mut Div,
+        text_color: Option<&mut TextColor>,
+        text_font: Option<&mut TextFont>,
+    ) -> bool {
+        match self {
+            Self::Background => theme.color(name).map(|color| div.background = Some(color)),
+            Self::BorderColor => theme
+                .color(name)
+                .map(|color| div.border_color = Some(color)),
+            Self::CornerRadii => theme
+                .radius(name)
+                .map(|radius| div.corner_radii = Corners::all(radius)),
+            Self::BoxShadow => theme.shadow(name).map(|shadows| {
+                div.box_shadow = Some(shadows.clone()).filter(|shadows| !shadows.is_empty());
+            }),
+            Self::Padding => theme.spacing(name).map(|val| {
+                div.style.padding = Rect::length(val);
+                div.clear_relative_lengths(&LengthProperty::PADDING);
+            }),
+            Self::Margin => theme.spacing(name).map(|val| {
+                div.style.margin = Rect::length(val);
+                div.clear_relative_lengths(&LengthProperty::MARGIN);
+            }),
+            Self::Gap => theme.spacing(name).map(|val| {
+                div.style.gap = Size {
+                    width: LengthPercentage::length(val),
+                    height: LengthPercentage::length(val),
+                };
+                div.clear_relative_lengths(&LengthProperty::GAP);
+            }),
+            Self::TextColor => theme.color(name).map(|color| {
+                if let Some(text_color) = text_color {
+                    text_color.0 = color;
+                }
+            }),
+            Self::Font => theme.font(name).map(|font| {
+                if let Some(text_font) = text_font {
+                    text_font.clone_from(font);
+                }
+            }),
+        }
+        .is_some()
+    }
+}
+
+impl Div {
+    /// References a token of the [`MoonTheme`] for the property.
+    ///
+    /// The token takes over the value of the property which is set on the node,
+    /// except in the style variants, see [`Div::hover`].
+    pub fn token(mut self, property: ThemeProperty, name: impl Into<Cow<'static, str>>) -> Self {
+        let name = name.into();
+
+        match self.tokens.iter_mut().find(|(other, _)| *other == property) {
+            Some((_, other)) => *other = name,
+            None => self.tokens.push((property, name)),
+        }
+        self
+    }
+
+    /// Removes the token of the property.
+    pub fn without_token(mut self, property: ThemeProperty) -> Self {
+        self.tokens.retain(|(other, _)| *other != property);
+        self
+    }
+
+    /// Returns `true` if the node references some tokens, see [`Div::token`].
+    pub fn has_tokens(&self) -> bool {
+        !self.tokens.is_empty()
+    }
+
+    /// Removes the tokens of the properties which are set by a style variant,
+    /// so the variant wins over the tokens of the base style.
+    pub(crate) fn drop_overridden_tokens(&mut self, base: &Div) {
+        let overridden = self
+            .tokens
+            .iter()
+            .filter(|token| base.tokens.contains(token) && token.0.differs(base, self))
+            .map(|token| token.0)
+            .collect::<SmallVec<[ThemeProperty; 2]>>();
+
+        self.tokens
+            .retain(|(property, _)| !overridden.contains(property));
+    }
+
+    pub fn background_token(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.token(ThemeProperty::Background, name)
+    }
+
+    pub fn border_color_token(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.token(ThemeProperty::BorderColor, name)
+    }
+
+    pub fn rounded_token(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.token(ThemeProperty::CornerRadii, name)
+    }
+
+    pub fn shadow_token(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.token(ThemeProperty::BoxShadow, name)
+    }
+
+    pub fn p_token(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.token(ThemeProperty::Padding, name)
+    }
+
+    pub fn m_token(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.token(ThemeProperty::Margin, name)
+    }
+
+    pub fn gap_token(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.token(ThemeProperty::Gap, name)
+    }
+
+    pub fn text_color_token(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.token(ThemeProperty::TextColor, name)
+    }
+
+    pub fn font_token(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.token(ThemeProperty::Font, name)
+    }
+}
+
+impl Div {
+    /// Resolves the tokens of the node with the theme, see
+    /// [`ComputedDiv`](crate::prelude::ComputedDiv).
+    ///
+    /// The text color and the font of the node are only changed when their values differ.
+    pub(crate) fn apply_tokens(
+        &mut self,
+        theme: &MoonTheme,
+        text_color: Option<Mut<TextColor>>,
+        text_font: Option<Mut<TextFont>>,
+    ) {
+        if !self.has_tokens() {
+            return;
+        }
+
+        let mut resolved_text_color = text_color.as_deref().cloned();
+        let mut resolved_text_font = text_font.as_deref().cloned();
+
+        let tokens = core::mem::take(&mut self.tokens);
+        for (property, name) in &tokens {
+            if !property.apply(
+                theme,
+                name,
+                self,
+                resolved_text_color.as_mut(),
+                resolved_text_font.as_mut(),
+            ) {
+                tracing::warn!("missing theme token `{name}` for {property:?}");
+            }
+        }
+        self.tokens = tokens;
+
+        // Only triggers change detection when the values have been changed.
+        if let (Some(mut text_color), Some(resolved)) = (text_color, resolved_text_color) {
+            text_color.set_if_neq(resolved);
+        }
+        if let (Some(mut text_font), Some(resolved)) = (text_font, resolved_text_font) {
+            text_font.set_if_neq(resolved);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{schedule::Schedule, world::World};
+
+    use super::*;
+    use crate::{
+        components::computed::{ComputedDiv, update_computed_divs},
+        prelude::div,
+    };
+
+    fn card() -> Div {
+        div()
+            .p_token("4")
+            .background_token("surface")
+            .rounded_token("lg")
+            .shadow_token("none")
+            .text_color_token("foreground")
+    }
+
+    #[test]
+    fn test_theme_tokens() {
+        let theme = MoonTheme::light();
+
+        assert_eq!(theme.color("surface"), Some(Color::WHITE));
+        assert_eq!(theme.color("sky-500"), Some(SKY_500.into()));
+        assert_eq!(theme.color("missing"), None);
+        assert_eq!(theme.spacing("4"), Some(16.0));
+        assert_eq!(theme.spacing("0.5"), Some(2.0));
+        assert_eq!(theme.radius("lg"), Some(8.0));
+        assert!(theme.shadow("none").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_theme_tokens() {
+        let mut world = World::new();
+        world.insert_resource(MoonTheme::light());
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_computed_divs);
+
+        let node = world.spawn((card(), TextColor::default())).id();
+        schedule.run(&mut world);
+
+        let current = world.get::<ComputedDiv>(node).unwrap();
+        assert_eq!(current.background, Some(Color::WHITE));
+        assert_eq!(current.corner_radii, Corners::all(8.0));
+        assert_eq!(current.style().padding, Rect::length(16.0));
+        assert_eq!(current.box_shadow, None);
+        assert_eq!(
+            world.get::<TextColor>(node).unwrap().0,
+            Color::from(SLATE_950)
+        );
+
+        // Restyles the nodes when the theme is switched.
+        world.insert_resource(MoonTheme::dark());
+        schedule.run(&mut world);
+
+        assert_eq!(
+            world.get::<ComputedDiv>(node).unwrap().background,
+            Some(SLATE_900.into())
+        );
+
+        // The tokens are kept on the node.
+        assert_eq!(world.get::<Div>(node), Some(&card()));
+        assert_eq!(
+            world.get::<TextColor>(node).unwrap().0,
+            Color::from(SLATE_50)
+        );
+    }
+
+    #[test]
+    fn test_variant_overrides_token() {
+        let has_token = |div: &Div, property| div.tokens.iter().any(|token| token.0 == property);
+
+        let base = card();
+        let mut variant = base.clone().background(SKY_500).rounded_token("xl");
+        variant.drop_overridden_tokens(&base);
+
+        assert!(!has_token(&variant, ThemeProperty::Background));
+        assert!(has_token(&variant, ThemeProperty::CornerRadii));
+        assert!(has_token(&variant, ThemeProperty::Padding));
+    }
+}
@@ -0,0 +1,391 @@
+//! The relative lengths of the nodes.
+//!
+//! ```ignore
+//! div().w_rem(20.0).p_em(1.0).h_vh(50.0)
+//! ```
+//!
+//! The lengths are relative to [`RemSize`](bevy_text::RemSize), to the font size of the node's
+//! [`TextFont`](bevy_text::TextFont), or to the size of the UI root, and are resolved into
+//! absolute lengths before the nodes are laid out. The nodes are laid out again when one of
+//! them changes, so a whole panel in `rem` is scaled by changing the `RemSize`.
+
+use bevy_math::Vec2;
+use bevy_reflect::{Reflect, prelude::ReflectDefault};
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use taffy::{Dimension, LengthPercentage, LengthPercentageAuto, Style};
+
+use crate::components::div::Div;
+
+/// A length relative to the font sizes or to the size of the UI root.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum RelativeLength {
+    /// Multiples of the root font size, [`RemSize`](bevy_text::RemSize).
+    Rem(f32),
+
+    /// Multiples of the font size of the node, or of the root font size without a font.
+    Em(f32),
+
+    /// Percentages of the width of the root.
+    Vw(f32),
+
+    /// Percentages of the height of the root.
+    Vh(f32),
+
+    /// Percentages of the smaller side of the root.
+    Vmin(f32),
+
+    /// Percentages of the larger side of the root.
+    Vmax(f32),
+}
+
+impl Default for RelativeLength {
+    fn default() -> Self {
+        Self::Rem(0.0)
+    }
+}
+
+impl RelativeLength {
+    /// Resolves the length in layout units.
+    pub fn resolve(self, context: &UnitContext) -> f32 {
+        match self {
+            Self::Rem(value) => value * context.rem,
+            Self::Em(value) => value * context.em,
+            Self::Vw(value) => value * 0.01 * context.root_size.x,
+            Self::Vh(value) => value * 0.01 * context.root_size.y,
+            Self::Vmin(value) => value * 0.01 * context.root_size.min_element(),
+            Self::Vmax(value) => value * 0.01 * context.root_size.max_element(),
+        }
+    }
+}
+
+/// The sizes which the relative lengths of a node are resolved against.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UnitContext {
+    /// The root font size.
+    pub rem: f32,
+
+    /// The font size of the node.
+    pub em: f32,
+
+    /// The available size of the root, in layout units.
+    ///
+    /// An axis which is sized to fit its content counts as zero.
+    pub root_size: Vec2,
+}
+
+impl UnitContext {
+    pub fn new(rem: f32, root_size: Vec2) -> Self {
+        Self {
+            rem,
+            em: rem,
+            root_size: Vec2::select(root_size.is_finite_mask(), root_size, Vec2::ZERO),
+        }
+    }
+
+    pub fn with_em(mut self, em: f32) -> Self {
+        self.em = em;
+        self
+    }
+}
+
+/// A length property of the layout style.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Clone, Debug, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum LengthProperty {
+    Width,
+    Height,
+    MinWidth,
+    MinHeight,
+    MaxWidth,
+    MaxHeight,
+    FlexBasis,
+    Top,
+    Right,
+    Bottom,
+    Left,
+    MarginTop,
+    MarginRight,
+    MarginBottom,
+    MarginLeft,
+    PaddingTop,
+    PaddingRight,
+    PaddingBottom,
+    PaddingLeft,
+    BorderTop,
+    BorderRight,
+    BorderBottom,
+    BorderLeft,
+    GapX,
+    GapY,
+}
+
+impl LengthProperty {
+    pub const SIZE: [Self; 2] = [Self::Width, Self::Height];
+    pub const INSET: [Self; 4] = [Self::Top, Self::Right, Self::Bottom, Self::Left];
+    pub const MARGIN: [Self; 4] = [
+        Self::MarginTop,
+        Self::MarginRight,
+        Self::MarginBottom,
+        Self::MarginLeft,
+    ];
+    pub const PADDING: [Self; 4] = [
+        Self::PaddingTop,
+        Self::PaddingRight,
+        Self::PaddingBottom,
+        Self::PaddingLeft,
+    ];
+    pub const BORDER: [Self; 4] = [
+        Self::BorderTop,
+        Self::BorderRight,
+        Self::BorderBottom,
+        Self::BorderLeft,
+    ];
+    pub const GAP: [Self; 2] = [Self::GapX, Self::GapY];
+
+    fn field(self, style: &mut Style) -> Field<'_> {
+        match self {
+            Self::Width => Field::Dimension(&mut style.size.width),
+            Self::Height => Field::Dimension(&mut style.size.height),
+            Self::MinWidth => Field::Dimension(&mut style.min_size.width),
+            Self::MinHeight => Field::Dimension(&mut style.min_size.height),
+            Self::MaxWidth => Field::Dimension(&mut style.max_size.width),
+            Self::MaxHeight => Field::Dimension(&mut style.max_size.height),
+            Self::FlexBasis => Field::Dimension(&mut style.flex_basis),
+            Self::Top => Field::LengthPercentageAuto(&mut style.inset.top),
+            Self::Right => Field::LengthPercentageAuto(&mut style.inset.right),
+            Self::Bottom => Field::LengthPercentageAuto(&mut style.inset.bottom),
+            Self::Left => Field::LengthPercentageAuto(&mut style.inset.left),
+            Self::MarginTop => Field::LengthPercentageAuto(&mut style.margin.top),
+            Self::MarginRight => Field::LengthPercentageAuto(&mut style.margin.right),
+            Self::MarginBottom => Field::LengthPercentageAuto(&mut style.margin.bottom),
+            Self::MarginLeft => Field::LengthPercentageAuto(&mut style.margin.left),
+            Self::PaddingTop => Field::LengthPercentage(&mut style.padding.top),
+            Self::PaddingRight => Field::LengthPercentage(&mut style.padding.right),
+            Self::PaddingBottom => Field::LengthPercentage(&mut style.padding.bottom),
+            Self::PaddingLeft => Field::LengthPercentage(&mut style.padding.left),
+            Self::BorderTop => Field::LengthPercentage(&mut style.border.top),
+            Self::BorderRight => Field::LengthPercentage(&mut style.border.right),
+            Self::BorderBottom => Field::LengthPercentage(&mut style.border.bottom),
+            Self::BorderLeft => Field::LengthPercentage(&mut style.border.left),
+            Self::GapX => Field::LengthPercentage(&mut style.gap.width),
+            Self::GapY => Field::LengthPercentage(&mut style.gap.height),
+        }
+    }
+
+    /// Sets the property to a length.
+    fn set(self, style: &mut Style, value: f32) {
+        match self.field(style) {
+            Field::Dimension(field) => *field = Dimension::length(value),
+            Field::LengthPercentage(field) => *field = LengthPercentage::length(value),
+            Field::LengthPercentageAuto(field) => *field = LengthPercentageAuto::length(value),
+        }
+    }
+
+    /// The bit of the property in the mask of the relative lengths of a node.
+    const fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+enum Field<'a> {
+    Dimension(&'a mut Dimension),
+    LengthPercentage(&'a mut LengthPercentage),
+    LengthPercentageAuto(&'a mut LengthPercentageAuto),
+}
+
+impl Div {
+    /// Sets the properties to a relative length, which is resolved before the layout.
+    ///
+    /// A later absolute length of a property, e.g. [`Div::w`], takes over the relative one.
+    pub fn relative_length(
+        mut self,
+        properties: impl IntoIterator<Item = LengthProperty>,
+        length: RelativeLength,
+    ) -> Self {
+        for property in properties {
+            self.set_relative_length(property, length);
+        }
+        self
+    }
+
+    pub(crate) fn set_relative_length(&mut self, property: LengthProperty, length: RelativeLength) {
+        self.relative_mask |= property.bit();
+
+        match self
+            .relative_lengths
+            .iter_mut()
+            .find(|(other, _)| *other == property)
+        {
+            Some((_, other)) => *other = length,
+            None => self.relative_lengths.push((property, length)),
+        }
+    }
+
+    /// Returns `true` if some lengths are relative, see [`Div::relative_length`].
+    pub fn has_relative_lengths(&self) -> bool {
+        self.relative_mask != 0
+    }
+
+    /// Returns `true` if the property is set to a relative length, see [`Div::relative_length`].
+    pub fn is_relative(&self, property: LengthProperty) -> bool {
+        self.relative_mask & property.bit() != 0
+    }
+
+    /// Drops the relative lengths of the properties, which are set to absolute lengths.
+    pub(crate) const fn clear_relative_lengths(&mut self, properties: &[LengthProperty]) {
+        let mut i = 0;
+        while i < properties.len() {
+            self.relative_mask &= !properties[i].bit();
+            i += 1;
+        }
+    }
+
+    /// Returns the layout style, with the relative lengths resolved in the context.
+    pub fn resolve_style(&self, context: &UnitContext) -> Style {
+        let mut style = self.style.0.clone();
+
+        for &(property, length) in &self.relative_lengths {
+            if self.is_relative(property) {
+                property.set(&mut style, length.resolve(context));
+            }
+        }
+
+        style
+    }
+
+    pub fn w_rem(self, val: f32) -> Self {
+        self.relative_length([LengthProperty::Width], RelativeLength::Rem(val))
+    }
+
+    pub fn h_rem(self, val: f32) -> Self {
+        self.relative_length([LengthProperty::Height], RelativeLength::Rem(val))
+    }
+
+    pub fn size_rem(self, val: f32) -> Self {
+        self.relative_length(LengthProperty::SIZE, RelativeLength::Rem(val))
+    }
+
+    pub fn m_rem(self, val: f32) -> Self {
+        self.relative_length(LengthProperty::MARGIN, RelativeLength::Rem(val))
+    }
+
+    pub fn p_rem(self, val: f32) -> Self {
+        self.relative_length(LengthProperty::PADDING, RelativeLength::Rem(val))
+    }
+
+    pub fn gap_rem(self, val: f32) -> Self {
+        self.relative_length(LengthProperty::GAP, RelativeLength::Rem(val))
+    }
+
+    pub fn w_em(self, val: f32) -> Self {
+        self.relative_length([LengthProperty::Width], RelativeLength::Em(val))
+    }
+
+    pub fn h_em(self, val: f32) -> Self {
+        self.relative_length([LengthProperty::Height], RelativeLength::Em(val))
+    }
+
+    pub fn m_em(self, val: f32) -> Self {
+        self.relative_length(LengthProperty::MARGIN, RelativeLength::Em(val))
+    }
+
+    pub fn p_em(self, val: f32) -> Self {
+        self.relative_length(LengthProperty::PADDING, RelativeLength::Em(val))
+    }
+
+    pub fn gap_em(self, val: f32) -> Self {
+        self.relative_length(LengthProperty::GAP, RelativeLength::Em(val))
+    }
+
+    /// Sets the width in percentages of the root's width.
+    pub fn w_vw(self, val: f32) -> Self {
+        self.relative_length([LengthProperty::Width], RelativeLength::Vw(val))
+    }
+
+    /// Sets the height in percentages of the root's height.
+    pub fn h_vh(self, val: f32) -> Self {
+        self.relative_length([LengthProperty::Height], RelativeLength::Vh(val))
+    }
+
+    /// Sets the size to the root's size.
+    pub fn size_screen(self) -> Self {
+        self.w_vw(100.0).h_vh(100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::div;
+
+    #[test]
+    fn test_resolve_relative_lengths() {
+        let context = UnitContext::new(16.0, Vec2::new(800.0, 600.0)).with_em(20.0);
+
+        let style = div()
+            .w_rem(10.0)
+            .h_vh(50.0)
+            .p_em(0.5)
+            .relative_length([LengthProperty::MaxWidth], RelativeLength::Vmin(10.0))
+            .resolve_style(&context);
+
+        assert_eq!(style.size.width, Dimension::length(160.0));
+        assert_eq!(style.size.height, Dimension::length(300.0));
+        assert_eq!(style.max_size.width, Dimension::length(60.0));
+        assert_eq!(style.padding.left, LengthPercentage::length(10.0));
+        assert_eq!(style.padding.top, LengthPercentage::length(10.0));
+
+        // Scales with the root font size.
+        let style = div()
+            .w_rem(10.0)
+            .resolve_style(&UnitContext::new(20.0, Vec2::INFINITY));
+
+        assert_eq!(style.size.width, Dimension::length(200.0));
+    }
+
+    #[test]
+    fn test_absolute_length_overrides_relative() {
+        let context = UnitContext::new(16.0, Vec2::INFINITY);
+
+        let node = div().w_rem(10.0).w(42.0).pt(3.0).p_rem(1.0).px(2.0);
+        let style = node.resolve_style(&context);
+
+        assert_eq!(style.size.width, Dimension::length(42.0));
+        assert_eq!(style.padding.top, LengthPercentage::length(16.0));
+        assert_eq!(style.padding.bottom, LengthPercentage::length(16.0));
+        assert_eq!(style.padding.left, LengthPercentage::length(2.0));
+        assert_eq!(style.padding.right, LengthPercentage::length(2.0));
+
+        // A not-a-number length is absolute as well.
+        let node = div().w_rem(10.0).w(f32::NAN);
+
+        assert!(!node.is_relative(LengthProperty::Width));
+        assert!(!node.has_relative_lengths());
+
+        // The classes set absolute lengths.
+        let node = div().p_rem(1.0).class("px-2");
+        let style = node.resolve_style(&context);
+
+        assert!(node.is_relative(LengthProperty::PaddingTop));
+        assert!(!node.is_relative(LengthProperty::PaddingLeft));
+        assert_eq!(style.padding.top, LengthPercentage::length(16.0));
+        assert_eq!(style.padding.left, LengthPercentage::length(8.0));
+
+        // A root which is sized to fit its content has no size.
+        let style = div().w_vw(50.0).resolve_style(&context);
+
+        assert_eq!(style.size.width, Dimension::length(0.0));
+    }
+}
@@ -0,0 +1,348 @@
+//! A debug overlay for the nodes.
+//!
+//! Draws the box model of every [`Div`] in world space with [`bevy_gizmos`]: the border box,
+//! the padding box inside the border widths and the content box inside the padding.
+//! The stack index of each node is labeled at its top-left corner with a [`Text2d`], which is
+//! not a node, so the labels are neither stacked nor laid out with the nodes. The node under the cursor
+//! is highlighted, and the quad, atlas and shadow instances can be drawn as wireframes.
+//!
+//! ```ignore
+//! app.add_plugins(MoonDebugPlugin)
+//!     .insert_resource(MoonDebugOverlay {
+//!         enabled: true,
+//!         ..MoonDebugOverlay::DEFAULT
+//!     });
+//! ```
+
+use bevy_app::{App, Plugin, PostUpdate, Update};
+use bevy_camera::visibility::InheritedVisibility;
+use bevy_color::{Alpha, Color, palettes::tailwind};
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    component::Component,
+    entity::{Entity, EntityHashSet},
+    prelude::ReflectComponent,
+    query::With,
+    reflect::ReflectResource,
+    resource::Resource,
+    schedule::IntoScheduleConfigs,
+    system::{Commands, Local, Query, Res, ResMut},
+};
+use bevy_gizmos::{
+    AppGizmoBuilder, config::GizmoConfigGroup, config::GizmoConfigStore, gizmos::Gizmos,
+};
+use bevy_input::{ButtonInput, keyboard::KeyCode};
+use bevy_math::{Affine3A, Vec2, Vec4};
+use bevy_reflect::{Reflect, prelude::ReflectDefault};
+use bevy_render::{Extract, ExtractSchedule, RenderApp};
+use bevy_sprite::{Anchor, Text2d};
+use bevy_text::{TextBackgroundColor, TextColor, TextFont};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+use bevy_moon_core::prelude::{ComputedLayout, Div, UiSystems, geometry::FLIP_Y};
+
+use crate::pipelines::UiWireframe;
+
+/// The color of the border boxes.
+const BORDER_BOX_COLOR: Color = Color::Srgba(tailwind::ORANGE_400);
+
+/// The color of the padding boxes.
+const PADDING_BOX_COLOR: Color = Color::Srgba(tailwind::YELLOW_300);
+
+/// The color of the content boxes.
+const CONTENT_BOX_COLOR: Color = Color::Srgba(tailwind::SKY_400);
+
+/// The color of the hovered node.
+const HIGHLIGHT_COLOR: Color = Color::Srgba(tailwind::FUCHSIA_500);
+
+/// The font size of the stack index labels, in layout units.
+const LABEL_FONT_SIZE: f32 = 10.0;
+
+/// Lifts the labels above their nodes, in layout units.
+const LABEL_DEPTH_OFFSET: f32 = 0.01;
+
+/// Adds the debug overlay, which is toggled by [`MoonDebugOverlay`].
+pub struct MoonDebugPlugin;
+
+impl Plugin for MoonDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MoonDebugOverlay>()
+            .init_gizmo_group::<MoonDebugGizmos>();
+
+        // The labels are not pickable, they must not hide the nodes under them.
+        #[cfg(feature = "picking")]
+        app.register_required_components_with::<MoonDebugLabel, _>(|| {
+            bevy_picking::Pickable::IGNORE
+        });
+
+        // Draws the overlay on top of the scene.
+        app.world_mut()
+            .resource_mut::<GizmoConfigStore>()
+            .config_mut::<MoonDebugGizmos>()
+            .0
+            .depth_bias = -1.0;
+
+        app.add_systems(Update, toggle_debug_overlay).add_systems(
+            PostUpdate,
+            (
+                update_stack_index_labels.in_set(UiSystems::Prepare),
+                (draw_box_models, draw_hovered_nodes)
+                    .after(UiSystems::PostLayout)
+                    .run_if(debug_overlay_enabled),
+            ),
+        );
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.add_systems(ExtractSchedule, extract_wireframe);
+    }
+}
+
+/// Settings of the debug overlay.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct MoonDebugOverlay {
+    /// Shows the overlay.
+    pub enabled: bool,
+
+    /// The key which toggles [`MoonDebugOverlay::enabled`].
+    pub toggle_key: Option<KeyCode>,
+
+    /// Draws the border, padding and content boxes of the nodes.
+    pub box_model: bool,
+
+    /// Labels the nodes with their stack indices.
+    pub stack_indices: bool,
+
+    /// Draws the edges of the quad, atlas and shadow instances instead of shading them.
+    pub wireframe: bool,
+
+    /// Highlights the nodes under the pointers, requires the `picking` feature.
+    pub highlight_hovered: bool,
+}
+
+impl Default for MoonDebugOverlay {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl MoonDebugOverlay {
+    pub const DEFAULT: Self = Self {
+        enabled: false,
+        toggle_key: Some(KeyCode::F12),
+        box_model: true,
+        stack_indices: true,
+        wireframe: false,
+        highlight_hovered: true,
+    };
+}
+
+/// The gizmo group of the debug overlay.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+#[reflect(Default)]
+pub struct MoonDebugGizmos;
+
+/// Marks a label of the debug overlay, which shows the stack index of its target node.
+///
+/// The label is a [`Text2d`] which follows the node, it is not a node itself.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Debug, Clone)]
+pub struct MoonDebugLabel {
+    pub target: Entity,
+}
+
+fn debug_overlay_enabled(overlay: Res<MoonDebugOverlay>) -> bool {
+    overlay.enabled
+}
+
+fn toggle_debug_overlay(
+    keyboard: Option<Res<ButtonInput<KeyCode>>>,
+    mut overlay: ResMut<MoonDebugOverlay>,
+) {
+    let (Some(keyboard), Some(toggle_key)) = (keyboard, overlay.toggle_key) else {
+        return;
+    };
+
+    if keyboard.just_pressed(toggle_key) {
+        overlay.enabled = !overlay.enabled;
+    }
+}
+
+fn draw_box_models(
+    overlay: Res<MoonDebugOverlay>,
+    mut gizmos: Gizmos<MoonDebugGizmos>,
+    div_query: Query<(&GlobalTransform, &InheritedVisibility, &ComputedLayout), With<Div>>,
+) {
+    if !overlay.box_model {
+        return;
+    }
+
+    for (transform, inherited_visibility, computed_layout) in &div_query {
+        if !inherited_visibility.get() || computed_layout.is_empty() {
+            continue;
+        }
+
+        let affine = transform.affine();
+        let size = computed_layout.size;
+        let border_widths = computed_layout.border_widths;
+        let padding = computed_layout.padding;
+
+        draw_box(&mut gizmos, &affine, size, Vec4::ZERO, BORDER_BOX_COLOR);
+        if border_widths != Vec4::ZERO {
+            draw_box(&mut gizmos, &affine, size, border_widths, PADDING_BOX_COLOR);
+        }
+        if padding != Vec4::ZERO {
+            draw_box(
+                &mut gizmos,
+                &affine,
+                size,
+                border_widths + padding,
+                CONTENT_BOX_COLOR,
+            );
+        }
+    }
+}
+
+#[cfg(feature = "picking")]
+fn draw_hovered_nodes(
+    overlay: Res<MoonDebugOverlay>,
+    mut gizmos: Gizmos<MoonDebugGizmos>,
+    hover_map: Option<Res<bevy_picking::hover::HoverMap>>,
+    div_query: Query<(&GlobalTransform, &ComputedLayout), With<Div>>,
+) {
+    let Some(hover_map) = hover_map.filter(|_| overlay.highlight_hovered) else {
+        return;
+    };
+
+    for (transform, computed_layout) in
+        div_query.iter_many(hover_map.values().flat_map(|hits| hits.keys()))
+    {
+        let affine = transform.affine();
+        let size = computed_layout.size;
+
+        draw_box(&mut gizmos, &affine, size, Vec4::ZERO, HIGHLIGHT_COLOR);
+        draw_box(
+            &mut gizmos,
+            &affine,
+            size,
+            computed_layout.border_widths + computed_layout.padding,
+            HIGHLIGHT_COLOR,
+        );
+    }
+}
+
+#[cfg(not(feature = "picking"))]
+fn draw_hovered_nodes() {}
+
+/// Draws the box of the node inset by `[top, right, bottom, left]`.
+fn draw_box(
+    gizmos: &mut Gizmos<MoonDebugGizmos>,
+    affine: &Affine3A,
+    size: Vec2,
+    insets: Vec4,
+    color: Color,
+) {
+    // y-down, relative to the center of the node.
+    let min = Vec2::new(insets.w, insets.x) - 0.5 * size;
+    let max = 0.5 * size - Vec2::new(insets.y, insets.z);
+
+    if min.cmpge(max).any() {
+        return;
+    }
+
+    gizmos.linestrip(
+        [
+            min,
+            Vec2::new(max.x, min.y),
+            max,
+            Vec2::new(min.x, max.y),
+            min,
+        ]
+        .map(|point| affine.transform_point3((point * FLIP_Y).extend(0.0))),
+        color,
+    );
+}
+
+/// Spawns a label at the top-left corner of each visible node, and despawns the stale ones.
+///
+/// The labels follow the latest transforms of their nodes, before the nodes are laid out.
+fn update_stack_index_labels(
+    mut commands: Commands,
+    overlay: Res<MoonDebugOverlay>,
+    mut labeled: Local<EntityHashSet>,
+    div_query: Query<(
+        Entity,
+        &Div,
+        &GlobalTransform,
+        &InheritedVisibility,
+        &ComputedLayout,
+    )>,
+    mut label_query: Query<(Entity, &MoonDebugLabel, &mut Text2d, &mut Transform)>,
+) {
+    labeled.clear();
+
+    let enabled = overlay.enabled && overlay.stack_indices;
+
+    for (label_entity, label, mut text, mut transform) in &mut label_query {
+        let Some((_, node, target_transform, _, computed_layout)) = div_query
+            .get(label.target)
+            .ok()
+            .filter(|(.., inherited_visibility, computed_layout)| {
+                enabled && inherited_visibility.get() && !computed_layout.is_empty()
+            })
+        else {
+            commands.entity(label_entity).despawn();
+            continue;
+        };
+
+        labeled.insert(label.target);
+
+        let stack_index = node.stack_index.to_string();
+        if text.0 != stack_index {
+            text.0 = stack_index;
+        }
+
+        let affine = label_affine(target_transform, computed_layout);
+        transform.set_if_neq(Transform::from_matrix(affine.into()));
+    }
+
+    if !enabled {
+        return;
+    }
+
+    for (entity, node, transform, inherited_visibility, computed_layout) in &div_query {
+        if labeled.contains(&entity) || !inherited_visibility.get() || computed_layout.is_empty() {
+            continue;
+        }
+
+        let affine = label_affine(transform, computed_layout);
+
+        commands.spawn((
+            MoonDebugLabel { target: entity },
+            Text2d::new(node.stack_index.to_string()),
+            TextFont::default().with_font_size(LABEL_FONT_SIZE),
+            TextColor(HIGHLIGHT_COLOR),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.6)),
+            Anchor::TOP_LEFT,
+            Transform::from_matrix(affine.into()),
+        ));
+    }
+}
+
+/// Returns the affine at the top-left corner of the node, lifted above it.
+fn label_affine(transform: &GlobalTransform, computed_layout: &ComputedLayout) -> Affine3A {
+    let affine = transform.affine();
+    let top_left = (-0.5 * computed_layout.size * FLIP_Y).extend(LABEL_DEPTH_OFFSET);
+
+    Affine3A {
+        translation: affine.transform_point3(top_left).into(),
+        ..affine
+    }
+}
+
+fn extract_wireframe(overlay: Extract<Res<MoonDebugOverlay>>, mut wireframe: ResMut<UiWireframe>) {
+    wireframe.set_if_neq(UiWireframe(overlay.enabled && overlay.wireframe));
+}
@@ -1,6 +1,8 @@
 // Copyright © Fangdun Tsai <fundon@pindash.io>
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+#[cfg(feature = "debug")]
+pub mod debug;
 mod pipelines;
 mod plugin;
 mod render_pass;
@@ -9,6 +11,8 @@ mod transparent;
 mod view;
 
 pub mod prelude {
+    #[cfg(feature = "debug")]
+    pub use crate::debug::{MoonDebugGizmos, MoonDebugLabel, MoonDebugOverlay, MoonDebugPlugin};
     pub use crate::pipelines::ExtractUiSystems;
     pub use crate::plugin::MoonRenderPlugin;
 }
@@ -1,5 +1,7 @@
 use bytemuck::{Pod, Zeroable};
 
+use crate::pipelines::UiClip;
+
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
 pub struct UiAtlas {
@@ -18,6 +20,9 @@ pub struct UiAtlas {
     /// | Image | `[flags, ObjectFit, ObjectPosition.x, ObjectPosition.y]` |
     pub extra: [f32; 4],
     pub flipped: [u32; 2],
+
+    /// The clipping region inherited from the clipping ancestors.
+    pub clip: UiClip,
 }
 
 impl Default for UiAtlas {
@@ -35,6 +40,7 @@ impl UiAtlas {
         corner_radii: [0.0; 4],
         extra: [0.0; 4],
         flipped: [0; 2],
+        clip: UiClip::NONE,
     };
 
     /// The `text` instance.
@@ -15,10 +15,12 @@ use bevy_render::{
     },
     view::{ViewTarget, ViewUniform},
 };
-use bevy_shader::Shader;
+use bevy_shader::{Shader, ShaderDefVal};
 use bevy_sprite_render::Mesh2dPipelineKey;
 use bevy_utils::default;
 
+use crate::pipelines::UiClip;
+
 #[derive(Resource, Clone)]
 pub struct UiAtlasPipeline {
     pub view_layout: BindGroupLayoutDescriptor,
@@ -29,6 +31,8 @@ pub struct UiAtlasPipeline {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct UiAtlasPipelineKey {
     pub mesh_key: Mesh2dPipelineKey,
+    /// Draws the edges of the instances, see [`UiWireframe`](crate::pipelines::UiWireframe).
+    pub wireframe: bool,
     // pub anti_alias: bool,
 }
 
@@ -36,7 +40,11 @@ impl SpecializedRenderPipeline for UiAtlasPipeline {
     type Key = UiAtlasPipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
-        let shader_defs = vec![];
+        let mut shader_defs = Vec::<ShaderDefVal>::new();
+
+        if key.wireframe {
+            shader_defs.push("WIREFRAME".into());
+        }
 
         let mesh_key = key.mesh_key;
 
@@ -70,7 +78,10 @@ impl SpecializedRenderPipeline for UiAtlasPipeline {
                 VertexFormat::Float32x4,
                 // flipped
                 VertexFormat::Uint32x2,
-            ],
+            ]
+            .into_iter()
+            // clip_from_world and rect of the clipping regions
+            .chain(UiClip::VERTEX_FORMATS),
         );
 
         RenderPipelineDescriptor {
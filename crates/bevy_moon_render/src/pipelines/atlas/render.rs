@@ -20,7 +20,7 @@ use bevy_render::{
 use indexmap::IndexMap;
 
 use crate::{
-    pipelines::{UiTextureBindGroups, atlas::ExtractedUiAtlases},
+    pipelines::{UiTextureBindGroups, UiWireframe, atlas::ExtractedUiAtlases},
     transparent::{RenderPhasesFilter, TransparentUi},
     view::{MoonUiCameraView, MoonUiOptions, MoonUiViewTarget},
 };
@@ -39,6 +39,7 @@ pub fn queue_atlases(
     ui_atlas_pipeline: Res<UiAtlasPipeline>,
     pipeline_cache: Res<PipelineCache>,
     draw_functions: Res<DrawFunctions<TransparentUi>>,
+    wireframe: Res<UiWireframe>,
     mut pipelines: ResMut<SpecializedRenderPipelines<UiAtlasPipeline>>,
     mut render_phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
 ) {
@@ -63,7 +64,10 @@ pub fn queue_atlases(
         let pipeline = pipelines.specialize(
             &pipeline_cache,
             &ui_atlas_pipeline,
-            UiAtlasPipelineKey { mesh_key },
+            UiAtlasPipelineKey {
+                mesh_key,
+                wireframe: wireframe.0,
+            },
         );
 
         let view_index = div.entity.1.index_u32() as usize;
@@ -15,10 +15,10 @@ use bevy_transform::components::GlobalTransform;
 
 use bevy_moon_core::{
     geometry::{FLIP_X, FLIP_Y},
-    prelude::{ComputedLayout, Div, Image, Text, UiStackMap},
+    prelude::{ComputedClip, ComputedDiv, ComputedLayout, Image, Text, UiStackMap},
 };
 
-use crate::pipelines::{ExtractedUiInstance, atlas::ExtractedUiAtlases};
+use crate::pipelines::{ExtractedUiInstance, UiClip, atlas::ExtractedUiAtlases, layer};
 
 use super::UiAtlas;
 
@@ -32,21 +32,22 @@ pub fn extract_images(
             &GlobalTransform,
             &InheritedVisibility,
             &ComputedLayout,
-            &Div,
+            &ComputedDiv,
             &Image,
+            Option<&ComputedClip>,
         )>,
     >,
 ) {
     for (&camera_entity, ui_stack) in ui_stack_map.iter() {
-        for div in ui_stack
-            .ranges
+        for (stack_index, div) in ui_stack
             .iter()
-            .flat_map(|range| image_query.iter_many(&ui_stack.entities[range.clone()]))
+            .filter_map(|(stack_index, entity)| Some((stack_index, image_query.get(entity).ok()?)))
         {
             extract_single_image(
                 &mut commands,
                 &mut extracted_ui_instances,
                 div,
+                stack_index,
                 camera_entity,
             );
         }
@@ -56,14 +57,16 @@ pub fn extract_images(
 fn extract_single_image(
     commands: &mut Commands,
     extracted_ui_atlases: &mut ExtractedUiAtlases,
-    (entity, transform, inherited_visibility, computed_layout, div, image): (
+    (entity, transform, inherited_visibility, computed_layout, div, image, computed_clip): (
         Entity,
         &GlobalTransform,
         &InheritedVisibility,
         &ComputedLayout,
-        &Div,
+        &ComputedDiv,
         &Image,
+        Option<&ComputedClip>,
     ),
+    stack_index: usize,
     camera_entity: Entity,
 ) {
     if !inherited_visibility.get() {
@@ -79,11 +82,11 @@ fn extract_single_image(
         return;
     }
 
-    let index = div.stack_index as f32 + 0.01;
+    let index = stack_index as f32 + layer::IMAGE;
     let main_entity = entity.into();
     let size = computed_layout.size.to_array();
     let color = image.color.to_linear().to_f32_array();
-    let corner_radii = div.corner_radii.to_array(); // should be computed_layout.corner_radii
+    let corner_radii = div.corner_radii.resolve(computed_layout.size);
     let extra = [
         0.0,
         image.object_fit as isize as f32,
@@ -91,6 +94,7 @@ fn extract_single_image(
         image.object_position.y,
     ];
     let flipped = image.flipped.map(Into::into);
+    let clip = UiClip::new(computed_clip);
 
     let matrix = Mat4::from(transform.affine()).to_cols_array_2d();
 
@@ -109,6 +113,7 @@ fn extract_single_image(
             corner_radii,
             extra,
             flipped,
+            clip,
             ..UiAtlas::IMAGE
         },
     });
@@ -124,26 +129,27 @@ pub fn extract_texts(
             &GlobalTransform,
             &InheritedVisibility,
             &ComputedLayout,
-            &Div,
+            &ComputedDiv,
             &Text,
             &TextColor,
             &TextLayoutInfo,
             &ComputedTextBlock,
+            Option<&ComputedClip>,
         )>,
     >,
     text_colors: Extract<Query<&TextColor>>,
 ) {
     for (&camera_entity, ui_stack) in ui_stack_map.iter() {
-        for div in ui_stack
-            .ranges
+        for (stack_index, div) in ui_stack
             .iter()
-            .flat_map(|range| text_query.iter_many(&ui_stack.entities[range.clone()]))
+            .filter_map(|(stack_index, entity)| Some((stack_index, text_query.get(entity).ok()?)))
         {
             extract_single_text(
                 &mut commands,
                 &mut extracted_ui_atlases,
                 div,
                 &text_colors,
+                stack_index,
                 camera_entity,
             );
         }
@@ -163,18 +169,21 @@ fn extract_single_text(
         text_color,
         text_layout_info,
         computed_text_block,
+        computed_clip,
     ): (
         Entity,
         &GlobalTransform,
         &InheritedVisibility,
         &ComputedLayout,
-        &Div,
+        &ComputedDiv,
         &Text,
         &TextColor,
         &TextLayoutInfo,
         &ComputedTextBlock,
+        Option<&ComputedClip>,
     ),
     text_colors: &Extract<Query<&TextColor>>,
+    stack_index: usize,
     camera_entity: Entity,
 ) {
     if !inherited_visibility.get() {
@@ -194,9 +203,10 @@ fn extract_single_text(
         .mul(Affine3A::from_translation(offset))
         .mul(scale_factor_affine.inverse());
 
-    let index = div.stack_index as f32 + 0.06;
+    let index = stack_index as f32 + layer::TEXT;
     let main_entity = entity.into();
-    let corner_radii = div.corner_radii.to_array();
+    let corner_radii = div.corner_radii.resolve(computed_layout.size);
+    let clip = UiClip::new(computed_clip);
 
     let mut color = text_color.to_linear();
     let mut current_selection_index = usize::MAX;
@@ -249,6 +259,7 @@ fn extract_single_text(
                 size,
                 corner_radii,
                 extra,
+                clip,
                 ..UiAtlas::TEXT
             },
         });
@@ -3,6 +3,9 @@ use std::{marker::PhantomData, ops::Range};
 use bevy_asset::AssetId;
 use bevy_ecs::{component::Component, entity::Entity, resource::Resource, schedule::SystemSet};
 use bevy_image::Image;
+use bevy_math::{Affine3A, Mat4};
+use bevy_mesh::VertexFormat;
+use bevy_moon_core::prelude::{ClipRegion, ComputedClip};
 use bevy_platform::collections::HashMap;
 use bevy_render::{
     render_resource::{BindGroup, BufferUsages, RawBufferVec},
@@ -18,6 +21,26 @@ mod plugin;
 
 pub use plugin::MoonInternalRenderPlugin;
 
+/// The offsets of the instances of a node from its stack index, in the order of drawing.
+///
+/// The offsets stay below `1.0`, the stack index of the next node.
+pub(crate) mod layer {
+    /// The outer box shadows, below the background.
+    pub const SHADOW: f32 = -0.1;
+    /// The background and the border.
+    pub const QUAD: f32 = 0.0;
+    /// The inset box shadows, above the background.
+    pub const INSET_SHADOW: f32 = 0.002;
+    /// The border gradient over a background gradient.
+    pub const BORDER_GRADIENT: f32 = 0.003;
+    /// The image.
+    pub const IMAGE: f32 = 0.01;
+    /// The text.
+    pub const TEXT: f32 = 0.06;
+    /// The outline, which is offset from the last descendant of the node, above its content.
+    pub const OUTLINE: f32 = 0.09;
+}
+
 #[derive(Resource)]
 pub struct UiMeta<T>
 where
@@ -37,6 +60,145 @@ where
     }
 }
 
+/// Draws the edges of the instances instead of shading them, for debugging.
+///
+/// A render-world resource, which is set by the debug overlay.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UiWireframe(pub bool);
+
+/// The clipping regions of an instance, from the outermost to the innermost.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct UiClip {
+    pub regions: [UiClipRegion; 2],
+}
+
+// The shaders test the fragments against two clipping regions.
+const _: () = assert!(ComputedClip::MAX_DEPTH == 2);
+
+impl Default for UiClip {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl UiClip {
+    /// Disables clipping.
+    pub const NONE: Self = Self {
+        regions: [UiClipRegion::NONE; 2],
+    };
+
+    /// The vertex formats of the clipping regions.
+    pub const VERTEX_FORMATS: [VertexFormat; 6] = [
+        // first region: clip_from_world x row, y row, rect
+        VertexFormat::Float32x4,
+        VertexFormat::Float32x4,
+        VertexFormat::Uint32x4,
+        // second region: clip_from_world x row, y row, rect
+        VertexFormat::Float32x4,
+        VertexFormat::Float32x4,
+        VertexFormat::Uint32x4,
+    ];
+
+    pub fn new(computed_clip: Option<&ComputedClip>) -> Self {
+        let mut clip = Self::NONE;
+
+        if let Some(computed_clip) = computed_clip {
+            for (region, computed_region) in clip.regions.iter_mut().zip(&computed_clip.regions) {
+                *region = UiClipRegion::new(computed_region);
+            }
+        }
+
+        clip
+    }
+}
+
+/// A clipping region of an instance, see `shaders/libs/clip.wgsl`.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct UiClipRegion {
+    /// The first two rows of the matrix, which transforms a world position into
+    /// the local space of the clipping region, relative to its center.
+    pub clip_from_world: [[f32; 4]; 2],
+
+    /// `[half_size.x, half_size.y, top_left and top_right, bottom_right and bottom_left]`
+    ///
+    /// The bits of the half size, which may be unbounded, and the corner radii as `float16x2`.
+    /// A negative half size disables the region.
+    pub rect: [u32; 4],
+}
+
+impl UiClipRegion {
+    /// A region which clips nothing.
+    pub const NONE: Self = Self {
+        clip_from_world: [[0.0; 4]; 2],
+        rect: [(-1.0f32).to_bits(), (-1.0f32).to_bits(), 0, 0],
+    };
+
+    pub fn new(region: &ClipRegion) -> Self {
+        // Moves the origin to the center of the region.
+        let clip_from_world =
+            Affine3A::from_translation(-region.center.extend(0.0)) * region.clip_from_world;
+        let matrix = Mat4::from(clip_from_world);
+        let [radii_top, radii_bottom] = pack_f16x4(region.corner_radii.to_array());
+
+        Self {
+            clip_from_world: [matrix.row(0).to_array(), matrix.row(1).to_array()],
+            rect: [
+                region.half_size.x.to_bits(),
+                region.half_size.y.to_bits(),
+                radii_top,
+                radii_bottom,
+            ],
+        }
+    }
+}
+
+/// Packs the values as a `float16x2`, which is unpacked by `unpack2x16float` in the shaders.
+#[inline]
+pub fn pack_f16x2(x: f32, y: f32) -> u32 {
+    f16_bits(x) | f16_bits(y) << 16
+}
+
+/// Packs the values as two `float16x2`, see [`pack_f16x2`].
+#[inline]
+pub fn pack_f16x4([x, y, z, w]: [f32; 4]) -> [u32; 2] {
+    [pack_f16x2(x, y), pack_f16x2(z, w)]
+}
+
+/// Returns the bits of the nearest half float, the values out of its range are clamped.
+fn f16_bits(value: f32) -> u32 {
+    /// The largest half float.
+    const MAX: f32 = 65504.0;
+    /// The smallest normal half float.
+    const MIN_NORMAL: f32 = 6.103_515_6e-5;
+
+    let sign = (value.to_bits() >> 16) & 0x8000;
+    let value = value.abs();
+
+    if value.is_nan() {
+        return sign | 0x7e00;
+    }
+    if value >= MAX {
+        return sign | 0x7bff;
+    }
+    if value < MIN_NORMAL {
+        // In steps of the smallest subnormal half float, `2^-24`.
+        return sign | (value * 16_777_216.0).round() as u32;
+    }
+
+    let bits = value.to_bits();
+    let exponent = (bits >> 23) - 127 + 15;
+    let mantissa = bits & 0x7f_ffff;
+
+    // Rounds to the nearest, ties to even, a carry goes into the exponent.
+    let half = exponent << 10 | mantissa >> 13;
+    let rest = mantissa & 0x1fff;
+    let round_up = rest > 0x1000 || (rest == 0x1000 && half & 1 == 1);
+
+    sign | (half + u32::from(round_up))
+}
+
 #[derive(Component, Debug)]
 pub struct UiBatch<T> {
     pub range: Range<u32>,
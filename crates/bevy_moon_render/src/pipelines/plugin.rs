@@ -9,7 +9,7 @@ use bevy_render::{
 use bevy_moon_core::prelude::UiStackMap;
 
 use crate::{
-    pipelines::{ExtractUiSystems, UiTextureBindGroups},
+    pipelines::{ExtractUiSystems, UiTextureBindGroups, UiWireframe},
     systems::{extract_camera_views, extract_sprite_events},
     transparent::TransparentUi,
 };
@@ -28,6 +28,7 @@ impl Plugin for MoonInternalRenderPlugin {
 
         render_app
             .init_resource::<UiTextureBindGroups>()
+            .init_resource::<UiWireframe>()
             .init_resource::<ViewSortedRenderPhases<TransparentUi>>()
             .init_resource::<DrawFunctions<TransparentUi>>();
 
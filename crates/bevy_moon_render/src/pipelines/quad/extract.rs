@@ -1,18 +1,46 @@
+use core::f32::consts::TAU;
+
+use bevy_color::{Color, ColorToComponents};
+use bevy_math::{Affine3A, Vec2, vec2};
+use bevy_mesh::VertexFormat;
+use bevy_moon_core::prelude::{ColorSpace, Gradient, GradientKind};
 use bytemuck::{Pod, Zeroable};
+use smallvec::SmallVec;
+
+use crate::pipelines::{UiClip, pack_f16x4};
 
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
 pub struct UiQuad {
-    /// A `[[f32; 4]; 4]` 3D array storing data in column major order (4Cx4R).
+    /// The x axis, the y axis and the translation of the affine transform, in column major order.
     ///
-    /// Sees [`bevy_math::Mat4::to_cols_array_2d`].
-    pub matrix: [[f32; 4]; 4],
+    /// The z axis is left out, as the local positions of the flat quads have no depth, and the
+    /// `w` lanes of the x and y axes, which are zero in an affine transform, carry the size.
+    /// Sees [`UiQuad::size`].
+    pub matrix: [[f32; 4]; 3],
+
+    /// `[red and green, blue and alpha]` of the background as sRGB `float16x2`, see [`pack_srgba`].
+    pub color: [u32; 2],
+
+    /// `[style, dash length or dot size and gap]`, the lengths as `float16x2`,
+    /// see `shaders/libs/borders.wgsl`.
+    pub border_style: [u32; 2],
+
+    /// `[top_left and top_right, bottom_right and bottom_left]` as `float16x2`.
+    pub corner_radii: [u32; 2],
+
+    /// `[top and right, bottom and left]` as `float16x2`.
+    pub border_widths: [u32; 2],
 
-    pub color: [f32; 4],
-    pub size: [f32; 2],
-    pub corner_radii: [f32; 4],
-    pub border_color: [f32; 4],
-    pub border_widths: [f32; 4],
+    /// `[red and green, blue and alpha]` of the sides `[top, right, bottom, left]` as sRGB
+    /// `float16x2`, see [`pack_srgba`].
+    pub border_colors: [[u32; 4]; 2],
+
+    /// The clipping regions inherited from the clipping ancestors.
+    pub clip: UiClip,
+
+    /// The gradient which replaces the background or the border color.
+    pub gradient: UiGradient,
 }
 
 impl Default for UiQuad {
@@ -24,11 +52,150 @@ impl Default for UiQuad {
 impl UiQuad {
     /// The default value of the quad instance.
     pub const DEFAULT: Self = Self {
-        matrix: [[0.0; 4]; 4],
-        color: [0.0; 4],
-        size: [0.0; 2],
-        corner_radii: [0.0; 4],
-        border_color: [0.0; 4],
-        border_widths: [0.0; 4],
+        matrix: [[0.0; 4]; 3],
+        color: [0; 2],
+        border_style: [0; 2],
+        corner_radii: [0; 2],
+        border_widths: [0; 2],
+        border_colors: [[0; 4]; 2],
+        clip: UiClip::NONE,
+        gradient: UiGradient::NONE,
+    };
+
+    /// Packs the transform and the size into [`UiQuad::matrix`].
+    pub fn pack_matrix(affine: Affine3A, size: Vec2) -> [[f32; 4]; 3] {
+        [
+            affine.x_axis.extend(size.x).to_array(),
+            affine.y_axis.extend(size.y).to_array(),
+            affine.translation.extend(1.0).to_array(),
+        ]
+    }
+
+    /// Returns the size, which is carried by the `w` lanes of the x and y axes.
+    #[inline]
+    pub fn size(&self) -> Vec2 {
+        vec2(self.matrix[0][3], self.matrix[1][3])
+    }
+
+    /// Returns the quad with the size.
+    #[inline]
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.matrix[0][3] = size.x;
+        self.matrix[1][3] = size.y;
+        self
+    }
+}
+
+/// The gradient of a quad instance, see `shaders/libs/gradients.wgsl`.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct UiGradient {
+    /// `[flags, center, positions, reserved]`
+    ///
+    /// - `flags`: the kind in the bits `0..2`, the color space in `2..5`, the count of the stops
+    ///   in `5..8`, the border target in `8` and the angle in turns as `unorm16` in `16..32`.
+    /// - `center`: the fractions of the box from its top-left corner as `unorm16x2`.
+    /// - `positions`: the positions of the stops as `unorm8x4`.
+    pub params: [u32; 4],
+
+    /// `[red and green, blue and alpha]` of the stops as sRGB `float16x2`, see [`pack_srgba`].
+    pub colors: [[u32; 4]; 2],
+}
+
+impl Default for UiGradient {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl UiGradient {
+    /// No gradient.
+    pub const NONE: Self = Self {
+        params: [0; 4],
+        colors: [[0; 4]; 2],
     };
+
+    /// The maximum count of the stops, a gradient with more stops is resampled.
+    pub const MAX_STOPS: usize = Gradient::MAX_STOPS;
+
+    /// The vertex formats of the gradient.
+    pub const VERTEX_FORMATS: [VertexFormat; 3] = [
+        // params
+        VertexFormat::Uint32x4,
+        // red and green of the colors
+        VertexFormat::Uint32x4,
+        // blue and alpha of the colors
+        VertexFormat::Uint32x4,
+    ];
+
+    /// Packs the gradient, `border` targets the border color instead of the background.
+    pub fn new(gradient: &Gradient, border: bool) -> Self {
+        let mut stops = gradient.resolved_stops();
+
+        if stops.len() > Self::MAX_STOPS {
+            let start = stops[0].1;
+            let end = stops[stops.len() - 1].1;
+            let step = (end - start) / (Self::MAX_STOPS - 1) as f32;
+
+            stops = (0..Self::MAX_STOPS)
+                .map(|index| {
+                    let position = start + step * index as f32;
+                    (gradient.sample(position), position)
+                })
+                .collect::<SmallVec<_>>();
+        }
+
+        let (kind, angle, center) = match gradient.kind {
+            GradientKind::Linear { angle } => (1, angle, Vec2::splat(0.5)),
+            GradientKind::Radial { center } => (2, 0.0, center),
+            GradientKind::Conic {
+                center,
+                start_angle,
+            } => (3, start_angle, center),
+        };
+
+        let color_space = match gradient.color_space {
+            ColorSpace::Srgba => 0,
+            ColorSpace::LinearRgba => 1,
+            ColorSpace::Hsla => 2,
+            ColorSpace::Oklaba => 3,
+            ColorSpace::Oklcha => 4,
+        };
+
+        let turns = (angle / TAU).rem_euclid(1.0);
+        let flags = kind
+            | color_space << 2
+            | (stops.len() as u32) << 5
+            | (border as u32) << 8
+            | unorm16(turns) << 16;
+
+        let center = unorm16(center.x) | unorm16(center.y) << 16;
+
+        let mut positions = [0; 4];
+        let mut colors = [[0; 4]; 2];
+
+        for (index, (color, position)) in stops.into_iter().enumerate() {
+            positions[index] = (position.clamp(0.0, 1.0) * 255.0).round() as u8;
+            [colors[0][index], colors[1][index]] = pack_srgba(color);
+        }
+
+        Self {
+            params: [flags, center, u32::from_le_bytes(positions), 0],
+            colors,
+        }
+    }
+}
+
+/// Packs the color as sRGB `[red and green, blue and alpha]` `float16x2`, which are unpacked by
+/// `unpack_srgba` in the shaders.
+///
+/// Unlike `unorm8`, it does not band the smooth gradients, and keeps the HDR colors.
+#[inline]
+pub fn pack_srgba(color: Color) -> [u32; 2] {
+    pack_f16x4(color.to_srgba().to_f32_array())
+}
+
+#[inline]
+fn unorm16(value: f32) -> u32 {
+    (value.clamp(0.0, 1.0) * 65535.0).round() as u32
 }
@@ -1,6 +1,9 @@
 use crate::pipelines::{UiBatch, UiMeta, UiViewBindGroup};
 
-use self::{extract::UiQuad, pipeline::UiQuadPipeline};
+use self::{
+    extract::{UiGradient, UiQuad, pack_srgba},
+    pipeline::UiQuadPipeline,
+};
 
 mod draw;
 mod extract;
@@ -14,10 +14,14 @@ use bevy_render::{
     },
     view::{ViewTarget, ViewUniform},
 };
-use bevy_shader::Shader;
+use bevy_shader::{Shader, ShaderDefVal};
 use bevy_sprite_render::Mesh2dPipelineKey;
 use bevy_utils::default;
 
+use crate::pipelines::UiClip;
+
+use super::UiGradient;
+
 #[derive(Resource, Clone)]
 pub struct UiQuadPipeline {
     pub view_layout: BindGroupLayoutDescriptor,
@@ -27,6 +31,8 @@ pub struct UiQuadPipeline {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct UiQuadPipelineKey {
     pub mesh_key: Mesh2dPipelineKey,
+    /// Draws the edges of the instances, see [`UiWireframe`](crate::pipelines::UiWireframe).
+    pub wireframe: bool,
     // pub anti_alias: bool,
 }
 
@@ -39,7 +45,11 @@ impl SpecializedRenderPipeline for UiQuadPipeline {
         //     .then_some(vec!["ANTI_ALIAS".into()])
         //     .unwrap_or_default();
 
-        let shader_defs = vec![];
+        let mut shader_defs = Vec::<ShaderDefVal>::new();
+
+        if key.wireframe {
+            shader_defs.push("WIREFRAME".into());
+        }
 
         let mesh_key = key.mesh_key;
 
@@ -51,28 +61,31 @@ impl SpecializedRenderPipeline for UiQuadPipeline {
 
         let layout = vec![self.view_layout.clone()];
 
+        // Uses all the 16 vertex attributes of the default limit of WebGPU, a new field has to be
+        // packed into the unused lanes of the existing ones, as the size into the axes.
         let vertex_layout = VertexBufferLayout::from_vertex_formats(
             VertexStepMode::Instance,
             vec![
-                // x_axis
-                VertexFormat::Float32x4,
-                // y_axis
+                // x_axis and width
                 VertexFormat::Float32x4,
-                // z_axis
+                // y_axis and height
                 VertexFormat::Float32x4,
                 // w_axis, its also translation
                 VertexFormat::Float32x4,
-                // color
-                VertexFormat::Float32x4,
-                // size
-                VertexFormat::Float32x2,
-                // corner_radii
-                VertexFormat::Float32x4,
-                // border color
-                VertexFormat::Float32x4,
-                // border widths
-                VertexFormat::Float32x4,
-            ],
+                // color and border style
+                VertexFormat::Uint32x4,
+                // corner radii and border widths
+                VertexFormat::Uint32x4,
+                // red and green of the border colors
+                VertexFormat::Uint32x4,
+                // blue and alpha of the border colors
+                VertexFormat::Uint32x4,
+            ]
+            .into_iter()
+            // clip_from_world and rect of the clipping regions
+            .chain(UiClip::VERTEX_FORMATS)
+            // gradient params, red and green, blue and alpha of the colors
+            .chain(UiGradient::VERTEX_FORMATS),
         );
 
         RenderPipelineDescriptor {
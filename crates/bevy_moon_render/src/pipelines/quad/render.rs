@@ -17,6 +17,7 @@ use bevy_render::{
 use indexmap::IndexMap;
 
 use crate::{
+    pipelines::UiWireframe,
     transparent::{RenderPhasesFilter, TransparentUi},
     view::{MoonUiCameraView, MoonUiOptions, MoonUiViewTarget},
 };
@@ -35,6 +36,7 @@ pub fn queue_quads(
     ui_quad_pipeline: Res<UiQuadPipeline>,
     pipeline_cache: Res<PipelineCache>,
     draw_functions: Res<DrawFunctions<TransparentUi>>,
+    wireframe: Res<UiWireframe>,
     mut pipelines: ResMut<SpecializedRenderPipelines<UiQuadPipeline>>,
     mut render_phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
 ) {
@@ -61,6 +63,7 @@ pub fn queue_quads(
             &ui_quad_pipeline,
             UiQuadPipelineKey {
                 mesh_key,
+                wireframe: wireframe.0,
                 // @TODO(fundon): add an `UiAntiAlias` option
                 // anti_alias: true,
             },
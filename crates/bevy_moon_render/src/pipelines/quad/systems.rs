@@ -1,20 +1,22 @@
 use bevy_asset::AssetId;
 use bevy_camera::visibility::InheritedVisibility;
-use bevy_color::{Alpha, Color, ColorToComponents};
+use bevy_color::{Alpha, Color};
 use bevy_ecs::{
     entity::Entity,
+    hierarchy::ChildOf,
     prelude::Res,
     system::{Commands, Query, ResMut},
 };
-use bevy_math::Mat4;
 use bevy_render::{Extract, sync_world::TemporaryRenderEntity};
 use bevy_transform::components::GlobalTransform;
 
-use bevy_moon_core::prelude::{ComputedLayout, Div, UiStackMap};
+use bevy_moon_core::prelude::{
+    BorderStyle, ComputedClip, ComputedDiv, ComputedLayout, Outline, UiStack, UiStackMap,
+};
 
-use crate::pipelines::ExtractedUiInstance;
+use crate::pipelines::{ExtractedUiInstance, UiClip, layer, pack_f16x2, pack_f16x4};
 
-use super::{ExtractedUiQuads, UiQuad};
+use super::{ExtractedUiQuads, UiGradient, UiQuad, pack_srgba};
 
 pub fn extract_quads(
     mut commands: Commands,
@@ -26,33 +28,68 @@ pub fn extract_quads(
             &GlobalTransform,
             &InheritedVisibility,
             &ComputedLayout,
-            &Div,
+            &ComputedDiv,
+            Option<&ComputedClip>,
         )>,
     >,
+    parent_query: Extract<Query<&ChildOf>>,
 ) {
     extracted_ui_quads.instances.clear();
 
     for (&camera_entity, ui_stack) in ui_stack_map.iter() {
-        for div in ui_stack
-            .ranges
+        for (stack_index, div) in ui_stack
             .iter()
-            .flat_map(|range| div_query.iter_many(&ui_stack.entities[range.clone()]))
+            .filter_map(|(stack_index, entity)| Some((stack_index, div_query.get(entity).ok()?)))
         {
-            extract_quad(&mut commands, &mut extracted_ui_quads, div, camera_entity);
+            let last_descendant = || last_descendant_index(ui_stack, stack_index, &parent_query);
+
+            extract_quad(
+                &mut commands,
+                &mut extracted_ui_quads,
+                div,
+                stack_index,
+                last_descendant,
+                camera_entity,
+            );
         }
     }
 }
 
+/// Returns the stack index of the last descendant of the node, or its own index without one.
+///
+/// The stack is built depth first, the descendants of a node follow it.
+fn last_descendant_index(
+    ui_stack: &UiStack,
+    stack_index: usize,
+    parent_query: &Query<&ChildOf>,
+) -> usize {
+    let entity = ui_stack.entities[stack_index];
+
+    let descendants = ui_stack.entities[stack_index + 1..]
+        .iter()
+        .take_while(|&&descendant| {
+            parent_query
+                .iter_ancestors(descendant)
+                .any(|ancestor| ancestor == entity)
+        })
+        .count();
+
+    stack_index + descendants
+}
+
 fn extract_quad(
     commands: &mut Commands,
     extracted_ui_quads: &mut ExtractedUiQuads,
-    (entity, transform, inherited_visibility, computed_layout, div): (
+    (entity, transform, inherited_visibility, computed_layout, div, computed_clip): (
         Entity,
         &GlobalTransform,
         &InheritedVisibility,
         &ComputedLayout,
-        &Div,
+        &ComputedDiv,
+        Option<&ComputedClip>,
     ),
+    stack_index: usize,
+    last_descendant: impl FnOnce() -> usize,
     camera_entity: Entity,
 ) {
     if !inherited_visibility.get() {
@@ -62,40 +99,167 @@ fn extract_quad(
         return;
     }
 
-    let color = div.background.unwrap_or(Color::NONE);
-    let border_color = div.border_color.unwrap_or(Color::NONE);
+    // a gradient takes precedence over the color
+    let background_gradient = div.background_gradient.as_ref();
+    let border_gradient = div.border_gradient.as_ref();
+
+    let color = div
+        .background
+        .filter(|_| background_gradient.is_none())
+        .unwrap_or(Color::NONE);
+    let border_colors = div.border_colors.to_array().map(|side_color| {
+        side_color
+            .or(div.border_color)
+            .filter(|_| border_gradient.is_none())
+            .unwrap_or(Color::NONE)
+    });
 
-    if color.is_fully_transparent() && border_color.is_fully_transparent() {
+    let outline = div
+        .outline
+        .filter(|outline| outline.width > 0.0 && !outline.color.is_fully_transparent());
+
+    let visible = !color.is_fully_transparent()
+        || !border_colors.iter().all(Color::is_fully_transparent)
+        || background_gradient.is_some()
+        || border_gradient.is_some();
+
+    if !visible && outline.is_none() {
         return;
     }
 
-    let color = color.to_linear().to_f32_array();
-    let border_color = border_color.to_linear().to_f32_array();
+    let color = pack_srgba(color);
+    let [top, right, bottom, left] = border_colors.map(pack_srgba);
+    let border_colors = [
+        [top[0], right[0], bottom[0], left[0]],
+        [top[1], right[1], bottom[1], left[1]],
+    ];
 
-    let index = div.stack_index as f32;
+    let index = stack_index as f32;
     let main_entity = entity.into();
 
-    let size = computed_layout.size.to_array();
-    let corner_radii = div.corner_radii.to_array(); // should be computed_layout.corner_radii
-    let border_widths = computed_layout.border_widths.to_array();
+    let corner_radii = div.corner_radii.resolve(computed_layout.size);
+    let border_widths = pack_f16x4(computed_layout.border_widths.to_array());
+    let border_style = pack_border_style(
+        div.border_style,
+        computed_layout.border_widths.max_element(),
+    );
+
+    let matrix = UiQuad::pack_matrix(transform.affine(), computed_layout.size);
+    let clip = UiClip::new(computed_clip);
+
+    let quad = UiQuad {
+        matrix,
+        color,
+        border_style,
+        corner_radii: pack_f16x4(corner_radii),
+        border_widths,
+        border_colors,
+        clip,
+        gradient: UiGradient::NONE,
+    };
 
-    let matrix = Mat4::from(transform.affine()).to_cols_array_2d();
+    // `[background, border gradient]`, in the order of drawing.
+    let mut quads = [None; 2];
+
+    // An instance carries one gradient, so a border gradient over a background gradient is drawn
+    // by a second instance, which only covers the border.
+    if visible {
+        match (background_gradient, border_gradient) {
+            (None, None) => quads[0] = Some(quad),
+            (Some(background_gradient), None) => {
+                quads[0] = Some(UiQuad {
+                    gradient: UiGradient::new(background_gradient, false),
+                    ..quad
+                });
+            }
+            (None, Some(border_gradient)) => {
+                quads[0] = Some(UiQuad {
+                    gradient: UiGradient::new(border_gradient, true),
+                    ..quad
+                });
+            }
+            (Some(background_gradient), Some(border_gradient)) => {
+                quads[0] = Some(UiQuad {
+                    gradient: UiGradient::new(background_gradient, false),
+                    ..quad
+                });
+                quads[1] = Some(UiQuad {
+                    color: [0; 2],
+                    gradient: UiGradient::new(border_gradient, true),
+                    ..quad
+                });
+            }
+        }
+    }
 
-    let render_entity = commands.spawn(TemporaryRenderEntity).id();
+    // the outline is drawn above the content and the descendants of the node
+    let outline = outline
+        .and_then(|outline| outline_quad(&quad, corner_radii, outline))
+        .map(|instance| (last_descendant() as f32 + layer::OUTLINE, instance));
 
-    extracted_ui_quads.instances.push(ExtractedUiInstance {
-        index,
-        camera_entity,
-        entity: (render_entity, main_entity),
-        texture: AssetId::default(),
+    let instances = [index + layer::QUAD, index + layer::BORDER_GRADIENT]
+        .into_iter()
+        .zip(quads)
+        .filter_map(|(index, instance)| Some((index, instance?)))
+        .chain(outline);
 
-        instance: UiQuad {
-            matrix,
-            color,
-            size,
-            corner_radii,
-            border_color,
-            border_widths,
-        },
+    for (index, instance) in instances {
+        let render_entity = commands.spawn(TemporaryRenderEntity).id();
+
+        extracted_ui_quads.instances.push(ExtractedUiInstance {
+            index,
+            camera_entity,
+            entity: (render_entity, main_entity),
+            texture: AssetId::default(),
+            instance,
+        });
+    }
+}
+
+/// Draws the outline as a border around the border box, which is expanded by the offset.
+fn outline_quad(quad: &UiQuad, corner_radii: [f32; 4], outline: Outline) -> Option<UiQuad> {
+    let expansion = outline.offset + outline.width;
+    let size = quad.size() + 2.0 * expansion;
+
+    if size.min_element() <= 0.0 {
+        return None;
+    }
+
+    // follows the rounded corners, the square corners stay square
+    let max_radius = 0.5 * size.min_element();
+    let corner_radii = corner_radii.map(|radius| match radius > 0.0 {
+        true => (radius + expansion).clamp(0.0, max_radius),
+        false => 0.0,
     });
+
+    let [red_green, blue_alpha] = pack_srgba(outline.color);
+
+    let quad = UiQuad {
+        color: [0; 2],
+        border_style: pack_border_style(outline.style, outline.width),
+        corner_radii: pack_f16x4(corner_radii),
+        border_widths: pack_f16x4([outline.width; 4]),
+        border_colors: [[red_green; 4], [blue_alpha; 4]],
+        gradient: UiGradient::NONE,
+        ..*quad
+    };
+
+    Some(quad.with_size(size))
+}
+
+/// Packs the border style as `[style, dash length or dot size and gap]`.
+fn pack_border_style(border_style: BorderStyle, width: f32) -> [u32; 2] {
+    let or_width = |value: f32, factor: f32| match value > 0.0 {
+        true => value,
+        false => width * factor,
+    };
+
+    let (style, length, gap) = match border_style {
+        BorderStyle::Solid => (0, 0.0, 0.0),
+        BorderStyle::Dashed { length, gap } => (1, or_width(length, 3.0), or_width(gap, 3.0)),
+        BorderStyle::Dotted { gap } => (2, width, or_width(gap, 1.0)),
+        BorderStyle::Double => (3, 0.0, 0.0),
+    };
+
+    [style, pack_f16x2(length, gap)]
 }
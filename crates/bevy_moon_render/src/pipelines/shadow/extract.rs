@@ -1,5 +1,7 @@
 use bytemuck::{Pod, Zeroable};
 
+use crate::pipelines::UiClip;
+
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
 pub struct UiShadow {
@@ -12,6 +14,15 @@ pub struct UiShadow {
     pub size: [f32; 2],
     pub corner_radii: [f32; 4],
     pub blur_radius: f32,
+
+    /// The clipping region inherited from the clipping ancestors.
+    pub clip: UiClip,
+
+    /// `[inset, offset.x, offset.y, spread]`, where `inset` is `1.0` for an inset shadow.
+    ///
+    /// An inset shadow covers the padding box, except for the hole, which is the padding box moved
+    /// by the offset and shrunk by the spread.
+    pub inset: [f32; 4],
 }
 
 impl Default for UiShadow {
@@ -27,5 +38,7 @@ impl UiShadow {
         size: [0.0; 2],
         corner_radii: [0.0; 4],
         blur_radius: 0.0,
+        clip: UiClip::NONE,
+        inset: [0.0; 4],
     };
 }
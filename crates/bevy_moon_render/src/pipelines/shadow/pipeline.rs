@@ -18,6 +18,8 @@ use bevy_shader::{Shader, ShaderDefVal};
 use bevy_sprite_render::Mesh2dPipelineKey;
 use bevy_utils::default;
 
+use crate::pipelines::UiClip;
+
 #[derive(Resource, Clone)]
 pub struct UiShadowPipeline {
     pub view_layout: BindGroupLayoutDescriptor,
@@ -29,17 +31,23 @@ pub struct UiShadowPipelineKey {
     pub mesh_key: Mesh2dPipelineKey,
     /// Number of samples, a higher value results in better quality shadows.
     pub samples: u32,
+    /// Draws the edges of the instances, see [`UiWireframe`](crate::pipelines::UiWireframe).
+    pub wireframe: bool,
 }
 
 impl SpecializedRenderPipeline for UiShadowPipeline {
     type Key = UiShadowPipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
-        let shader_defs = vec![ShaderDefVal::UInt(
+        let mut shader_defs = vec![ShaderDefVal::UInt(
             "SHADOW_SAMPLES".to_string(),
             key.samples,
         )];
 
+        if key.wireframe {
+            shader_defs.push("WIREFRAME".into());
+        }
+
         let mesh_key = key.mesh_key;
 
         let format = match mesh_key.contains(Mesh2dPipelineKey::HDR) {
@@ -69,7 +77,12 @@ impl SpecializedRenderPipeline for UiShadowPipeline {
                 VertexFormat::Float32x4,
                 // blur_radius
                 VertexFormat::Float32,
-            ],
+            ]
+            .into_iter()
+            // clip_from_world and rect of the clipping regions
+            .chain(UiClip::VERTEX_FORMATS)
+            // inset
+            .chain([VertexFormat::Float32x4]),
         );
 
         RenderPipelineDescriptor {
@@ -16,6 +16,7 @@ use bevy_render::{
 };
 
 use crate::{
+    pipelines::UiWireframe,
     transparent::{RenderPhasesFilter, TransparentUi},
     view::{MoonUiCameraView, MoonUiOptions, MoonUiViewTarget},
 };
@@ -34,6 +35,7 @@ pub fn queue_shadows(
     ui_shadow_pipeline: Res<UiShadowPipeline>,
     pipeline_cache: Res<PipelineCache>,
     draw_functions: Res<DrawFunctions<TransparentUi>>,
+    wireframe: Res<UiWireframe>,
     mut pipelines: ResMut<SpecializedRenderPipelines<UiShadowPipeline>>,
     mut render_phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
 ) {
@@ -63,6 +65,7 @@ pub fn queue_shadows(
             UiShadowPipelineKey {
                 mesh_key,
                 samples: 4,
+                wireframe: wireframe.0,
             },
         );
 
@@ -120,8 +123,8 @@ pub fn prepare_shadows(
     mut ui_shadow_meta: ResMut<UiShadowMeta>,
     mut extracted_ui_shadows: ResMut<ExtractedUiShadows>,
     mut render_phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
-    // maps `main entity` to `render entity`
-    mut live_entities: Local<MainEntityHashMap<Entity>>,
+    // maps `main entity` to `render entity`, for the outer and the inset shadows
+    mut live_entities: Local<[MainEntityHashMap<Entity>; 2]>,
     mut cached_draw_function: Local<Option<DrawFunctionId>>,
 ) {
     ui_shadow_meta.instance_buffer.clear();
@@ -129,7 +132,8 @@ pub fn prepare_shadows(
     let draw_function =
         *cached_draw_function.get_or_insert_with(|| draw_functions.read().id::<DrawUiShadow>());
 
-    let mut batches = EntityHashMap::<UiShadowBatch>::with_capacity(live_entities.capacity());
+    let capacity = live_entities[0].capacity() + live_entities[1].capacity();
+    let mut batches = EntityHashMap::<UiShadowBatch>::with_capacity(capacity);
 
     for (item, instance) in render_phases.filter(draw_function).filter_map(|item| {
         extracted_ui_shadows
@@ -137,7 +141,9 @@ pub fn prepare_shadows(
             .get(item.extracted_index)
             .map(|extracted_ui_instance| (item, extracted_ui_instance.instance))
     }) {
-        let render_entity = live_entities
+        // the inset shadows are drawn after the background, so they are batched separately
+        let inset = instance.inset[0] > 0.0;
+        let render_entity = live_entities[inset as usize]
             .entry(item.main_entity())
             .or_insert_with(|| item.entity());
 
@@ -163,5 +169,7 @@ pub fn prepare_shadows(
     commands.try_insert_batch(batches);
 
     extracted_ui_shadows.instances.clear();
-    live_entities.clear();
+    for entities in live_entities.iter_mut() {
+        entities.clear();
+    }
 }
@@ -8,16 +8,16 @@ use bevy_ecs::{
     prelude::Res,
     system::{Commands, Query, ResMut},
 };
-use bevy_math::{Affine3A, Mat4, vec2};
+use bevy_math::{Affine3A, Mat4, Vec2, Vec3, vec2, vec3};
 use bevy_render::{Extract, sync_world::TemporaryRenderEntity};
 use bevy_transform::components::GlobalTransform;
 
 use bevy_moon_core::{
     geometry::FLIP_Y,
-    prelude::{ComputedLayout, Div, UiStackMap},
+    prelude::{ComputedClip, ComputedDiv, ComputedLayout, UiStackMap},
 };
 
-use crate::pipelines::ExtractedUiInstance;
+use crate::pipelines::{ExtractedUiInstance, UiClip, layer};
 
 use super::{ExtractedUiShadows, UiShadow};
 
@@ -31,19 +31,25 @@ pub fn extract_shadows(
             &GlobalTransform,
             &InheritedVisibility,
             &ComputedLayout,
-            &Div,
+            &ComputedDiv,
+            Option<&ComputedClip>,
         )>,
     >,
 ) {
     extracted_ui_shadows.instances.clear();
 
     for (&camera_entity, ui_stack) in ui_stack_map.iter() {
-        for div in ui_stack
-            .ranges
+        for (stack_index, div) in ui_stack
             .iter()
-            .flat_map(|range| div_query.iter_many(&ui_stack.entities[range.clone()]))
+            .filter_map(|(stack_index, entity)| Some((stack_index, div_query.get(entity).ok()?)))
         {
-            extract_from_single_div(&mut commands, &mut extracted_ui_shadows, div, camera_entity);
+            extract_from_single_div(
+                &mut commands,
+                &mut extracted_ui_shadows,
+                div,
+                stack_index,
+                camera_entity,
+            );
         }
     }
 }
@@ -51,13 +57,15 @@ pub fn extract_shadows(
 fn extract_from_single_div(
     commands: &mut Commands,
     extracted_ui_shadows: &mut ExtractedUiShadows,
-    (entity, transform, inherited_visibility, computed_layout, div): (
+    (entity, transform, inherited_visibility, computed_layout, div, computed_clip): (
         Entity,
         &GlobalTransform,
         &InheritedVisibility,
         &ComputedLayout,
-        &Div,
+        &ComputedDiv,
+        Option<&ComputedClip>,
     ),
+    stack_index: usize,
     camera_entity: Entity,
 ) {
     if !inherited_visibility.get() {
@@ -73,46 +81,106 @@ fn extract_from_single_div(
         return;
     }
 
-    let index = div.stack_index as f32 - 0.1;
     let affine = transform.affine();
     let main_entity = entity.into();
     let size = computed_layout.size;
     let spread_ratio = size.y / size.x;
-    let corner_radii = div.corner_radii.to_array(); // should be computed_layout.corner_radii
+    let corner_radii = div.corner_radii.resolve(computed_layout.size);
+    let clip = UiClip::new(computed_clip);
+
+    let padding_box = padding_box(computed_layout, corner_radii);
 
     for shadow in shadows {
         if shadow.color.is_fully_transparent() {
             continue;
         }
 
-        let spread_radius = shadow.spread_radius;
-        let spread = vec2(spread_radius, spread_radius * spread_ratio);
-        let offset = shadow.offset.mul(FLIP_Y).extend(0.0);
-
-        // expands bounds for shadow
-        let shadow_size = size - spread * 2.0;
-
         let blur_radius = shadow.blur_radius;
         let color = shadow.color.to_linear().to_f32_array();
 
-        let matrix = Mat4::from(affine.mul(Affine3A::from_translation(offset))).to_cols_array_2d();
+        let (index, instance) = if shadow.inset {
+            let Some((center, padding_size, padding_radii)) = padding_box else {
+                continue;
+            };
 
-        let render_entity = commands.spawn(TemporaryRenderEntity).id();
+            // the hole moves with the offset, the padding box which clips the shadow stays in place
+            let offset = shadow.offset.mul(FLIP_Y);
+            let matrix =
+                Mat4::from(affine.mul(Affine3A::from_translation(center))).to_cols_array_2d();
 
-        extracted_ui_shadows.instances.push(ExtractedUiInstance {
-            index,
-            camera_entity,
-            entity: (render_entity, main_entity),
-            texture: AssetId::default(),
+            let instance = UiShadow {
+                matrix,
+                color,
+                corner_radii: padding_radii,
+                blur_radius,
+                size: padding_size.to_array(),
+                clip,
+                inset: [1.0, offset.x, offset.y, shadow.spread_radius],
+            };
+
+            // draws above the background, below the border gradient and the outline
+            (stack_index as f32 + layer::INSET_SHADOW, instance)
+        } else {
+            let spread_radius = shadow.spread_radius;
+            let spread = vec2(spread_radius, spread_radius * spread_ratio);
+            let offset = shadow.offset.mul(FLIP_Y).extend(0.0);
+
+            // expands bounds for shadow
+            let shadow_size = size - spread * 2.0;
+
+            let matrix =
+                Mat4::from(affine.mul(Affine3A::from_translation(offset))).to_cols_array_2d();
 
-            instance: UiShadow {
+            let instance = UiShadow {
                 matrix,
                 color,
                 corner_radii,
                 blur_radius,
                 size: shadow_size.to_array(),
+                clip,
                 ..UiShadow::DEFAULT
-            },
+            };
+
+            (stack_index as f32 + layer::SHADOW, instance)
+        };
+
+        let render_entity = commands.spawn(TemporaryRenderEntity).id();
+
+        extracted_ui_shadows.instances.push(ExtractedUiInstance {
+            index,
+            camera_entity,
+            entity: (render_entity, main_entity),
+            texture: AssetId::default(),
+            instance,
         });
     }
 }
+
+/// Returns the center, the size and the corner radii of the padding box, which is inside the
+/// borders, or `None` when the borders fill the box.
+fn padding_box(
+    computed_layout: &ComputedLayout,
+    corner_radii: [f32; 4],
+) -> Option<(Vec3, Vec2, [f32; 4])> {
+    let [top, right, bottom, left] = computed_layout.border_widths.to_array();
+    let size = computed_layout.size - vec2(left + right, top + bottom);
+
+    if size.min_element() <= 0.0 {
+        return None;
+    }
+
+    // the local space is centered, with the y axis up
+    let center = vec3((left - right) * 0.5, (bottom - top) * 0.5, 0.0);
+
+    let max_radius = 0.5 * size.min_element();
+    let [top_left, top_right, bottom_right, bottom_left] = corner_radii;
+    let corner_radii = [
+        top_left - top.max(left),
+        top_right - top.max(right),
+        bottom_right - bottom.max(right),
+        bottom_left - bottom.max(left),
+    ]
+    .map(|radius| radius.clamp(0.0, max_radius));
+
+    Some((center, size, corner_radii))
+}
@@ -17,6 +17,9 @@ impl Plugin for MoonRenderPlugin {
         load_shader_library!(app, "shaders/libs/rectangles.wgsl");
         load_shader_library!(app, "shaders/libs/atlas.wgsl");
         load_shader_library!(app, "shaders/libs/utils.wgsl");
+        load_shader_library!(app, "shaders/libs/clip.wgsl");
+        load_shader_library!(app, "shaders/libs/gradients.wgsl");
+        load_shader_library!(app, "shaders/libs/borders.wgsl");
 
         app.add_plugins(ExtractResourcePlugin::<UiStackMap>::default());
 
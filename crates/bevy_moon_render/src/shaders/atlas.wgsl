@@ -9,8 +9,10 @@
 }
 #import bevy_moon::utils::is_empty4
 #import bevy_moon::utils::aa_s
+#import bevy_moon::utils::wireframe
 #import bevy_moon::rectangles::sd_rounded_box
 #import bevy_moon::atlas
+#import bevy_moon::clip::{to_clip_space, clip_coverage}
 
 @group(0) @binding(0) var<uniform> view: View;
 
@@ -33,6 +35,15 @@ struct VertexInput {
     // image: [flags, ObjectFit, ObjectPosition.x, ObjectPosition.y]
     @location(7) extra: vec4<f32>,
     @location(8) flipped: vec2<u32>,
+
+    // the clipping regions, from the outermost to the innermost: clip_from_world [x_row, y_row]
+    // and [half_size.x, half_size.y, top_left and top_right, bottom_right and bottom_left]
+    @location(9) first_clip_x: vec4<f32>,
+    @location(10) first_clip_y: vec4<f32>,
+    @location(11) first_clip_rect: vec4<u32>,
+    @location(12) second_clip_x: vec4<f32>,
+    @location(13) second_clip_y: vec4<f32>,
+    @location(14) second_clip_rect: vec4<u32>,
 };
 
 struct VertexOutput {
@@ -46,6 +57,11 @@ struct VertexOutput {
     @location(4) @interpolate(flat) corner_radii: vec4<f32>,
     @location(5) @interpolate(flat) extra: vec4<f32>,
     @location(6) @interpolate(flat) flipped: vec2<u32>,
+
+    // [first.x, first.y, second.x, second.y]
+    @location(7) clip_points: vec4<f32>,
+    @location(8) @interpolate(flat) first_clip_rect: vec4<u32>,
+    @location(9) @interpolate(flat) second_clip_rect: vec4<u32>,
 };
 
 @vertex
@@ -59,6 +75,13 @@ fn vertex(in: VertexInput) -> VertexOutput {
     let matrix = mat4x4(in.x_axis, in.y_axis, in.z_axis, in.w_axis);
     let world_position = matrix * world_from_local;
     let clip_position = view.clip_from_world * world_position;
+    let clip_points = to_clip_space(
+        world_position,
+        in.first_clip_x,
+        in.first_clip_y,
+        in.second_clip_x,
+        in.second_clip_y,
+    );
 
     return VertexOutput(
         clip_position,
@@ -68,12 +91,14 @@ fn vertex(in: VertexInput) -> VertexOutput {
         in.size,
         in.corner_radii,
         in.extra,
-        in.flipped
+        in.flipped,
+        clip_points,
+        in.first_clip_rect,
+        in.second_clip_rect,
     );
 }
 
-@fragment
-fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+fn shade(in: VertexOutput) -> vec4<f32> {
     let src_size = vec2<f32>(textureDimensions(atlas_texture, 0));
     let dst_size = in.size;
     let flags = u32(in.extra.x);
@@ -131,3 +156,29 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
 
     return color;
 }
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+#ifdef WIREFRAME
+    let edge = wireframe(in.uv);
+
+    if (edge <= 0.0) {
+        discard;
+    }
+
+    return vec4(1.0, 1.0, 0.0, edge);
+#else
+    let coverage = clip_coverage(in.clip_points, in.first_clip_rect, in.second_clip_rect);
+
+    // fast path
+    if (coverage <= 0.0) {
+        discard;
+    }
+
+    var color = shade(in);
+
+    color.a *= coverage;
+
+    return color;
+#endif
+}
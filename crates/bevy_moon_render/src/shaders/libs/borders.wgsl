@@ -0,0 +1,201 @@
+#define_import_path bevy_moon::borders
+
+#import bevy_moon::maths::HALF_PI
+#import bevy_moon::gradients::{srgba_to_linear, unpack_srgba}
+
+// The styles of the borders
+//
+// `style` is `[style, dash length or dot size, gap, 0.0]`.
+const SOLID  = 0u;
+const DASHED = 1u;
+const DOTTED = 2u;
+const DOUBLE = 3u;
+
+// The sides, in the order of `widths`, `rg` and `ba`
+const TOP    = 0u;
+const RIGHT  = 1u;
+const BOTTOM = 2u;
+const LEFT   = 3u;
+
+// A point of the border
+struct BorderPoint {
+    // The distance along the center line of the border, clockwise from the top-left corner
+    along: f32,
+    // The distance from the center line of the border, positive outwards
+    across: f32,
+    // The length of the center line of the border
+    perimeter: f32,
+    // The width of the border at the point
+    width: f32,
+}
+
+// Returns the signed distance to the join of the sides at the nearest corner
+//
+// The join goes from the outer corner to the inner corner, the distance is positive on the
+// side of the horizontal edge.
+// `point` is relative to the center of the box, with the y axis up.
+// `widths` are `[top, right, bottom, left]`.
+fn join_distance(point: vec2<f32>, half_size: vec2<f32>, widths: vec4<f32>) -> f32 {
+    let s = select(vec2(-1.0), vec2(1.0), point >= vec2(0.0));
+    let horizontal = select(widths.z, widths.x, point.y >= 0.0);
+    let vertical = select(widths.w, widths.y, point.x >= 0.0);
+
+    let outer = s * half_size;
+    let normal = vec2(-s.x * horizontal, s.y * vertical);
+
+    return dot(point - outer, normal) / max(length(normal), 1e-6);
+}
+
+// Returns the linear color of the border at the point
+//
+// `rg` and `ba` are the red and green, the blue and alpha of `[top, right, bottom, left]` as
+// sRGB `float16x2`, the colors of the adjacent sides are blended along their join.
+fn border_side_color(point: vec2<f32>, half_size: vec2<f32>, widths: vec4<f32>, rg: vec4<u32>, ba: vec4<u32>) -> vec4<f32> {
+    if (all(rg == rg.xxxx) && all(ba == ba.xxxx)) {
+        return srgba_to_linear(unpack_srgba(rg.x, ba.x));
+    }
+
+    let horizontal = select(BOTTOM, TOP, point.y >= 0.0);
+    let vertical = select(LEFT, RIGHT, point.x >= 0.0);
+    let factor = smoothstep(-0.5, 0.5, join_distance(point, half_size, widths));
+
+    return mix(
+        srgba_to_linear(unpack_srgba(rg[vertical], ba[vertical])),
+        srgba_to_linear(unpack_srgba(rg[horizontal], ba[horizontal])),
+        factor,
+    );
+}
+
+// Locates the point on the center line of the border
+//
+// `corner_radii` are the outer radii of `[top_left, top_right, bottom_right, bottom_left]`.
+fn border_point(point: vec2<f32>, half_size: vec2<f32>, widths: vec4<f32>, corner_radii: vec4<f32>) -> BorderPoint {
+    // the center line: `[top, right, bottom, left]`
+    let edges = vec4(
+        half_size.y - widths.x * 0.5,
+        half_size.x - widths.y * 0.5,
+        widths.z * 0.5 - half_size.y,
+        widths.w * 0.5 - half_size.x,
+    );
+    let inner_half_size = max(vec2(edges.y - edges.w, edges.x - edges.z) * 0.5, vec2(0.0));
+    let radii = clamp(
+        corner_radii - 0.5 * max(widths.wxyz, widths.xyzw),
+        vec4(0.0),
+        vec4(min(inner_half_size.x, inner_half_size.y)),
+    );
+    let arcs = radii * HALF_PI;
+
+    // the lengths of the straight sides
+    let top = max(edges.y - radii.y - edges.w - radii.x, 0.0);
+    let right = max(edges.x - radii.y - edges.z - radii.z, 0.0);
+    let bottom = max(edges.y - radii.z - edges.w - radii.w, 0.0);
+    let left = max(edges.x - radii.x - edges.z - radii.w, 0.0);
+
+    // starts at the middle of the top-left corner, so the pattern is centered on it
+    let top_start = arcs.x * 0.5;
+    let top_right_start = top_start + top;
+    let right_start = top_right_start + arcs.y;
+    let bottom_right_start = right_start + right;
+    let bottom_start = bottom_right_start + arcs.z;
+    let bottom_left_start = bottom_start + bottom;
+    let left_start = bottom_left_start + arcs.w;
+    let top_left_start = left_start + left;
+    let perimeter = top + right + bottom + left + arcs.x + arcs.y + arcs.z + arcs.w;
+
+    let horizontal = select(BOTTOM, TOP, point.y >= 0.0);
+    let vertical = select(LEFT, RIGHT, point.x >= 0.0);
+    let width = max(widths[horizontal], widths[vertical]);
+
+    // the rounded corners
+    if (point.x >= 0.0 && point.y >= 0.0) {
+        let d = point - vec2(edges.y - radii.y, edges.x - radii.y);
+        if (radii.y > 0.0 && all(d >= vec2(0.0))) {
+            let along = top_right_start + atan2(d.x, d.y) * radii.y;
+            return BorderPoint(along, length(d) - radii.y, perimeter, width);
+        }
+    } else if (point.x >= 0.0) {
+        let d = point - vec2(edges.y - radii.z, edges.z + radii.z);
+        if (radii.z > 0.0 && d.x >= 0.0 && d.y <= 0.0) {
+            let along = bottom_right_start + atan2(-d.y, d.x) * radii.z;
+            return BorderPoint(along, length(d) - radii.z, perimeter, width);
+        }
+    } else if (point.y < 0.0) {
+        let d = point - vec2(edges.w + radii.w, edges.z + radii.w);
+        if (radii.w > 0.0 && all(d <= vec2(0.0))) {
+            let along = bottom_left_start + atan2(-d.x, -d.y) * radii.w;
+            return BorderPoint(along, length(d) - radii.w, perimeter, width);
+        }
+    } else {
+        let d = point - vec2(edges.w + radii.x, edges.x - radii.x);
+        if (radii.x > 0.0 && d.x <= 0.0 && d.y >= 0.0) {
+            let along = top_left_start + atan2(d.y, -d.x) * radii.x;
+            return BorderPoint(along, length(d) - radii.x, perimeter, width);
+        }
+    }
+
+    // the straight sides
+    let side = select(vertical, horizontal, join_distance(point, half_size, widths) >= 0.0);
+
+    switch side {
+        case TOP: {
+            let along = top_start + point.x - edges.w - radii.x;
+            return BorderPoint(along, point.y - edges.x, perimeter, widths.x);
+        }
+        case RIGHT: {
+            let along = right_start + edges.x - radii.y - point.y;
+            return BorderPoint(along, point.x - edges.y, perimeter, widths.y);
+        }
+        case BOTTOM: {
+            let along = bottom_start + edges.y - radii.z - point.x;
+            return BorderPoint(along, edges.z - point.y, perimeter, widths.z);
+        }
+        default: {
+            let along = left_start + point.y - edges.z - radii.w;
+            return BorderPoint(along, edges.w - point.x, perimeter, widths.w);
+        }
+    }
+}
+
+// Returns the distance to the nearest repeat of the pattern along the border
+//
+// The period is stretched, so the pattern repeats a whole number of times.
+fn pattern_distance(along: f32, perimeter: f32, period: f32) -> vec2<f32> {
+    let count = max(round(perimeter / max(period, 1e-6)), 1.0);
+    let stretched = perimeter / count;
+    let distance = abs(fract(along / stretched + 0.5) - 0.5) * stretched;
+
+    return vec2(distance, stretched / max(period, 1e-6));
+}
+
+// Returns the coverage of the border by its style
+fn border_style_coverage(point: vec2<f32>, half_size: vec2<f32>, widths: vec4<f32>, corner_radii: vec4<f32>, style: vec4<f32>) -> f32 {
+    let kind = u32(style.x);
+
+    if (kind == SOLID) {
+        return 1.0;
+    }
+
+    let border = border_point(point, half_size, widths, corner_radii);
+
+    switch kind {
+        case DASHED: {
+            let pattern = pattern_distance(border.along, border.perimeter, style.y + style.z);
+            let half_length = style.y * pattern.y * 0.5;
+            return 1.0 - smoothstep(half_length - 0.5, half_length + 0.5, pattern.x);
+        }
+        case DOTTED: {
+            let pattern = pattern_distance(border.along, border.perimeter, style.y + style.z);
+            let radius = min(style.y, border.width) * 0.5;
+            let distance = length(vec2(pattern.x, border.across)) - radius;
+            return 1.0 - smoothstep(-0.5, 0.5, distance);
+        }
+        case DOUBLE: {
+            // the middle third is the gap between the lines
+            let half_gap = border.width / 6.0;
+            return smoothstep(half_gap - 0.5, half_gap + 0.5, abs(border.across));
+        }
+        default: {
+            return 1.0;
+        }
+    }
+}
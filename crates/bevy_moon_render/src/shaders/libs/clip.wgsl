@@ -0,0 +1,48 @@
+#define_import_path bevy_moon::clip
+
+#import bevy_moon::quad::get_corner_index
+#import bevy_moon::utils::aa_s
+#import bevy_moon::rectangles::sd_rounded_box
+
+// Transforms a world position into the local spaces of the two clipping regions,
+// relative to their centers
+//
+// Returns `[first.x, first.y, second.x, second.y]`.
+fn to_clip_space(
+    world_position: vec4<f32>,
+    first_x: vec4<f32>,
+    first_y: vec4<f32>,
+    second_x: vec4<f32>,
+    second_y: vec4<f32>,
+) -> vec4<f32> {
+    return vec4(
+        dot(first_x, world_position),
+        dot(first_y, world_position),
+        dot(second_x, world_position),
+        dot(second_y, world_position),
+    );
+}
+
+// Returns the coverage of the point by a clipping region
+//
+// `rect` is `[half_size.x, half_size.y, top_left and top_right, bottom_right and bottom_left]`,
+// the bits of the half size and the corner radii as float16x2,
+// a negative half size disables the region.
+fn region_coverage(point: vec2<f32>, rect: vec4<u32>) -> f32 {
+    let half_size = bitcast<vec2<f32>>(rect.xy);
+
+    if (half_size.x < 0.0) {
+        return 1.0;
+    }
+
+    let corner_radii = vec4(unpack2x16float(rect.z), unpack2x16float(rect.w));
+    let radius = min(corner_radii[get_corner_index(point)], min(half_size.x, half_size.y));
+    let distance = sd_rounded_box(point, half_size, max(radius, 0.0));
+
+    return aa_s(distance);
+}
+
+// Returns the coverage of the points by the two clipping regions, see `to_clip_space`
+fn clip_coverage(points: vec4<f32>, first: vec4<u32>, second: vec4<u32>) -> f32 {
+    return min(region_coverage(points.xy, first), region_coverage(points.zw, second));
+}
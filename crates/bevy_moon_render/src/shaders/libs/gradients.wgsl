@@ -0,0 +1,222 @@
+#define_import_path bevy_moon::gradients
+
+#import bevy_moon::maths::PI_2
+
+// The kinds of the gradients
+const NONE   = 0u;
+const LINEAR = 1u;
+const RADIAL = 2u;
+const CONIC  = 3u;
+
+// The color spaces of the interpolation
+const SRGBA       = 0u;
+const LINEAR_RGBA = 1u;
+const HSLA        = 2u;
+const OKLABA      = 3u;
+const OKLCHA      = 4u;
+
+// Targets the border color instead of the background
+const BORDER = 256u;
+
+// `params` is `[flags, center, positions, reserved]`
+//
+// - `flags`: the kind in the bits `0..2`, the color space in `2..5`, the count of the stops
+//   in `5..8`, the border target in `8` and the angle in turns as `unorm16` in `16..32`.
+// - `center`: the fractions of the box from its top-left corner as `unorm16x2`.
+// - `positions`: the positions of the stops as `unorm8x4`.
+//
+// `rg` and `ba` are the red and green, the blue and alpha of the stops as sRGB `float16x2`,
+// which keep the smooth gradients from banding and the HDR colors from clamping.
+
+fn is_gradient(params: vec4<u32>) -> bool {
+    return (params.x & 3u) != NONE;
+}
+
+fn is_border_gradient(params: vec4<u32>) -> bool {
+    return (params.x & BORDER) != 0u;
+}
+
+// Returns the position along the gradient line
+//
+// `point` is relative to the center of the box, with the y axis up.
+fn gradient_position(params: vec4<u32>, point: vec2<f32>, size: vec2<f32>) -> f32 {
+    let angle = f32(params.x >> 16u) / 65535.0 * PI_2;
+    let center = (unpack2x16unorm(params.y) - 0.5) * vec2(1.0, -1.0) * size;
+
+    switch params.x & 3u {
+        case LINEAR: {
+            // clockwise from the top
+            let direction = vec2(sin(angle), cos(angle));
+            // the gradient line passes through the corners
+            let line_length = abs(size.x * direction.x) + abs(size.y * direction.y);
+            return dot(point, direction) / max(line_length, 1e-6) + 0.5;
+        }
+        case RADIAL: {
+            // a circle which reaches the farthest corner
+            let radius = length(abs(center) + size * 0.5);
+            return length(point - center) / max(radius, 1e-6);
+        }
+        case CONIC: {
+            let p = point - center;
+            return fract((atan2(p.x, p.y) - angle) / PI_2);
+        }
+        default: {
+            return 0.0;
+        }
+    }
+}
+
+// Returns the linear color at the position along the gradient line
+fn gradient_color(params: vec4<u32>, rg: vec4<u32>, ba: vec4<u32>, t: f32) -> vec4<f32> {
+    let space = (params.x >> 2u) & 7u;
+    let count = (params.x >> 5u) & 7u;
+    let positions = unpack4x8unorm(params.z);
+
+    if (count == 0u) {
+        return vec4(0.0);
+    }
+
+    if (t <= positions[0]) {
+        return srgba_to_linear(unpack_srgba(rg[0], ba[0]));
+    }
+
+    for (var i = 1u; i < count; i++) {
+        let end = positions[i];
+
+        if (t <= end) {
+            let start = positions[i - 1u];
+            let factor = select(1.0, (t - start) / (end - start), end > start);
+
+            let first = unpack_srgba(rg[i - 1u], ba[i - 1u]);
+            let second = unpack_srgba(rg[i], ba[i]);
+
+            return mix_colors(first, second, factor, space);
+        }
+    }
+
+    return srgba_to_linear(unpack_srgba(rg[count - 1u], ba[count - 1u]));
+}
+
+// Unpacks the sRGB color of a stop
+fn unpack_srgba(rg: u32, ba: u32) -> vec4<f32> {
+    return vec4(unpack2x16float(rg), unpack2x16float(ba));
+}
+
+// Mixes the sRGB colors in the color space, returns a linear color
+fn mix_colors(a: vec4<f32>, b: vec4<f32>, t: f32, space: u32) -> vec4<f32> {
+    let alpha = mix(a.a, b.a, t);
+
+    switch space {
+        case SRGBA: {
+            return srgba_to_linear(mix(a, b, t));
+        }
+        case LINEAR_RGBA: {
+            return vec4(mix(srgb_to_linear(a.rgb), srgb_to_linear(b.rgb), t), alpha);
+        }
+        case HSLA: {
+            let first = srgb_to_hsl(a.rgb);
+            let second = srgb_to_hsl(b.rgb);
+            let hsl = vec3(mix_hue(first.x, second.x, t), mix(first.yz, second.yz, t));
+            return vec4(srgb_to_linear(hsl_to_srgb(hsl)), alpha);
+        }
+        case OKLABA: {
+            let first = linear_to_oklab(srgb_to_linear(a.rgb));
+            let second = linear_to_oklab(srgb_to_linear(b.rgb));
+            return vec4(oklab_to_linear(mix(first, second, t)), alpha);
+        }
+        default: {
+            let first = oklab_to_oklch(linear_to_oklab(srgb_to_linear(a.rgb)));
+            let second = oklab_to_oklch(linear_to_oklab(srgb_to_linear(b.rgb)));
+            let lch = vec3(mix(first.xy, second.xy, t), mix_hue(first.z, second.z, t));
+            return vec4(oklab_to_linear(oklch_to_oklab(lch)), alpha);
+        }
+    }
+}
+
+// Mixes the hues in turns along the shorter arc
+fn mix_hue(a: f32, b: f32, t: f32) -> f32 {
+    let delta = fract(b - a + 0.5) - 0.5;
+    return fract(a + delta * t);
+}
+
+fn srgba_to_linear(color: vec4<f32>) -> vec4<f32> {
+    return vec4(srgb_to_linear(color.rgb), color.a);
+}
+
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    let low = color / 12.92;
+    let high = pow((color + 0.055) / 1.055, vec3(2.4));
+    return select(high, low, color <= vec3(0.04045));
+}
+
+// Returns `[hue in turns, saturation, lightness]`
+fn srgb_to_hsl(color: vec3<f32>) -> vec3<f32> {
+    let high = max(color.r, max(color.g, color.b));
+    let low = min(color.r, min(color.g, color.b));
+    let lightness = (high + low) * 0.5;
+    let delta = high - low;
+
+    if (delta <= 0.0) {
+        return vec3(0.0, 0.0, lightness);
+    }
+
+    let saturation = delta / (1.0 - abs(2.0 * lightness - 1.0));
+
+    var hue: f32;
+    if (high == color.r) {
+        hue = (color.g - color.b) / delta;
+    } else if (high == color.g) {
+        hue = (color.b - color.r) / delta + 2.0;
+    } else {
+        hue = (color.r - color.g) / delta + 4.0;
+    }
+
+    return vec3(fract(hue / 6.0), saturation, lightness);
+}
+
+fn hsl_to_srgb(hsl: vec3<f32>) -> vec3<f32> {
+    let k = (vec3(0.0, 8.0, 4.0) + hsl.x * 12.0) % 12.0;
+    let a = hsl.y * min(hsl.z, 1.0 - hsl.z);
+    return hsl.z - a * clamp(min(k - 3.0, 9.0 - k), vec3(-1.0), vec3(1.0));
+}
+
+// <https://bottosson.github.io/posts/oklab/>
+fn linear_to_oklab(color: vec3<f32>) -> vec3<f32> {
+    let lms = vec3(
+        dot(color, vec3(0.4122214708, 0.5363325363, 0.0514459929)),
+        dot(color, vec3(0.2119034982, 0.6806995451, 0.1073969566)),
+        dot(color, vec3(0.0883024619, 0.2817188376, 0.6299787005)),
+    );
+    let lms_cbrt = sign(lms) * pow(abs(lms), vec3(1.0 / 3.0));
+
+    return vec3(
+        dot(lms_cbrt, vec3(0.2104542553, 0.7936177850, -0.0040720468)),
+        dot(lms_cbrt, vec3(1.9779984951, -2.4285922050, 0.4505937099)),
+        dot(lms_cbrt, vec3(0.0259040371, 0.7827717662, -0.8086757660)),
+    );
+}
+
+fn oklab_to_linear(lab: vec3<f32>) -> vec3<f32> {
+    let lms_cbrt = vec3(
+        dot(lab, vec3(1.0, 0.3963377774, 0.2158037573)),
+        dot(lab, vec3(1.0, -0.1055613458, -0.0638541728)),
+        dot(lab, vec3(1.0, -0.0894841775, -1.2914855480)),
+    );
+    let lms = lms_cbrt * lms_cbrt * lms_cbrt;
+
+    return vec3(
+        dot(lms, vec3(4.0767416621, -3.3077115913, 0.2309699292)),
+        dot(lms, vec3(-1.2684380046, 2.6097574011, -0.3413193965)),
+        dot(lms, vec3(-0.0041960863, -0.7034186147, 1.7076147010)),
+    );
+}
+
+// Returns `[lightness, chroma, hue in turns]`
+fn oklab_to_oklch(lab: vec3<f32>) -> vec3<f32> {
+    return vec3(lab.x, length(lab.yz), fract(atan2(lab.z, lab.y) / PI_2));
+}
+
+fn oklch_to_oklab(lch: vec3<f32>) -> vec3<f32> {
+    let hue = lch.z * PI_2;
+    return vec3(lch.x, lch.y * cos(hue), lch.y * sin(hue));
+}
@@ -2,6 +2,13 @@
 
 #import bevy_moon::maths::{AA_T, SQRT_2}
 
+// Returns the coverage of the edges of the quad, about one pixel wide.
+fn wireframe(uv: vec2<f32>) -> f32 {
+    let width = max(fwidth(uv), vec2(1e-6));
+    let distance = min(uv, 1.0 - uv) / width;
+    return 1.0 - smoothstep(0.5, 1.5, min(distance.x, distance.y));
+}
+
 fn is_all3(v: vec3<f32>) -> bool {
     return all(v != vec3(0.0));
 }
@@ -7,26 +7,55 @@
     get_inset_by_index,
     to_uv,
 }
-#import bevy_moon::utils::{is_all3, is_empty4}
+#import bevy_moon::utils::{is_empty4, wireframe}
 #import bevy_moon::utils::{aa_c, aa_s}
 #import bevy_moon::rectangles::{sd_rounded_box, sd_inset_rounded_box}
 #import bevy_moon::atlas
+#import bevy_moon::clip::{to_clip_space, clip_coverage}
+#import bevy_moon::gradients::{
+    is_gradient,
+    is_border_gradient,
+    gradient_position,
+    gradient_color,
+    srgba_to_linear,
+    unpack_srgba,
+}
+#import bevy_moon::borders::{border_side_color, border_style_coverage}
 
 @group(0) @binding(0) var<uniform> view: View;
 
 struct VertexInput {
     @builtin(vertex_index) vertex_id: u32,
 
+    // the z axis is left out, the quads are flat, the width and the height are in the w lanes
     @location(0) x_axis: vec4<f32>,
     @location(1) y_axis: vec4<f32>,
-    @location(2) z_axis: vec4<f32>,
-    @location(3) w_axis: vec4<f32>,
-
-    @location(4) color: vec4<f32>,
-    @location(5) size: vec2<f32>,
-    @location(6) corner_radii: vec4<f32>,
-    @location(7) border_color: vec4<f32>,
-    @location(8) border_widths: vec4<f32>,
+    @location(2) w_axis: vec4<f32>,
+
+    // [color red and green, color blue and alpha, style, dash length or dot size and gap],
+    // the color as sRGB float16x2 and the lengths as float16x2
+    @location(3) color_style: vec4<u32>,
+    // [top_left and top_right, bottom_right and bottom_left] corner radii and
+    // [top and right, bottom and left] border widths as float16x2
+    @location(4) radii_widths: vec4<u32>,
+    // the red and green, the blue and alpha of [top, right, bottom, left] as sRGB float16x2
+    @location(5) border_rg: vec4<u32>,
+    @location(6) border_ba: vec4<u32>,
+
+    // the clipping regions, from the outermost to the innermost: clip_from_world [x_row, y_row]
+    // and [half_size.x, half_size.y, top_left and top_right, bottom_right and bottom_left]
+    @location(7) first_clip_x: vec4<f32>,
+    @location(8) first_clip_y: vec4<f32>,
+    @location(9) first_clip_rect: vec4<u32>,
+    @location(10) second_clip_x: vec4<f32>,
+    @location(11) second_clip_y: vec4<f32>,
+    @location(12) second_clip_rect: vec4<u32>,
+
+    // [flags, center, positions, reserved]
+    @location(13) gradient: vec4<u32>,
+    // the red and green, the blue and alpha of the stops as sRGB float16x2
+    @location(14) gradient_rg: vec4<u32>,
+    @location(15) gradient_ba: vec4<u32>,
 };
 
 struct VertexOutput {
@@ -38,8 +67,19 @@ struct VertexOutput {
     @location(2) @interpolate(flat) color: vec4<f32>,
     @location(3) @interpolate(flat) size: vec2<f32>,
     @location(4) @interpolate(flat) corner_radii: vec4<f32>,
-    @location(5) @interpolate(flat) border_color: vec4<f32>,
-    @location(6) @interpolate(flat) border_widths: vec4<f32>,
+    @location(5) @interpolate(flat) border_rg: vec4<u32>,
+    @location(6) @interpolate(flat) border_ba: vec4<u32>,
+    @location(7) @interpolate(flat) border_widths: vec4<f32>,
+
+    // [first.x, first.y, second.x, second.y]
+    @location(8) clip_points: vec4<f32>,
+    @location(9) @interpolate(flat) first_clip_rect: vec4<u32>,
+    @location(10) @interpolate(flat) second_clip_rect: vec4<u32>,
+
+    @location(11) @interpolate(flat) gradient: vec4<u32>,
+    @location(12) @interpolate(flat) gradient_rg: vec4<u32>,
+    @location(13) @interpolate(flat) gradient_ba: vec4<u32>,
+    @location(14) @interpolate(flat) border_style: vec4<f32>,
 };
 
 @vertex
@@ -48,28 +88,62 @@ fn vertex(in: VertexInput) -> VertexOutput {
     let vertex = get_vertex_by_index(vertex_index);
 
     let uv = to_uv(vertex_index);
-    let local_position = vertex * in.size;
-    let world_from_local = vec4(local_position, 0.0, 1.0);
-    let matrix = mat4x4(in.x_axis, in.y_axis, in.z_axis, in.w_axis);
-    let world_position = matrix * world_from_local;
+    let size = vec2(in.x_axis.w, in.y_axis.w);
+    let local_position = vertex * size;
+    let x_axis = vec4(in.x_axis.xyz, 0.0);
+    let y_axis = vec4(in.y_axis.xyz, 0.0);
+    let world_position = x_axis * local_position.x + y_axis * local_position.y + in.w_axis;
     let clip_position = view.clip_from_world * world_position;
+    let clip_points = to_clip_space(
+        world_position,
+        in.first_clip_x,
+        in.first_clip_y,
+        in.second_clip_x,
+        in.second_clip_y,
+    );
 
     return VertexOutput(
         clip_position,
         uv,
         local_position,
-        in.color,
-        in.size,
-        in.corner_radii,
-        in.border_color,
-        in.border_widths,
+        srgba_to_linear(unpack_srgba(in.color_style.x, in.color_style.y)),
+        size,
+        vec4(unpack2x16float(in.radii_widths.x), unpack2x16float(in.radii_widths.y)),
+        in.border_rg,
+        in.border_ba,
+        vec4(unpack2x16float(in.radii_widths.z), unpack2x16float(in.radii_widths.w)),
+        clip_points,
+        in.first_clip_rect,
+        in.second_clip_rect,
+        in.gradient,
+        in.gradient_rg,
+        in.gradient_ba,
+        vec4(f32(in.color_style.z), unpack2x16float(in.color_style.w), 0.0),
     );
 }
 
-@fragment
-fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+fn shade(in: VertexOutput) -> vec4<f32> {
     var color = in.color;
 
+    // position relative to the center of the box
+    let point = in.local_position;
+    let half_size = in.size * 0.5;
+
+    // The colors of the sides are blended along the joins at the corners.
+    var border_color = border_side_color(point, half_size, in.border_widths, in.border_rg, in.border_ba);
+
+    // A gradient replaces the background or the border color.
+    if (is_gradient(in.gradient)) {
+        let t = gradient_position(in.gradient, point, in.size);
+        let gradient = gradient_color(in.gradient, in.gradient_rg, in.gradient_ba, t);
+
+        if (is_border_gradient(in.gradient)) {
+            border_color = gradient;
+        } else {
+            color = gradient;
+        }
+    }
+
     let corner_radii = in.corner_radii;
     let border_widths = in.border_widths;
 
@@ -81,25 +155,25 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
         return color;
     }
 
-    // position relative to the center of the box
-    let point = in.local_position;
     let corner_index = get_corner_index(point);
     
     let tl = get_inset_by_index(border_widths, 0); // TopLeft
     let br = get_inset_by_index(border_widths, 2); // BottomRight
     let cb = get_inset_by_index(border_widths, corner_index); // Current corner border
- 
-    let half_size = in.size * 0.5;
+
     let radius = corner_radii[corner_index];
-    let border_color = in.border_color;
 
-    // If there's a border color and border width we need to calculate the inner sdf.
-    if (is_all3(vec3(cb, border_color.a))) {
+    // If there's a border color and border width we need to calculate the inner sdf,
+    // a side may have no width, such as a tab with only a top border.
+    if (any(cb != vec2(0.0)) && border_color.a > 0.0) {
         // inner sdf
         let internal_distance = sd_inset_rounded_box(point, half_size, radius, tl, br, cb);
 
+        // The dashes, the dots or the double lines of the border.
+        let coverage = border_style_coverage(point, half_size, border_widths, corner_radii, in.border_style);
+
         // Blend in the color with the border color.
-        color = mix(color, border_color, smoothstep(-0.5, 0.5, internal_distance));
+        color = mix(color, border_color, smoothstep(-0.5, 0.5, internal_distance) * coverage);
     }
 
     // If there's no corner radius, we don't need to do any anti aliasing.
@@ -123,3 +197,29 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
 
     return color;
 }
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+#ifdef WIREFRAME
+    let edge = wireframe(in.uv);
+
+    if (edge <= 0.0) {
+        discard;
+    }
+
+    return vec4(0.0, 1.0, 0.0, edge);
+#else
+    let coverage = clip_coverage(in.clip_points, in.first_clip_rect, in.second_clip_rect);
+
+    // fast path
+    if (coverage <= 0.0) {
+        discard;
+    }
+
+    var color = shade(in);
+
+    color.a *= coverage;
+
+    return color;
+#endif
+}
@@ -11,6 +11,8 @@
     get_inset_by_index,
 }
 #import bevy_moon::rectangles::{sd_rounded_box}
+#import bevy_moon::clip::{to_clip_space, clip_coverage}
+#import bevy_moon::utils::{aa_s, wireframe}
 
 @group(0) @binding(0) var<uniform> view: View;
 
@@ -111,6 +113,18 @@ struct VertexInput {
     @location(5) size: vec2<f32>,
     @location(6) corner_radii: vec4<f32>,
     @location(7) blur_radius: f32,
+
+    // the clipping regions, from the outermost to the innermost: clip_from_world [x_row, y_row]
+    // and [half_size.x, half_size.y, top_left and top_right, bottom_right and bottom_left]
+    @location(8) first_clip_x: vec4<f32>,
+    @location(9) first_clip_y: vec4<f32>,
+    @location(10) first_clip_rect: vec4<u32>,
+    @location(11) second_clip_x: vec4<f32>,
+    @location(12) second_clip_y: vec4<f32>,
+    @location(13) second_clip_rect: vec4<u32>,
+
+    // [inset, offset.x, offset.y, spread]
+    @location(14) inset: vec4<f32>,
 };
 
 struct VertexOutput {
@@ -122,6 +136,13 @@ struct VertexOutput {
     @location(2) @interpolate(flat) size: vec2<f32>,
     @location(3) @interpolate(flat) corner_radii: vec4<f32>,
     @location(4) @interpolate(flat) blur_radius: f32,
+
+    // [first.x, first.y, second.x, second.y]
+    @location(5) clip_points: vec4<f32>,
+    @location(6) @interpolate(flat) first_clip_rect: vec4<u32>,
+    @location(7) @interpolate(flat) second_clip_rect: vec4<u32>,
+
+    @location(8) @interpolate(flat) inset: vec4<f32>,
 };
 
 @vertex
@@ -129,13 +150,21 @@ fn vertex(in: VertexInput) -> VertexOutput {
     let vertex_index = normalize_vertex_index(in.vertex_id);
     let vertex = get_vertex_by_index(vertex_index);
 
-    let margin = in.blur_radius * 3.0;
+    // an inset shadow stays inside the padding box
+    let margin = select(in.blur_radius * 3.0, 0.0, in.inset.x > 0.0);
     let bounds = in.size + margin * 2.0; // shadow bounds
     let local_position = vertex * bounds;
     let world_from_local = vec4(local_position, 0.0, 1.0);
     let matrix = mat4x4(in.x_axis, in.y_axis, in.z_axis, in.w_axis);
     let world_position = matrix * world_from_local;
     let clip_position = view.clip_from_world * world_position;
+    let clip_points = to_clip_space(
+        world_position,
+        in.first_clip_x,
+        in.first_clip_y,
+        in.second_clip_x,
+        in.second_clip_y,
+    );
 
     return VertexOutput(
         clip_position,
@@ -144,11 +173,18 @@ fn vertex(in: VertexInput) -> VertexOutput {
         in.size,
         in.corner_radii,
         in.blur_radius,
+        clip_points,
+        in.first_clip_rect,
+        in.second_clip_rect,
+        in.inset,
     );
 }
 
-@fragment
-fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+fn shade(in: VertexOutput) -> vec4<f32> {
+    if (in.inset.x > 0.0) {
+        return shade_inset(in);
+    }
+
     let half_size = in.size * 0.5;
     let point = in.local_position;
     let blur_radius = in.blur_radius;
@@ -166,3 +202,65 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
 
     return color;
 }
+
+// The shadow of the hole, which is the padding box moved by the offset and shrunk by the spread,
+// so the blur and the spread grow the shadow inwards.
+fn shade_inset(in: VertexOutput) -> vec4<f32> {
+    let half_size = in.size * 0.5;
+    let point = in.local_position;
+    let offset = in.inset.yz;
+    let spread = in.inset.w;
+
+    // clipped by the rounded padding box
+    let radius = in.corner_radii[get_corner_index(point)];
+    let inside = aa_s(sd_rounded_box(point, half_size, radius));
+
+    let hole_point = point - offset;
+    let hole_half_size = max(half_size - spread, vec2(0.0));
+    let hole_radius = clamp(
+        in.corner_radii[get_corner_index(hole_point)] - spread,
+        0.0,
+        min(hole_half_size.x, hole_half_size.y),
+    );
+
+    var hole: f32;
+    if (in.blur_radius > 0.0) {
+        hole = blur7(hole_point, hole_half_size, hole_radius, in.blur_radius);
+    } else {
+        hole = aa_s(sd_rounded_box(hole_point, hole_half_size, hole_radius));
+    }
+
+    var color = in.color;
+    color.a *= (1.0 - hole) * inside;
+
+    return color;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+#ifdef WIREFRAME
+    // The instance is inflated by the blur, unless it is inset.
+    let bounds = in.size + select(in.blur_radius * 6.0, 0.0, in.inset.x > 0.0);
+    let uv = in.local_position / bounds + 0.5;
+    let edge = wireframe(uv);
+
+    if (edge <= 0.0) {
+        discard;
+    }
+
+    return vec4(1.0, 0.0, 1.0, edge);
+#else
+    let coverage = clip_coverage(in.clip_points, in.first_clip_rect, in.second_clip_rect);
+
+    // fast path
+    if (coverage <= 0.0) {
+        discard;
+    }
+
+    var color = shade(in);
+
+    color.a *= coverage;
+
+    return color;
+#endif
+}
@@ -4,11 +4,14 @@ use bevy::{
     camera_controller::pan_camera::{PanCamera, PanCameraPlugin},
     color::palettes::css::{ANTIQUE_WHITE, BLACK, BLUE, DEEP_SKY_BLUE, GRAY, GREEN, RED, WHITE},
     prelude::*,
+    sprite::Anchor,
 };
 
 use lucide_icons::Icon;
 
-use bevy_moon::prelude::{Corners, MoonPlugin, ObjectPosition, div, img, text};
+use bevy_moon::prelude::{
+    Corners, Div, MoonDebugPlugin, MoonPlugin, ObjectPosition, UiRoot, div, img, text,
+};
 use taffy::{LengthPercentage, Rect};
 
 const LOOP_LENGTH: f32 = 4.0;
@@ -249,15 +252,26 @@ fn setup(
         Transform::from_xyz(150.0, 150.0, 0.0),
     ));
 
-    commands.spawn((
-        div()
-            .w(96.0)
-            .h(96.0)
-            .background(WHITE)
-            .corner_radii(Corners::all(25.0).top_left(0.0).bottom_right(0.0))
-            .shadow_sm(),
-        Transform::from_xyz(100.0, -100.0, 0.0),
-    ));
+    commands
+        .spawn((
+            div()
+                .w(96.0)
+                .h(96.0)
+                .background(WHITE)
+                .corner_radii(Corners::all(25.0).top_left(0.0).bottom_right(0.0))
+                .shadow_sm(),
+            Transform::from_xyz(100.0, -100.0, 0.0),
+        ))
+        .observe(|over: On<Pointer<Over>>, mut divs: Query<&mut Div>| {
+            if let Ok(mut div) = divs.get_mut(over.entity) {
+                div.background = Some(DEEP_SKY_BLUE.into());
+            }
+        })
+        .observe(|out: On<Pointer<Out>>, mut divs: Query<&mut Div>| {
+            if let Ok(mut div) = divs.get_mut(out.entity) {
+                div.background = Some(WHITE.into());
+            }
+        });
 
     commands.spawn((
         div().w(216.0).h(29.0).background(GRAY),
@@ -283,7 +297,8 @@ fn setup(
     ));
 
     commands.spawn((
-        div().w(250.0).flex().p_px().background(WHITE).shadow_lg(),
+        div().w_full().flex().p_px().background(WHITE).shadow_lg(),
+        UiRoot::new(Vec2::new(250.0, f32::INFINITY)).with_anchor(Anchor::TOP_LEFT),
         children![
           (
             div().w_full(),
@@ -292,9 +307,33 @@ fn setup(
             TextFont::default().with_font(font).with_font_size(24.0),
           ),
         ],
-        Transform::from_xyz(-450.0, 200.0, 0.0),
+        Transform::from_xyz(-575.0, 260.0, 0.0),
     ));
 
+    commands
+        .spawn((
+            div()
+                .w(160.0)
+                .h(96.0)
+                .flex_col()
+                .p_px()
+                .overflow_y_scroll()
+                .background(WHITE)
+                .corner_radii(Corners::all(8.0))
+                .shadow_sm(),
+            Transform::from_xyz(-450.0, -100.0, 0.0),
+        ))
+        .with_children(|parent| {
+            for i in 1..=12 {
+                parent.spawn((
+                    div().w_full(),
+                    text(format!("Log line {i}")),
+                    TextColor::BLACK,
+                    TextFont::default().with_font_size(16.0),
+                ));
+            }
+        });
+
     commands.spawn((
         Text2d::new(Icon::Bird.to_string()),
         TextColor::BLACK,
@@ -393,6 +432,8 @@ fn main() {
     app.add_plugins(default_plugins)
         .add_plugins(PanCameraPlugin)
         .add_plugins(MoonPlugin)
+        // Press F12 to toggle the debug overlay.
+        .add_plugins(MoonDebugPlugin)
         .add_systems(Startup, setup)
         .add_systems(
             Update,